@@ -12,6 +12,7 @@ use soroban_sdk::{
     token, Address, Env,
 };
 
+use crate::test::dust_goal_options;
 use crate::{CrowdfundContract, CrowdfundContractClient};
 
 // ── Setup Helpers ───────────────────────────────────────────────────────────
@@ -76,14 +77,25 @@ fn test_withdraw_only_creator_can_withdraw() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     // Create a contributor and make a contribution
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 1_000_000);
-    
+
     // Contribute requires the contributor's own authorization
-    client.contribute(&contributor, &1_000_000);
+    client.contribute(
+        &contributor,
+        &1_000_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
 
     assert_eq!(client.total_raised(), goal);
 
@@ -95,7 +107,7 @@ fn test_withdraw_only_creator_can_withdraw() {
 
     // Verify the withdrawal worked correctly
     assert_eq!(client.total_raised(), 0);
-    
+
     let token_client = token::Client::new(&env, &token_address);
     assert_eq!(token_client.balance(&creator), 10_000_000 + 1_000_000);
 }
@@ -124,18 +136,29 @@ fn test_contribute_requires_own_auth() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     // Test contribution with proper authorization
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 1_000_000);
-    
+
     // The contract requires contributor.require_auth() - only the contributor
     // address can authorize their own contribution
-    client.contribute(&contributor, &1_000_000);
+    client.contribute(
+        &contributor,
+        &1_000_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
 
     assert_eq!(client.total_raised(), 1_000_000);
-    
+
     // Verify the contribution was recorded for the correct contributor
     let contribution = client.contribution(&contributor);
     assert_eq!(contribution, 1_000_000);
@@ -166,6 +189,8 @@ fn test_initialize_requires_creator_auth() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     // Verify initialization was successful
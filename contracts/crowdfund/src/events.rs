@@ -0,0 +1,476 @@
+//! Typed event payloads published by the crowdfund contract.
+//!
+//! Every event is published under the topics `(campaign, <name>)`, both as
+//! `Symbol`s. Events carrying more than one value use the structs below so
+//! consumers can decode them by field name instead of tuple position.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, IntoVal, String, Symbol, Val};
+
+/// Publish `data` under the `(campaign, name)` topics.
+pub(crate) fn emit<D: IntoVal<Env, Val>>(env: &Env, name: &str, data: D) {
+    env.events()
+        .publish((symbol_short!("campaign"), Symbol::new(env, name)), data);
+}
+
+/// `contributed` / `contributed_for`: a contribution was accepted.
+///
+/// `payer` equals `contributor` except for `contribute_for` and
+/// `contribute_batch`, which credit a different beneficiary. `source_tag`
+/// is the tag the contribution was credited to, `other` for unregistered
+/// tags.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ContributedEvent {
+    pub contributor: Address,
+    pub payer: Address,
+    pub requested: i128,
+    pub accepted: i128,
+    pub total_raised: i128,
+    pub receipt_id: u64,
+    pub memo: Option<String>,
+    pub source_tag: Option<Symbol>,
+}
+
+/// `entry_fee_charged`: the platform fee was taken from a contribution as
+/// it arrived, in the contributed token. Only `net` is recorded.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct EntryFeeEvent {
+    pub contributor: Address,
+    pub gross: i128,
+    pub fee: i128,
+    pub net: i128,
+}
+
+/// `token_contributed`: a contribution was paid in a secondary token.
+/// Accompanies `contributed`, whose amounts are goal-denominated.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct TokenContributedEvent {
+    pub contributor: Address,
+    pub token_index: u32,
+    pub amount: i128,
+    pub credited: i128,
+}
+
+/// `goal_reached`: total raised met the goal for the first time.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct GoalReachedEvent {
+    pub goal: i128,
+    pub total_raised: i128,
+}
+
+/// `contribution_revealed`: an anonymous contribution was made public.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ContributionRevealedEvent {
+    pub contributor: Address,
+    pub amount: i128,
+}
+
+/// `referral`: a referrer was credited for a contribution.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ReferralEvent {
+    pub referrer: Address,
+    pub beneficiary: Address,
+    pub credited: i128,
+}
+
+/// `referral_ignored`: a referral was not credited; `reason` is one of the
+/// `REFERRAL_IGNORED_*` codes.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ReferralIgnoredEvent {
+    pub referrer: Address,
+    pub reason: u32,
+}
+
+/// `referral_reversed`: a refund took back referral credit from a
+/// referrer.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ReferralReversedEvent {
+    pub referrer: Address,
+    pub reversed: i128,
+}
+
+/// `prorated_refund`: the contract held less than the primary-token
+/// principal when refunds began, so a refund was paid at the ratio
+/// `balance / total_raised`.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ProratedRefundEvent {
+    pub contributor: Address,
+    pub contribution: i128,
+    pub refunded: i128,
+    pub balance: i128,
+    pub total_raised: i128,
+}
+
+/// `pledged` / `pledge_cancelled`: a pledge was recorded or withdrawn by
+/// its pledger.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct PledgedEvent {
+    pub pledger: Address,
+    pub amount: i128,
+    pub total_pledged: i128,
+    pub total_raised: i128,
+}
+
+/// `pledge_expired`: a stale pledge was dropped at collection.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct PledgeExpiredEvent {
+    pub pledger: Address,
+    pub amount: i128,
+}
+
+/// `pledge_flagged` / `pledge_voided`: a pledge exceeded the pledger's
+/// token balance at revalidation and was flagged, or voided after being
+/// flagged before.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct PledgeFlaggedEvent {
+    pub pledger: Address,
+    pub pledged: i128,
+    pub balance: i128,
+}
+
+/// `pledges_collected`: `caller` collected the outstanding pledges.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct PledgesCollectedEvent {
+    pub caller: Address,
+    pub collected: i128,
+    pub total_raised: i128,
+}
+
+/// `pledge_trimmed`: only part of a pledge was collected.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct PledgeTrimmedEvent {
+    pub pledger: Address,
+    pub collected: i128,
+    pub pledged: i128,
+}
+
+/// `installment_collected` / `installment_plan_cancelled`: a plan's
+/// installment was credited, or the plan was cancelled by its contributor.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct InstallmentEvent {
+    pub contributor: Address,
+    pub amount: i128,
+}
+
+/// `installment_failed`: a due installment could not be collected. The
+/// plan is dropped once `consecutive_failures` reaches two.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct InstallmentFailedEvent {
+    pub contributor: Address,
+    pub amount: i128,
+    pub consecutive_failures: u32,
+}
+
+/// `voucher_created` / `voucher_redeemed` / `voucher_reclaimed`: a voucher
+/// was escrowed by, redeemed by or returned to `account`.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct VoucherEvent {
+    pub code_hash: BytesN<32>,
+    pub account: Address,
+    pub amount: i128,
+}
+
+/// `balance_reconciled`: the contract held a different balance than
+/// `total_raised` at withdrawal.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct BalanceReconciledEvent {
+    pub total_raised: i128,
+    pub balance: i128,
+    pub delta: i128,
+}
+
+/// `fee_transferred`: the platform fee was paid out, or made claimable if
+/// its transfer failed.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct FeeTransferredEvent {
+    pub platform: Address,
+    pub fee: i128,
+}
+
+/// `fee_waived`: the platform permanently waived its fee for the campaign.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct FeeWaivedEvent {
+    pub platform: Address,
+    pub original_fee_bps: u32,
+}
+
+/// `withdrawn` / `vested_withdrawn` / `escrow_released`: funds were
+/// released to the creator. `total_raised` is read after the release.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct WithdrawnEvent {
+    pub creator: Address,
+    pub amount: i128,
+    pub total_raised: i128,
+}
+
+/// `token_withdrawn`: a secondary-token balance was released to the
+/// creator; `amount` includes any platform fee.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct TokenWithdrawnEvent {
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// `payout_failed` / `payout_claimed`: a queued payout could not be
+/// transferred and was made claimable, or was later claimed.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct PayoutEvent {
+    pub recipient: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// `expense_reported`: the creator reported how withdrawn funds were spent.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ExpenseReportedEvent {
+    pub amount: i128,
+    pub description: String,
+    pub receipt_hash: BytesN<32>,
+    pub total_reported: i128,
+}
+
+/// `surplus_claimed`: the creator claimed tokens set aside at withdrawal.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct SurplusClaimedEvent {
+    pub creator: Address,
+    pub amount: i128,
+}
+
+/// `refund_claimed`: a contributor pulled their refund. `total_raised` is
+/// what remains to be refunded to everyone else.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct RefundedEvent {
+    pub contributor: Address,
+    pub amount: i128,
+    pub total_raised: i128,
+}
+
+/// `contribution_reversed`: the platform clawed back part of a
+/// contribution after a chargeback, with a platform-defined `reason`.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ContributionReversedEvent {
+    pub contributor: Address,
+    pub amount: i128,
+    pub reason: u32,
+}
+
+/// `archive_totals`: a finished campaign's final accounting, re-emitted
+/// for off-chain archival.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ArchiveTotalsEvent {
+    pub total_raised: i128,
+    pub paid_to_creator: i128,
+    pub contributors: u32,
+}
+
+/// `archive_entry`: a contributor's final contribution and refunded
+/// amount, re-emitted for off-chain archival.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ArchiveEntryEvent {
+    pub contributor: Address,
+    pub amount: i128,
+    pub refunded: i128,
+}
+
+/// `unclaimed_swept`: refunds left unclaimed after the claim window were
+/// swept to the configured recipient.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct UnclaimedSweptEvent {
+    pub recipient: Address,
+    pub amount: i128,
+}
+
+/// `refund_batch`: a `refund_batch` call refunded `count` contributors.
+/// `total_raised` is what remains to be refunded.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct RefundBatchEvent {
+    pub count: u32,
+    pub refunded: i128,
+    pub total_raised: i128,
+}
+
+/// `checkpoint`: the campaign's authoritative totals, published by
+/// `finalize` and `extend_storage` so consumers can resync.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct CheckpointEvent {
+    pub total_raised: i128,
+    pub total_pledged: i128,
+    pub paid_to_creator: i128,
+    pub active_contributors: u32,
+}
+
+/// `keeper_bounty`: a keeper was paid for driving refunds.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct KeeperBountyEvent {
+    pub keeper: Address,
+    pub bounty: i128,
+}
+
+/// `withdrawal_approved` / `approval_revoked`: a contributor changed their
+/// withdrawal approval.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ApprovalEvent {
+    pub contributor: Address,
+    pub weight: i128,
+}
+
+/// `dispute_raised`: a contributor disputed the campaign.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct DisputeRaisedEvent {
+    pub contributor: Address,
+    pub reason_hash: BytesN<32>,
+    pub weight: i128,
+}
+
+/// `rate_limit_exempt_updated`: an address's cooldown exemption changed.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct RateLimitExemptEvent {
+    pub addr: Address,
+    pub exempt: bool,
+}
+
+/// `rate_limited`: a contribution from `addr` was bounced by its cooldown,
+/// which ends in `remaining_secs`. Emitted once per cooldown.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct RateLimitedEvent {
+    pub addr: Address,
+    pub remaining_secs: u64,
+}
+
+/// `token_changed`: the creator replaced the contribution token before
+/// any funds arrived.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct TokenChangedEvent {
+    pub old_token: Address,
+    pub new_token: Address,
+}
+
+/// `deadline_updated`: the creator moved the deadline.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct DeadlineUpdatedEvent {
+    pub old_deadline: u64,
+    pub new_deadline: u64,
+    pub extension_used: u64,
+}
+
+/// `goal_updated`: the creator lowered the goal.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct GoalUpdatedEvent {
+    pub old_goal: i128,
+    pub new_goal: i128,
+}
+
+/// `tier_changed`: a contribution or refund moved a backer into a
+/// different reward tier; `None` means no tier.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct TierChangedEvent {
+    pub contributor: Address,
+    pub old_tier: Option<String>,
+    pub new_tier: Option<String>,
+}
+
+/// `inactive_creator`: a contributor flagged the creator as inactive after
+/// a missed heartbeat.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct InactiveCreatorEvent {
+    pub contributor: Address,
+    pub last_heartbeat: u64,
+}
+
+/// `roadmap_item_added`: a roadmap item was appended.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct RoadmapItemAddedEvent {
+    pub date: u64,
+    pub description: String,
+}
+
+/// `reward_tier_added`: a reward tier was added.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct RewardTierAddedEvent {
+    pub name: String,
+    pub min_amount: i128,
+}
+
+/// `tier_delivery_updated`: the creator changed the delivery status of the
+/// reward tier at `index`.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct TierDeliveryUpdatedEvent {
+    pub index: u32,
+    pub name: String,
+    pub status: u32,
+}
+
+/// `bonus_skipped`: a contribution was accepted but did not earn `bonus`
+/// (e.g. `referral`); `reason` is one of the `BONUS_SKIPPED_*` codes.
+/// `contributor` is `None` for anonymous contributions.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct BonusSkippedEvent {
+    pub contributor: Option<Address>,
+    pub bonus: Symbol,
+    pub reason: u32,
+    pub eligible_ledger: u32,
+}
+
+/// `refund_pool_funded`: `from` topped up the refund pool; `funded` is the
+/// total top-up so far.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct RefundPoolFundedEvent {
+    pub from: Address,
+    pub amount: i128,
+    pub funded: i128,
+}
+
+/// `refund_topped_up` / `top_up_returned`: refund top-up was paid to a
+/// backer refunded short, or its unpaid remainder returned to a funder.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct RefundTopUpEvent {
+    pub recipient: Address,
+    pub amount: i128,
+}
@@ -1,8 +1,33 @@
 #![no_std]
 #![allow(missing_docs)]
+// Contract entry points accept up to 10 arguments; the generated client and
+// argument types mirror them, so the lint cannot be silenced per function.
+#![allow(clippy::too_many_arguments)]
+
+use soroban_sdk::{
+    contract, contractimpl, contracttype, token, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal,
+    Map, String, Symbol, TryFromVal, Val, Vec,
+};
+
+pub mod events;
+
+use events::{
+    ApprovalEvent, ArchiveEntryEvent, ArchiveTotalsEvent, BalanceReconciledEvent,
+    BonusSkippedEvent, CheckpointEvent, ContributedEvent, ContributionRevealedEvent,
+    ContributionReversedEvent, DeadlineUpdatedEvent, DisputeRaisedEvent, EntryFeeEvent,
+    ExpenseReportedEvent, FeeTransferredEvent, FeeWaivedEvent, GoalReachedEvent, GoalUpdatedEvent,
+    InactiveCreatorEvent, InstallmentEvent, InstallmentFailedEvent, KeeperBountyEvent, PayoutEvent,
+    PledgeExpiredEvent, PledgeFlaggedEvent, PledgeTrimmedEvent, PledgedEvent,
+    PledgesCollectedEvent, ProratedRefundEvent, RateLimitExemptEvent, RateLimitedEvent,
+    ReferralEvent, ReferralIgnoredEvent, ReferralReversedEvent, RefundBatchEvent,
+    RefundPoolFundedEvent, RefundTopUpEvent, RefundedEvent, RewardTierAddedEvent,
+    RoadmapItemAddedEvent, SurplusClaimedEvent, TierChangedEvent, TierDeliveryUpdatedEvent,
+    TokenChangedEvent, TokenContributedEvent, TokenWithdrawnEvent, UnclaimedSweptEvent,
+    VoucherEvent, WithdrawnEvent,
+};
 
-use soroban_sdk::{contract, contractimpl, contracttype, token, Address, Env, String, Symbol, Vec};
-
+#[cfg(test)]
+mod auth_tests;
 #[cfg(test)]
 mod test;
 
@@ -39,20 +64,429 @@ pub struct RoadmapItem {
     pub description: String,
 }
 
+/// When the platform fee is taken.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum FeeMode {
+    /// From each payout to the creator.
+    OnWithdraw,
+    /// From each contribution as it arrives; only the net is recorded, so
+    /// refunds return what backers actually risked.
+    OnContribute,
+}
+
+/// Contribution credit escrowed by `create_voucher`, redeemable by
+/// whoever presents the code.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct Voucher {
+    pub funder: Address,
+    pub amount: i128,
+    /// Whether the voucher was redeemed or reclaimed.
+    pub spent: bool,
+}
+
+/// Order `refund_batch` refunds contributors in.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[contracttype]
+pub enum RefundOrder {
+    /// Earliest contributors first.
+    #[default]
+    Fifo,
+    /// Most recent contributors first.
+    Lifo,
+    /// Earliest first, with every refund a proportional share of the
+    /// frozen balance even when it covers the principal.
+    ProRataOnly,
+}
+
 /// Platform configuration for fee handling.
 #[derive(Clone)]
 #[contracttype]
 pub struct PlatformConfig {
     pub address: Address,
     pub fee_bps: u32,
+    pub fee_mode: FeeMode,
+}
+
+/// Circuit breaker pausing the campaign when more than `max_inflow` is
+/// contributed within `window_secs`.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct CircuitBreaker {
+    pub max_inflow: i128,
+    pub window_secs: u64,
+}
+
+/// Recurring contribution of `amount_per_period` every `period_secs`,
+/// collected by `execute_installments` from the contributor's allowance.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct InstallmentPlan {
+    pub amount_per_period: i128,
+    pub period_secs: u64,
+    /// Ledger timestamp from which the next installment can be collected.
+    pub next_due: u64,
+    /// Installments in a row that could not be collected.
+    pub failures: u32,
+}
+
+/// Optional campaign behaviour configured at initialization.
+///
+/// Every field defaults to "disabled", so `CampaignOptions::default()`
+/// keeps the standard campaign behaviour.
+#[derive(Clone, Default)]
+#[contracttype]
+pub struct CampaignOptions {
+    /// Second signer that must authorize `withdraw` alongside the creator.
+    pub withdraw_cosigner: Option<Address>,
+    /// Share of `TotalRaised` (in basis points) that must approve the
+    /// withdrawal via `approve_withdrawal`. `0` disables the vote.
+    pub approval_required_bps: u32,
+    /// Seconds after the deadline during which approvals may be cast. If the
+    /// threshold is not reached in time, contributors may `claim_refund`.
+    /// `0` leaves the vote open indefinitely.
+    pub approval_window_secs: u64,
+    /// Campaign admin, stored under `DataKey::Admin` (upgrades, disputes).
+    pub admin: Option<Address>,
+    /// Seconds after the deadline before `withdraw` is allowed, during which
+    /// contributors may `raise_dispute`.
+    pub withdraw_delay_secs: u64,
+    /// Share of `TotalRaised` (in basis points) that disputes must exceed to
+    /// freeze withdrawal pending `resolve_dispute`. `0` never freezes.
+    pub dispute_threshold_bps: u32,
+    /// Length of the linear vesting schedule that starts at the deadline.
+    /// When non-zero, payouts go through `withdraw_vested` instead of
+    /// `withdraw`.
+    pub vest_duration_secs: u64,
+    /// Seconds after the deadline before anything vests.
+    pub cliff_secs: u64,
+    /// Accept a goal smaller than one whole token (`10^decimals` base
+    /// units), which is usually a unit mistake.
+    pub allow_dust_goal: bool,
+    /// Timestamp before which `contribute` and `pledge` are rejected with
+    /// `CampaignNotStarted`. `0` opens the campaign immediately.
+    pub start_time: u64,
+    /// Cap on the cumulative time `update_deadline` may add to the original
+    /// deadline. `0` uses `DEFAULT_MAX_EXTENSION_SECS`.
+    pub max_total_extension_secs: u64,
+    /// Contributions below this amount do not earn referral credit.
+    pub min_referral_amount: i128,
+    /// Only credit referrers who contributed at least `min_contribution`.
+    pub require_referrer_contributed: bool,
+    /// Cap on any single referrer's tally as a share of `TotalRaised`, in
+    /// basis points. `0` disables the cap.
+    pub max_referral_share_bps: u32,
+    /// Share of each batch refunded via `refund_batch` paid to the keeper
+    /// that triggered it, in basis points (at most `MAX_KEEPER_BOUNTY_BPS`).
+    pub keeper_bounty_bps: u32,
+    /// Pledges older than this many seconds are dropped by
+    /// `collect_pledges` instead of collected. `0` never expires pledges.
+    pub pledge_valid_secs: u64,
+    /// Timestamp after which new pledges are rejected (must be before the
+    /// deadline). `0` accepts pledges until the deadline.
+    pub pledge_deadline: u64,
+    /// Stop `collect_pledges` once the goal is met: the pledge that crosses
+    /// it is collected only in part and later pledges are voided.
+    pub collect_pledges_to_goal: bool,
+    /// Factory that deployed the campaign. Its admin may archive the
+    /// campaign, and `archive` notifies it.
+    pub factory: Option<Address>,
+    /// Seconds after the campaign enters `Refunded` during which refunds
+    /// can be claimed. Afterwards the admin may sweep unclaimed funds to
+    /// `sweep_recipient`. `0` keeps refunds claimable forever.
+    pub refund_claim_window_secs: u64,
+    /// Recipient of funds swept by `sweep_unclaimed`; required when
+    /// `refund_claim_window_secs` is set.
+    pub sweep_recipient: Option<Address>,
+    /// Up to two further tokens accepted by `contribute` (token indices 1
+    /// and 2), each counted toward the goal at a static rate. `None` keeps
+    /// the campaign single-token.
+    pub secondary_tokens: Option<Vec<AcceptedToken>>,
+    /// SEP-40 price feed quoting the token in USD. When set, goal checks
+    /// compare the USD value of `TotalRaised` against `goal_usd`.
+    pub oracle: Option<Address>,
+    /// Goal in USD, with the token's decimals. Required with `oracle`.
+    pub goal_usd: i128,
+    /// Oldest oracle price, in seconds, still used for goal checks; older
+    /// prices fall back to the token-denominated goal. `0` uses
+    /// `DEFAULT_ORACLE_MAX_AGE_SECS`.
+    pub oracle_max_age_secs: u64,
+    /// Attestation contract whose `is_verified(addr)` must return true for
+    /// contributors and pledgers. `None` disables the KYC gate.
+    pub attestation_contract: Option<Address>,
+    /// Seconds a positive attestation is cached before the attestation
+    /// contract is asked again. `0` checks on every contribution.
+    pub attestation_cache_secs: u64,
+    /// Seconds after the deadline after which a campaign that missed its
+    /// goal expires: contributions, pledges and withdrawals fail with
+    /// `CampaignExpired` and anyone may call `trigger_auto_refund`. `0`
+    /// disables expiry.
+    pub auto_refund_after_secs: u64,
+    /// Seconds before the deadline after which `update_goal` is rejected.
+    /// `0` uses `DEFAULT_GOAL_UPDATE_BUFFER_SECS`.
+    pub goal_update_buffer_secs: u64,
+    /// Ceiling on any one address's contribution. `0` disables the cap.
+    pub max_contribution_per_address: i128,
+    /// Count outstanding pledges toward `max_contribution_per_address`, so
+    /// contribution plus pledge share one ceiling. Otherwise each is capped
+    /// on its own.
+    pub pledges_count_toward_cap: bool,
+    /// Report each credited referral to the factory's platform-wide
+    /// referral program. Requires `factory`.
+    pub report_referrals_to_factory: bool,
+    /// Seconds within which the creator must `heartbeat` once the campaign
+    /// is Successful. Past `HEARTBEAT_GRACE_BPS` of it, contributors may
+    /// `flag_inactive`. `0` disables heartbeats.
+    pub heartbeat_interval_secs: u64,
+    /// Ledgers an address must have been known to the campaign before its
+    /// contributions earn referral credit. `0` disables the check and the
+    /// first-seen tracking.
+    pub min_first_seen_ledgers: u32,
+    /// Share of a `withdraw` (in basis points) held back until rewards
+    /// ship and released by `release_escrow`. Requires an admin and
+    /// `escrow_release_delay_secs`. `0` pays out in full.
+    pub fulfillment_escrow_bps: u32,
+    /// Seconds after `withdraw` before the creator may `release_escrow`
+    /// without backer approval.
+    pub escrow_release_delay_secs: u64,
+    /// Contributions made during the cooldown are still accepted while the
+    /// payer's total for the current cooldown window stays within this
+    /// amount, so small corrections are not throttled. `0` disables it.
+    pub cooldown_bypass_amount: i128,
+    /// Seconds before the deadline within which an unmet goal raises the
+    /// `deadline_near` creator alert. `0` uses `DEFAULT_ALERT_WINDOW_SECS`.
+    pub alert_window_secs: u64,
+    /// Void a pledge `revalidate_pledges` finds underfunded on two
+    /// consecutive checks. Otherwise underfunded pledges are only flagged.
+    pub void_underfunded_pledges: bool,
+    /// Distinct contributors still backing the campaign required, besides
+    /// the goal, for it to succeed. `0` disables the quorum.
+    pub min_contributors: u32,
+}
+
+/// Asset identifier of the SEP-40 price-feed interface.
+#[derive(Clone)]
+#[contracttype]
+pub enum OracleAsset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+/// A price quoted by a SEP-40 price feed, scaled by its `decimals()`.
+#[derive(Clone)]
+#[contracttype]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// A secondary token accepted by a multi-token campaign.
+///
+/// Each unit of `token` counts as `rate / RATE_SCALE` units of the primary
+/// token toward the goal, hard cap and every other goal-denominated total.
+#[derive(Clone)]
+#[contracttype]
+pub struct AcceptedToken {
+    pub token: Address,
+    pub rate: i128,
+}
+
+/// Raw amount of a secondary token taken in, and the goal-denominated
+/// amount it was credited as.
+#[derive(Clone, Default)]
+#[contracttype]
+pub struct TokenTally {
+    pub raised: i128,
+    pub credited: i128,
+}
+
+/// Content hashes (IPFS/Arweave) of the campaign's cover image and
+/// gallery.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct Media {
+    pub cover: BytesN<32>,
+    pub gallery: Vec<BytesN<32>>,
+}
+
+/// A link to one of the campaign's social profiles.
+#[derive(Clone)]
+#[contracttype]
+pub struct SocialLink {
+    pub platform: Symbol,
+    pub url: String,
 }
 
 /// A reward tier with a name and minimum contribution amount to qualify.
+///
+/// `estimated_delivery` is a timestamp after the deadline, or 0 when the
+/// creator gave no estimate. `delivery_status` is 0 (pending),
+/// 1 (in production), 2 (shipped) or 3 (delivered).
 #[derive(Clone)]
 #[contracttype]
 pub struct RewardTier {
     pub name: String,
     pub min_amount: i128,
+    pub estimated_delivery: u64,
+    pub delivery_status: u32,
+}
+
+/// `RewardTier` as stored before delivery tracking; read by
+/// `load_reward_tiers` and rewritten by `migrate`.
+#[derive(Clone)]
+#[contracttype]
+struct LegacyRewardTier {
+    name: String,
+    min_amount: i128,
+}
+
+/// A public message attached to a contribution.
+///
+/// `contributor` is `None` when the contribution was made anonymously.
+#[derive(Clone)]
+#[contracttype]
+pub struct MemoEntry {
+    pub contributor: Option<Address>,
+    pub memo: String,
+    pub timestamp: u64,
+}
+
+/// A record of a single accepted contribution.
+///
+/// `contributor` is `None` when the contribution was made anonymously or
+/// the contributor has since been forgotten; a forgotten `referral` reads
+/// `None` too.
+#[derive(Clone)]
+#[contracttype]
+pub struct Receipt {
+    pub id: u64,
+    pub contributor: Option<Address>,
+    pub amount: i128,
+    pub timestamp: u64,
+    pub referral: Option<Address>,
+}
+
+/// An expenditure reported by the creator after withdrawing funds.
+#[derive(Clone)]
+#[contracttype]
+pub struct Expense {
+    pub amount: i128,
+    pub description: String,
+    pub receipt_hash: soroban_sdk::BytesN<32>,
+    pub timestamp: u64,
+}
+
+/// An update posted by the creator to the campaign page.
+#[derive(Clone)]
+#[contracttype]
+pub struct UpdatePost {
+    pub content: String,
+    pub timestamp: u64,
+}
+
+/// A transfer queued by `withdraw` and paid out by `settle`. `principal`
+/// marks the creator's payout of the primary token.
+#[derive(Clone)]
+#[contracttype]
+pub struct Payout {
+    pub recipient: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub principal: bool,
+}
+
+/// Primary-token balance and principal frozen when refunds begin, and how
+/// much of each has been refunded since.
+///
+/// `funded` is the top-up added by `fund_refund_pool` on top of the frozen
+/// balance, `topped_up` how much of it backers have received, and
+/// `bounties` the keeper bounties withheld from refunds so far.
+#[derive(Clone)]
+#[contracttype]
+pub struct RefundPool {
+    pub balance: i128,
+    pub principal: i128,
+    pub refunded: i128,
+    pub paid: i128,
+    pub bounties: i128,
+    pub funded: i128,
+    pub topped_up: i128,
+    pub funders: Map<Address, i128>,
+}
+
+/// Outcome of `can_contribute`. `reason` is the `ContractError` code the
+/// contribution would fail with, or `0` when `ok`.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct ContributeCheck {
+    pub ok: bool,
+    pub accepted_amount: i128,
+    pub reason: u32,
+}
+
+/// What `withdraw` would pay out right now, from `withdraw_preview`.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct WithdrawPreview {
+    /// Primary-token amount released, before fees.
+    pub gross: i128,
+    /// Share of `gross` paid to the platform.
+    pub platform_fee: i128,
+    /// Referral rewards paid out of `gross`. Referrals only keep a tally,
+    /// so this is currently always zero.
+    pub referral_rewards: i128,
+    /// Share of `gross` held in escrow until `release_escrow`.
+    pub escrow: i128,
+    /// Amount transferred to the creator.
+    pub net_to_creator: i128,
+}
+
+/// Everything the campaign records about one address, from
+/// `backer_profile`. Addresses that never interacted read zero / `None`.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct BackerProfile {
+    /// Current contribution, net of refunds and reversals.
+    pub contributed: i128,
+    /// Pledge not yet collected; `0` once the campaign closes.
+    pub pledged_outstanding: i128,
+    /// Total refunded, before any keeper bounty.
+    pub refunds_received: i128,
+    /// Timestamp of the first contribution (`0` if none).
+    pub first_contribution_ts: u64,
+    /// Reward tier the current contribution qualifies for.
+    pub tier: Option<String>,
+    /// Contribution amount credited to the address as a referrer.
+    pub referral_tally: i128,
+}
+
+/// Reward-fulfillment escrow held back by `withdraw`.
+///
+/// `raised` is `TotalRaised` at withdrawal, against which contributions
+/// weigh release approvals and arbitrated refunds.
+#[derive(Clone)]
+#[contracttype]
+pub struct Escrow {
+    pub amount: i128,
+    pub retained_at: u64,
+    pub raised: i128,
+    pub approved: i128,
+}
+
+/// Permanent record of a payout to the creator.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct WithdrawalReceipt {
+    pub timestamp: u64,
+    /// Primary-token amount released, before fees.
+    pub gross: i128,
+    pub platform_fee: i128,
+    pub referral_rewards: i128,
+    /// Share of `gross` held in escrow until `release_escrow`.
+    pub escrow_retained: i128,
+    pub net_to_creator: i128,
+    pub payout_address: Address,
 }
 
 /// Represents all storage keys used by the crowdfund contract.
@@ -63,9 +497,18 @@ pub struct CampaignStats {
     pub total_raised: i128,
     /// The funding goal.
     pub goal: i128,
-    /// Progress towards goal in basis points (10000 = 100%).
+    /// Progress towards goal in basis points (10000 = 100%), capped at
+    /// 10000.
     pub progress_bps: u32,
-    /// Number of contributors.
+    /// Uncapped progress towards goal in basis points.
+    pub progress_raw_bps: i128,
+    /// Progress beyond the goal in basis points of the goal.
+    pub overfunded_bps: i128,
+    /// Total raised in basis points of the hard cap.
+    pub cap_utilization_bps: i128,
+    /// Uncapped progress counting outstanding pledges, in basis points.
+    pub projected_progress_bps: i128,
+    /// Number of contributors who have not been refunded.
     pub contributor_count: u32,
     /// Average contribution amount.
     pub average_contribution: i128,
@@ -73,6 +516,28 @@ pub struct CampaignStats {
     pub largest_contribution: i128,
 }
 
+/// Campaign configuration and state for the `campaign_info` view.
+#[derive(Clone)]
+#[contracttype]
+pub struct CampaignInfo {
+    pub creator: Address,
+    pub token: Address,
+    pub goal: i128,
+    pub hard_cap: i128,
+    /// Timestamp contributions open at (`0` if open since initialization).
+    pub start_time: u64,
+    pub deadline: u64,
+    pub min_contribution: i128,
+    /// Contributor quorum required for success (`0` if none).
+    pub min_contributors: u32,
+    pub total_raised: i128,
+    pub status: Status,
+    pub refund_order: RefundOrder,
+    /// Content hash of the cover image, if media was set.
+    pub cover: Option<BytesN<32>>,
+    pub gallery: Vec<BytesN<32>>,
+}
+
 /// Represents all storage keys used by the crowdfund contract.
 #[derive(Clone)]
 #[contracttype]
@@ -113,7 +578,7 @@ pub enum DataKey {
     LastContributionTime(Address),
     /// Campaign description.
     Description,
-    /// Campaign social links.
+    /// Campaign social links (`Vec<SocialLink>`).
     SocialLinks,
     /// Platform configuration for fee handling.
     PlatformConfig,
@@ -129,12 +594,376 @@ pub enum DataKey {
     StretchGoals,
     /// Total amount referred by each referrer address.
     ReferralTally(Address),
+    /// Amount contributed anonymously by an address (hidden from public views).
+    AnonymousAmount(Address),
+    /// Aggregate amount contributed anonymously.
+    AnonymousTotal,
+    /// Most recent memo left by a contributor.
+    Memo(Address),
+    /// Ring buffer of the most recent memos (oldest first).
+    RecentMemos,
+    /// Decimals reported by the contribution token at initialization.
+    TokenDecimals,
+    /// Optional campaign behaviour (see `CampaignOptions`).
+    Options,
+    /// Contribution weight with which an address approved the withdrawal.
+    Approval(Address),
+    /// Total contribution weight that has approved the withdrawal.
+    ApprovedWeight,
+    /// Contribution weight with which an address disputed the campaign.
+    Dispute(Address),
+    /// Total contribution weight that has disputed the campaign.
+    DisputedWeight,
+    /// Whether disputes froze withdrawal pending admin resolution.
+    DisputeFrozen,
+    /// Whether the admin resolved the dispute in the creator's favour.
+    DisputeResolved,
+    /// Gross amount released to the creator through `withdraw_vested`.
+    VestedWithdrawn,
+    /// Cumulative seconds added to the deadline via `update_deadline`.
+    ExtensionUsed,
+    /// Whether an address is exempt from the contribution cooldown.
+    RateLimitExempt(Address),
+    /// Index into `Contributors` where the next `refund_batch` resumes.
+    RefundCursor,
+    /// Timestamp of a pledger's most recent pledge.
+    PledgeTime(Address),
+    /// Total of pledges dropped by `collect_pledges` as expired.
+    ExpiredPledges,
+    /// Tokens held above `TotalRaised` at withdrawal, claimable by the creator.
+    Surplus,
+    /// Net amount raised per day bucket (`timestamp / 86_400`).
+    DailyTotals,
+    /// Tier a contributor qualified for when the campaign succeeded.
+    TierSnapshot(Address),
+    /// Contributors who qualified for a tier when the campaign succeeded.
+    TierRoster(String),
+    /// Whether the `goal_reached` event has been emitted.
+    GoalReachedEmitted,
+    /// Number of contributors whose contribution has not been refunded.
+    ActiveContributorCount,
+}
+
+/// Storage keys for contribution receipts and creator reporting.
+///
+/// Kept apart from `DataKey`, which is at the contract spec's 50-variant
+/// limit. Variant names must not collide with `DataKey`'s.
+#[derive(Clone)]
+#[contracttype]
+pub enum RecordKey {
+    /// Receipt for a single accepted contribution, by sequential id.
+    Receipt(u64),
+    /// Number of receipts issued so far (also the next receipt id).
+    ReceiptCount,
+    /// Total transferred to the creator by withdrawals and surplus claims.
+    PaidToCreator,
+    /// Expenses reported by the creator (`Vec<Expense>`).
+    Expenses,
+    /// Sum of all reported expense amounts.
+    ExpensesReported,
+    /// Whether the campaign has been archived.
+    Archived,
+    /// Ledger timestamp at which the campaign entered `Refunded`.
+    RefundedAt,
+    /// A contributor's secondary-token tallies (`Map<u32, TokenTally>`,
+    /// keyed by token index).
+    TokenContribution(Address),
+    /// Campaign-wide secondary-token tallies (`Map<u32, TokenTally>`).
+    TokenTotals,
+    /// Contribution refunded to an address, before any keeper bounty.
+    Refunded(Address),
+    /// Cached positive attestation: the attestation contract that
+    /// verified the address and when.
+    KycVerified(Address),
+    /// Contribution demand turned away by the hard cap.
+    ExcessDemand,
+    /// Updates posted by the creator (`Vec<UpdatePost>`).
+    UpdatePosts,
+    /// Index into `UpdatePosts` of the pinned update.
+    PinnedUpdate,
+    /// Referral credit earned through a contributor's contributions
+    /// (`Map<Address, i128>`, keyed by referrer).
+    ReferralCredits(Address),
+    /// Payouts queued by `withdraw` and not yet settled (`Vec<Payout>`).
+    PendingPayouts,
+    /// Payouts whose transfer failed, claimable by the recipient
+    /// (`Map<Address, i128>`, keyed by token).
+    ClaimablePayouts(Address),
+    /// Refund pool frozen when refunds begin (`RefundPool`).
+    RefundPool,
+    /// Reward tier an address last qualified for, as announced by
+    /// `tier_changed`.
+    KnownTier(Address),
+    /// Timestamp of the creator's last heartbeat, starting when the
+    /// campaign turned Successful.
+    LastHeartbeat,
+    /// Whether a contributor flagged the creator as inactive since their
+    /// last heartbeat.
+    CreatorInactive,
+    /// Hash of the `initialize` arguments, including the init nonce.
+    InitHash,
+    /// Ledger sequence at which an address first contributed or pledged.
+    FirstSeen(Address),
+    /// A contributor's refund shortfall and how much of it was topped up.
+    RefundShortfall(Address),
+    /// Reward-fulfillment escrow held since `withdraw` (`Escrow`).
+    Escrow,
+    /// Contribution weight with which a backer approved releasing the
+    /// escrow.
+    EscrowApproval(Address),
+    /// Start of an address's current cooldown window and its total
+    /// contributed within it (`(u64, i128)`).
+    CooldownWindow(Address),
+    /// Companion badge contract minting backer badges for the campaign.
+    BadgeContract,
+    /// Timestamp of an address's first contribution.
+    FirstContribution(Address),
+    /// Creator alerts already announced by an `alert` event (`ALERT_*`
+    /// bit flags).
+    AlertsRaised,
+    /// Total contributed under a marketing source tag.
+    SourceTally(Symbol),
+    /// Whether the creator registered a source tag.
+    SourceTagRegistered(Symbol),
+    /// Source tags that have been credited, in first-seen order.
+    SourceTags,
+    /// Index of the next contributor `emit_archive_snapshot` will export.
+    ArchiveCursor,
+    /// Timestamp at which `revalidate_pledges` first found a pledge
+    /// exceeding the pledger's balance.
+    PledgeFlagged(Address),
+    /// Pledgers currently flagged as underfunded.
+    FlaggedPledges,
+    /// Amount accepted for a contribution submitted with an idempotency
+    /// key, and when (`(i128, u64)`).
+    IdempotencyKey(BytesN<32>),
+    /// Receipt of the `withdraw` payout (`WithdrawalReceipt`).
+    WithdrawalReceipt,
+    /// Receipts of each `withdraw_vested` payout, in order.
+    VestedReceipts,
+    /// Ledger timestamp at which the campaign was initialized.
+    InitializedAt,
+    /// Cap on active contributors set by `set_max_contributors`.
+    MaxContributors,
+    /// Whether an address asked to be forgotten with `forget_me`.
+    Forgotten(Address),
+    /// Number of addresses removed from `Contributors` by `forget_me`.
+    ForgottenCount,
+    /// Inflow limit set by `set_circuit_breaker` (`CircuitBreaker`).
+    CircuitBreaker,
+    /// Start of the current inflow window and the amount contributed
+    /// within it (`(u64, i128)`).
+    InflowWindow,
+    /// A contributor's installment plan (`InstallmentPlan`).
+    InstallmentPlan(Address),
+    /// Addresses that have set up an installment plan, in order.
+    InstallmentPlans,
+    /// sha256 of the fields light clients poll, kept by
+    /// `refresh_state_digest` (`BytesN<32>`).
+    StateDigest,
+    /// Refund ordering chosen with `set_refund_order` (`RefundOrder`).
+    RefundOrder,
+}
+
+/// Storage keys for vouchers, campaign media, pledge collection, cooldown
+/// settings and hits, fee waivers and per-address contribution days.
+///
+/// Kept apart from `DataKey` and `RecordKey`, which are both at the
+/// contract spec's 50-variant limit. Variant names must not collide with
+/// theirs.
+#[derive(Clone)]
+#[contracttype]
+pub enum ExtraKey {
+    /// A voucher by the sha256 of its code (`Voucher`).
+    Voucher(BytesN<32>),
+    /// Primary-token amount held for unspent vouchers.
+    VoucherEscrow,
+    /// Cover image and gallery set with `set_media` (`Media`).
+    Media,
+    /// Whether the creator allowed an address to `collect_pledges`.
+    PledgeKeeper(Address),
+    /// Seconds after the deadline before pledges can be collected.
+    PledgeGraceSecs,
+    /// Whether addresses holding no contribution skip the cooldown.
+    SkipCooldownForNew,
+    /// Platform `fee_bps` before `waive_platform_fee` zeroed it.
+    WaivedFeeBps,
+    /// An address's net credit per day still held in `DailyTotals`
+    /// (`Map<u64, i128>`), so refunds are booked against those days.
+    ContributionDays(Address),
+    /// Contributions bounced by the cooldown, counted once per address per
+    /// cooldown (`u32`).
+    RateLimitHits,
+    /// An address's bounced contributions and the end of the cooldown its
+    /// last one was counted in (`(u32, u64)`).
+    RateLimitHitsFor(Address),
 }
 
 // ── Rate Limiting ──────────────────────────────────────────────────────────
 /// Minimum seconds required between contributions from the same address.
 const CONTRIBUTION_COOLDOWN: u64 = 5;
 
+// ── Deadline Extensions ─────────────────────────────────────────────────────
+/// Default cap on cumulative deadline extensions (30 days).
+const DEFAULT_MAX_EXTENSION_SECS: u64 = 30 * 24 * 60 * 60;
+
+// ── Teardown ────────────────────────────────────────────────────────────────
+/// Seconds after initialization during which the creator may tear down an
+/// unfunded campaign (24 hours).
+const TEARDOWN_WINDOW_SECS: u64 = 24 * 60 * 60;
+
+// ── Goal Decreases ──────────────────────────────────────────────────────────
+/// Default window before the deadline in which the goal is frozen (7 days).
+const DEFAULT_GOAL_UPDATE_BUFFER_SECS: u64 = 7 * 24 * 60 * 60;
+/// A lowered goal must stay at least this share of `TotalRaised`, in basis
+/// points, so a creator cannot cut the goal to what is already raised.
+const GOAL_FLOOR_BPS: i128 = 12_000;
+
+// ── Fulfillment Escrow ──────────────────────────────────────────────────────
+/// Seconds after withdrawal before the admin may arbitrate an escrow the
+/// creator could not release (180 days).
+const ESCROW_ARBITRATION_SECS: u64 = 180 * 24 * 60 * 60;
+
+// ── Referrals ───────────────────────────────────────────────────────────────
+/// `referral_ignored` reason: the referrer is the payer or beneficiary.
+const REFERRAL_IGNORED_SELF: u32 = 1;
+/// `referral_ignored` reason: the referrer is the creator or platform.
+const REFERRAL_IGNORED_CAMPAIGN_PARTY: u32 = 2;
+/// `referral_ignored` reason: the contribution is below `min_referral_amount`.
+const REFERRAL_IGNORED_BELOW_MINIMUM: u32 = 3;
+/// `referral_ignored` reason: the referrer has not contributed the minimum.
+const REFERRAL_IGNORED_NOT_CONTRIBUTOR: u32 = 4;
+/// `referral_ignored` reason: the referrer's tally is at its share cap.
+const REFERRAL_IGNORED_CAP_REACHED: u32 = 5;
+
+// ── Account Age ─────────────────────────────────────────────────────────────
+/// `bonus_skipped` reason: the payer was first seen fewer than
+/// `min_first_seen_ledgers` ledgers ago.
+const BONUS_SKIPPED_ACCOUNT_TOO_NEW: u32 = 1;
+
+// ── Reward Delivery ─────────────────────────────────────────────────────────
+/// `delivery_status`: rewards have not gone into production yet.
+const DELIVERY_PENDING: u32 = 0;
+/// `delivery_status`: the rewards have reached backers; the last status.
+const DELIVERY_DELIVERED: u32 = 3;
+
+// ── Metadata Limits ─────────────────────────────────────────────────────────
+/// Maximum campaign title length in bytes.
+const MAX_TITLE_LEN: u32 = 100;
+/// Maximum campaign description length in bytes.
+const MAX_DESCRIPTION_LEN: u32 = 2_000;
+/// Maximum combined length of all social link URLs in bytes.
+const MAX_SOCIALS_LEN: u32 = 500;
+/// Maximum length of a single social link URL in bytes.
+const MAX_SOCIAL_URL_LEN: u32 = 200;
+/// Maximum number of social links.
+const MAX_SOCIAL_LINKS: u32 = 10;
+/// Maximum number of update posts.
+const MAX_UPDATE_POSTS: u32 = 100;
+/// Maximum length of the category in bytes.
+const MAX_CATEGORY_LEN: u32 = 50;
+/// Maximum length of each tag in bytes.
+const MAX_TAG_LEN: u32 = 32;
+/// Maximum number of tags.
+const MAX_TAGS: u32 = 10;
+/// Maximum number of gallery images.
+const MAX_GALLERY_ITEMS: u32 = 12;
+/// Maximum number of reward tiers.
+const MAX_REWARD_TIERS: u32 = 20;
+/// Maximum number of roadmap items.
+const MAX_ROADMAP_ITEMS: u32 = 50;
+
+// ── Refunds ─────────────────────────────────────────────────────────────────
+/// Maximum keeper bounty on batch refunds (0.5%).
+const MAX_KEEPER_BOUNTY_BPS: u32 = 50;
+
+// ── Storage TTLs ────────────────────────────────────────────────────────────
+/// Average ledger close time, used to turn timestamps into ledger counts.
+const LEDGER_SECS: u64 = 5;
+/// Ledgers that records needed to settle the campaign outlive the deadline
+/// (~30 days).
+const ACTIVE_RECORD_BUFFER: u32 = 518_400;
+/// Seconds an idempotency key is remembered, and the ledgers its record
+/// lives (7 days).
+const IDEMPOTENCY_WINDOW_SECS: u64 = 7 * 24 * 60 * 60;
+const IDEMPOTENCY_KEY_TTL: u32 = (IDEMPOTENCY_WINDOW_SECS / LEDGER_SECS) as u32;
+/// Ledgers that post-settlement audit records live (~1 year), clamped to
+/// the network's maximum TTL.
+const AUDIT_RECORD_TTL: u32 = 6_307_200;
+
+// ── Heartbeats ──────────────────────────────────────────────────────────────
+/// Share of `heartbeat_interval_secs`, in basis points, that may pass
+/// without a heartbeat before the creator can be flagged inactive.
+const HEARTBEAT_GRACE_BPS: u64 = 15_000;
+
+/// Maximum number of beneficiaries credited by one `contribute_batch` call.
+const MAX_BATCH_ENTRIES: u32 = 20;
+
+/// Installments in a row that may fail before the plan is dropped.
+const MAX_INSTALLMENT_FAILURES: u32 = 2;
+
+// ── Source Tags ─────────────────────────────────────────────────────────────
+/// Maximum number of named source tags tracked; contributions under any
+/// further tag are credited to `other`.
+const MAX_SOURCE_TAGS: u32 = 50;
+
+// ── Creator Alerts ──────────────────────────────────────────────────────────
+/// Default window before the deadline for the `deadline_near` alert (24 h).
+const DEFAULT_ALERT_WINDOW_SECS: u64 = 24 * 60 * 60;
+/// Distance below the hard cap, in basis points, that raises `hard_cap_near`.
+const HARD_CAP_ALERT_BPS: i128 = 500;
+/// Alert flag: the goal is met.
+const ALERT_GOAL_REACHED: u32 = 1 << 0;
+/// Alert flag: the deadline is within the alert window and the goal unmet.
+const ALERT_DEADLINE_NEAR: u32 = 1 << 1;
+/// Alert flag: `TotalRaised` is within `HARD_CAP_ALERT_BPS` of the hard cap.
+const ALERT_HARD_CAP_NEAR: u32 = 1 << 2;
+
+// ── Health Checks ───────────────────────────────────────────────────────────
+/// Contributors and pledgers sampled by one `health_check` call.
+const HEALTH_SAMPLE_SIZE: u32 = 20;
+/// `health_violation`: the primary-token balance is below what was raised
+/// in it while Active.
+const HEALTH_BALANCE_SHORT: u32 = 1;
+/// `health_violation`: `total_raised` exceeds the hard cap.
+const HEALTH_OVER_HARD_CAP: u32 = 2;
+/// `health_violation`: the sampled contributors disagree with
+/// `contributor_count` while Active.
+const HEALTH_CONTRIBUTOR_COUNT: u32 = 3;
+/// `health_violation`: the sampled pledges disagree with `total_pledged`
+/// while Active.
+const HEALTH_PLEDGE_TOTAL: u32 = 4;
+/// `health_violation`: the campaign turned Successful before its deadline.
+const HEALTH_STATUS_DEADLINE: u32 = 5;
+
+// ── Memos ──────────────────────────────────────────────────────────────────
+/// Maximum length of a contribution memo in bytes.
+const MAX_MEMO_LEN: u32 = 140;
+/// Number of memos kept in the `RecentMemos` ring buffer.
+const MEMO_BUFFER_SIZE: u32 = 50;
+
+/// Length of a `daily_totals` bucket, in seconds.
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Maximum number of day buckets kept by `daily_totals`.
+const MAX_DAILY_BUCKETS: u32 = 90;
+
+// ── Multi-Token ─────────────────────────────────────────────────────────────
+/// Fixed-point scale of `AcceptedToken::rate` (1.0 == 10_000_000).
+pub const RATE_SCALE: i128 = 10_000_000;
+
+/// Maximum number of secondary tokens; with the primary token a campaign
+/// accepts at most three.
+const MAX_SECONDARY_TOKENS: u32 = 2;
+
+// ── Price Oracle ────────────────────────────────────────────────────────────
+/// Default maximum age of an oracle price used for goal checks (1 hour).
+const DEFAULT_ORACLE_MAX_AGE_SECS: u64 = 60 * 60;
+
+// ── Payout Settlement ───────────────────────────────────────────────────────
+/// Payouts `withdraw` settles itself; any further entries wait for `settle`.
+const MAX_SETTLE_BATCH: u32 = 10;
+
 // ── Contract Error ──────────────────────────────────────────────────────────
 
 use soroban_sdk::contracterror;
@@ -151,1029 +980,8133 @@ pub enum ContractError {
     Overflow = 6,
     InvalidHardCap = 7,
     HardCapExceeded = 8,
+    /// Only reported by `can_contribute`: the contribution paths accept 0
+    /// during the cooldown instead, so the hit is counted.
     RateLimitExceeded = 9,
     ContractPaused = 10,
     InvalidLimit = 11,
+    InvalidInput = 12,
+    InvalidToken = 13,
+    WithdrawalNotApproved = 14,
+    WithdrawTimelocked = 15,
+    WithdrawalDisputed = 16,
+    NothingVested = 17,
+    SuspiciouslySmallGoal = 18,
+    CampaignNotStarted = 19,
+    ExtensionLimitExceeded = 20,
+    MetadataTooLarge = 21,
+    CampaignNotActive = 22,
+    NoSurplus = 23,
+    ExpenseExceedsWithdrawn = 24,
+    RefundWindowClosed = 25,
+    RefundWindowOpen = 26,
+    UnsupportedToken = 27,
+    AlreadyRefunded = 28,
+    KycRequired = 29,
+    CampaignExpired = 30,
+    GoalUpdateTooLate = 31,
+    GoalBelowFloor = 32,
+    NothingToClaim = 33,
+    MaxContributionExceeded = 34,
+    BelowMinimum = 35,
+    HeartbeatNotOverdue = 36,
+    CreatorInactive = 37,
+    EscrowLocked = 38,
+    NotAuthorized = 39,
+    ArchiveCursorGap = 40,
+    InsufficientBalanceForPledge = 41,
+    DuplicateIdempotencyKey = 42,
+    CampaignHasFunds = 43,
+    QuorumNotMet = 44,
+    TeardownWindowClosed = 45,
+    ContributorLimitReached = 46,
+    SettlementPending = 47,
+    InvalidVoucher = 48,
 }
 
-// ── Contract ────────────────────────────────────────────────────────────────
-
-/// The main crowdfund contract implementation.
-#[contract]
-pub struct CrowdfundContract;
+// ── Internal Helpers ────────────────────────────────────────────────────────
+
+/// Seconds until `addr` may contribute again. Always `0` for addresses
+/// exempted via `set_rate_limit_exempt` (custodial wallets, on-ramps), and
+/// under `set_skip_cooldown_for_new` for addresses holding no contribution.
+fn cooldown_remaining_for(env: &Env, addr: &Address) -> u64 {
+    let exempt: bool = env
+        .storage()
+        .persistent()
+        .get(&DataKey::RateLimitExempt(addr.clone()))
+        .unwrap_or(false);
+    if exempt {
+        return 0;
+    }
+    let skip_new: bool = env
+        .storage()
+        .instance()
+        .get(&ExtraKey::SkipCooldownForNew)
+        .unwrap_or(false);
+    if skip_new
+        && env
+            .storage()
+            .persistent()
+            .get::<_, i128>(&DataKey::Contribution(addr.clone()))
+            .unwrap_or(0)
+            == 0
+    {
+        return 0;
+    }
+    match env
+        .storage()
+        .persistent()
+        .get::<_, u64>(&DataKey::LastContributionTime(addr.clone()))
+    {
+        Some(last_time) => last_time
+            .saturating_add(CONTRIBUTION_COOLDOWN)
+            .saturating_sub(env.ledger().timestamp()),
+        None => 0,
+    }
+}
 
-#[contractimpl]
-impl CrowdfundContract {
-    /// Initializes a new crowdfunding campaign.
-    ///
-    /// # Arguments
-    /// * `creator`            – The campaign creator's address.
-    /// * `token`              – The token contract address used for contributions.
-    /// * `goal`               – The funding goal (in the token's smallest unit).
-    /// * `hard_cap`           – Maximum total amount that can be raised (must be >= goal).
-    /// * `deadline`           – The campaign deadline as a ledger timestamp.
-    /// * `min_contribution`   – The minimum contribution amount.
-    /// * `platform_config`    – Optional platform configuration (address and fee in basis points).
-    ///
-    /// # Panics
-    /// * If already initialized.
-    /// * If platform fee exceeds 10,000 (100%).
-    #[allow(clippy::too_many_arguments)]
-    pub fn initialize(
-        env: Env,
-        creator: Address,
-        token: Address,
-        goal: i128,
-        _hard_cap: i128,
-        deadline: u64,
-        min_contribution: i128,
-        platform_config: Option<PlatformConfig>,
-    ) -> Result<(), ContractError> {
-        // Prevent re-initialization.
-        if env.storage().instance().has(&DataKey::Creator) {
-            return Err(ContractError::AlreadyInitialized);
+/// Total `addr` contributed in its current cooldown window (`0` once the
+/// window has passed).
+fn cooldown_window_total(env: &Env, addr: &Address) -> i128 {
+    match env
+        .storage()
+        .persistent()
+        .get::<_, (u64, i128)>(&RecordKey::CooldownWindow(addr.clone()))
+    {
+        Some((start, total))
+            if env.ledger().timestamp() < start.saturating_add(CONTRIBUTION_COOLDOWN) =>
+        {
+            total
         }
+        _ => 0,
+    }
+}
 
-        creator.require_auth();
+/// How much more `addr` may contribute during its cooldown under
+/// `cooldown_bypass_amount`.
+fn cooldown_bypass_remaining_for(env: &Env, addr: &Address) -> i128 {
+    let bypass = load_options(env).cooldown_bypass_amount;
+    (bypass - cooldown_window_total(env, addr)).max(0)
+}
 
-        // Validate platform fee if provided.
-        if let Some(ref config) = platform_config {
-            if config.fee_bps > 10_000 {
-                panic!("platform fee cannot exceed 100%");
-            }
+/// Credit `amount` to `referrer`'s referral tally, unless the referral is
+/// self-dealing, too small, or would push the referrer past the configured
+/// share of `total_raised`. Dropped referrals emit `referral_ignored` with a
+/// `REFERRAL_IGNORED_*` reason code.
+fn credit_referral(
+    env: &Env,
+    referrer: &Address,
+    payer: &Address,
+    beneficiary: &Address,
+    amount: i128,
+    total_raised: i128,
+    anonymous: bool,
+) -> Result<(), ContractError> {
+    let options = load_options(env);
+    let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+    let platform: Option<PlatformConfig> = env.storage().instance().get(&DataKey::PlatformConfig);
+
+    let referral_key = DataKey::ReferralTally(referrer.clone());
+    let current_tally: i128 = env.storage().persistent().get(&referral_key).unwrap_or(0);
+
+    let mut credited = amount;
+    let mut reason = None;
+    if referrer == beneficiary || referrer == payer {
+        reason = Some(REFERRAL_IGNORED_SELF);
+    } else if *referrer == creator || platform.is_some_and(|p| p.address == *referrer) {
+        reason = Some(REFERRAL_IGNORED_CAMPAIGN_PARTY);
+    } else if amount < options.min_referral_amount {
+        reason = Some(REFERRAL_IGNORED_BELOW_MINIMUM);
+    } else if options.require_referrer_contributed {
+        let min_contribution: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MinContribution)
+            .unwrap();
+        let referrer_contribution: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contribution(referrer.clone()))
+            .unwrap_or(0);
+        if referrer_contribution < min_contribution {
+            reason = Some(REFERRAL_IGNORED_NOT_CONTRIBUTOR);
+        }
+    }
+    if reason.is_none() && options.max_referral_share_bps > 0 {
+        let max_tally = total_raised
+            .checked_mul(options.max_referral_share_bps as i128)
+            .ok_or(ContractError::Overflow)?
+            / 10_000;
+        credited = credited.min(max_tally - current_tally);
+        if credited <= 0 {
+            reason = Some(REFERRAL_IGNORED_CAP_REACHED);
         }
+    }
 
-        env.storage().instance().set(&DataKey::Creator, &creator);
-        env.storage().instance().set(&DataKey::Token, &token);
+    if let Some(code) = reason {
+        events::emit(
+            env,
+            "referral_ignored",
+            ReferralIgnoredEvent {
+                referrer: referrer.clone(),
+                reason: code,
+            },
+        );
+        return Ok(());
+    }
 
-        env.storage().instance().set(&DataKey::Goal, &goal);
-        env.storage().instance().set(&DataKey::Deadline, &deadline);
-        env.storage()
-            .instance()
-            .set(&DataKey::MinContribution, &min_contribution);
-        env.storage().instance().set(&DataKey::TotalRaised, &0i128);
-        env.storage()
-            .instance()
-            .set(&DataKey::Status, &Status::Active);
-        env.storage().instance().set(&DataKey::Paused, &false);
+    let new_tally = current_tally
+        .checked_add(credited)
+        .ok_or(ContractError::Overflow)?;
+    env.storage().persistent().set(&referral_key, &new_tally);
+    extend_active_record(env, &referral_key);
+
+    // The factory aggregates referrals across campaigns. Its failure must
+    // never block a contribution.
+    if let Some(factory) = options
+        .factory
+        .filter(|_| options.report_referrals_to_factory)
+    {
+        let args: Vec<Val> =
+            (env.current_contract_address(), referrer.clone(), credited).into_val(env);
+        let _ = env.try_invoke_contract::<(), soroban_sdk::Error>(
+            &factory,
+            &Symbol::new(env, "record_referral"),
+            args,
+        );
+    }
 
-        let empty_contributors: Vec<Address> = Vec::new(&env);
+    // Remember who earned credit through this beneficiary so a refund can
+    // reverse it.
+    let credits_key = RecordKey::ReferralCredits(beneficiary.clone());
+    let mut credits: Map<Address, i128> = env
+        .storage()
+        .persistent()
+        .get(&credits_key)
+        .unwrap_or_else(|| Map::new(env));
+    let earned = credits.get(referrer.clone()).unwrap_or(0);
+    credits.set(
+        referrer.clone(),
+        earned
+            .checked_add(credited)
+            .ok_or(ContractError::Overflow)?,
+    );
+    env.storage().persistent().set(&credits_key, &credits);
+    extend_active_record(env, &credits_key);
+
+    // Emit referral event (skipped for anonymous contributions so the
+    // backer's address is never published).
+    if !anonymous {
+        events::emit(
+            env,
+            "referral",
+            ReferralEvent {
+                referrer: referrer.clone(),
+                beneficiary: beneficiary.clone(),
+                credited,
+            },
+        );
+    }
+
+    Ok(())
+}
+
+/// Record the current ledger as `addr`'s first-seen ledger if it has none
+/// yet. No-op unless `min_first_seen_ledgers` is set.
+fn note_first_seen(env: &Env, addr: &Address) {
+    if load_options(env).min_first_seen_ledgers == 0 {
+        return;
+    }
+    let key = RecordKey::FirstSeen(addr.clone());
+    if !env.storage().persistent().has(&key) {
         env.storage()
             .persistent()
-            .set(&DataKey::Contributors, &empty_contributors);
+            .set(&key, &env.ledger().sequence());
+    }
+    extend_active_record(env, &key);
+}
 
-        let empty_roadmap: Vec<RoadmapItem> = Vec::new(&env);
-        env.storage()
-            .instance()
-            .set(&DataKey::Roadmap, &empty_roadmap);
+/// Whether `payer` is old enough to earn `bonus` for the campaign. When it
+/// is not, emits `bonus_skipped` (without the payer if `anonymous`).
+fn bonus_allowed(env: &Env, payer: &Address, bonus: &str, anonymous: bool) -> bool {
+    let min_age = load_options(env).min_first_seen_ledgers;
+    if min_age == 0 {
+        return true;
+    }
+    let first_seen: u32 = env
+        .storage()
+        .persistent()
+        .get(&RecordKey::FirstSeen(payer.clone()))
+        .unwrap_or(env.ledger().sequence());
+    if env.ledger().sequence().saturating_sub(first_seen) >= min_age {
+        return true;
+    }
+    events::emit(
+        env,
+        "bonus_skipped",
+        BonusSkippedEvent {
+            contributor: if anonymous { None } else { Some(payer.clone()) },
+            bonus: Symbol::new(env, bonus),
+            reason: BONUS_SKIPPED_ACCOUNT_TOO_NEW,
+            eligible_ledger: first_seen.saturating_add(min_age),
+        },
+    );
+    false
+}
 
-        let empty_reward_tiers: Vec<RewardTier> = Vec::new(&env);
-        env.storage()
-            .instance()
-            .set(&DataKey::RewardTiers, &empty_reward_tiers);
+/// Enforce `max_contribution_per_address` on `addr` as if `contributing`
+/// were added to its contribution and `pledging` to its pledge.
+fn check_address_cap(
+    env: &Env,
+    addr: &Address,
+    contributing: i128,
+    pledging: i128,
+) -> Result<(), ContractError> {
+    let options = load_options(env);
+    if options.max_contribution_per_address == 0 {
+        return Ok(());
+    }
+    let contributed: i128 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Contribution(addr.clone()))
+        .unwrap_or(0);
+    let pledged: i128 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Pledge(addr.clone()))
+        .unwrap_or(0);
+    let contributed = contributed
+        .checked_add(contributing)
+        .ok_or(ContractError::Overflow)?;
+    let pledged = pledged
+        .checked_add(pledging)
+        .ok_or(ContractError::Overflow)?;
+    let used = if options.pledges_count_toward_cap {
+        contributed
+            .checked_add(pledged)
+            .ok_or(ContractError::Overflow)?
+    } else if pledging > 0 {
+        pledged
+    } else {
+        contributed
+    };
+    if used > options.max_contribution_per_address {
+        return Err(ContractError::MaxContributionExceeded);
+    }
+    Ok(())
+}
 
-        Ok(())
+/// Take back the referral credit earned through `contributor`'s refunded
+/// contributions, clamping each referrer's tally at zero.
+fn reverse_referral_credits(env: &Env, contributor: &Address) -> Result<(), ContractError> {
+    let credits_key = RecordKey::ReferralCredits(contributor.clone());
+    let credits: Map<Address, i128> = match env.storage().persistent().get(&credits_key) {
+        Some(credits) => credits,
+        None => return Ok(()),
+    };
+    env.storage().persistent().remove(&credits_key);
+
+    for (referrer, credited) in credits.iter() {
+        let referral_key = DataKey::ReferralTally(referrer.clone());
+        let tally: i128 = env.storage().persistent().get(&referral_key).unwrap_or(0);
+        let reversed = credited.min(tally);
+        let new_tally = tally.checked_sub(reversed).ok_or(ContractError::Overflow)?;
+        env.storage().persistent().set(&referral_key, &new_tally);
+        extend_active_record(env, &referral_key);
+
+        events::emit(
+            env,
+            "referral_reversed",
+            ReferralReversedEvent { referrer, reversed },
+        );
     }
+    Ok(())
+}
 
-    /// Contribute tokens to the campaign.
-    ///
-    /// The contributor must authorize the call. Contributions are rejected
-    /// after the deadline has passed.
-    pub fn contribute(env: Env, contributor: Address, amount: i128, referral: Option<Address>) -> Result<(), ContractError> {
-        // ── Rate limiting: enforce cooldown between contributions ──
-        let now = env.ledger().timestamp();
-        let last_time_key = DataKey::LastContributionTime(contributor.clone());
-        if let Some(last_time) = env.storage().persistent().get::<_, u64>(&last_time_key) {
-            if now < last_time + CONTRIBUTION_COOLDOWN {
-                return Err(ContractError::RateLimitExceeded);
-            }
-        }
+/// A contribution as `check_contribution` would accept it.
+struct Accepted {
+    /// Requested amount, goal-denominated.
+    requested: i128,
+    /// Accepted amount after hard-cap truncation, goal-denominated.
+    amount: i128,
+    /// Accepted amount in the contributed token.
+    token_amount: i128,
+}
 
-        let paused: bool = env
-            .storage()
-            .instance()
-            .get(&DataKey::Paused)
-            .unwrap_or(false);
-        if paused {
-            return Err(ContractError::ContractPaused);
-        }
+/// Where the tokens `record_contribution` credits come from.
+#[derive(Clone, Copy, PartialEq)]
+enum Funding {
+    /// Transferred by the payer, who authorizes the contribution.
+    Transfer,
+    /// Pulled from the payer's allowance, authorized with an installment
+    /// plan.
+    Allowance,
+    /// Already escrowed in the contract by a voucher.
+    Escrowed,
+}
 
-        contributor.require_auth();
+/// Every check `record_contribution` applies before moving funds, in
+/// order. With `dry_run` nothing is written and no authorization is
+/// required, so `can_contribute` reports exactly what a contribution
+/// would do. Amounts below the minimum fail with `BelowMinimum`.
+fn check_contribution(
+    env: &Env,
+    payer: &Address,
+    beneficiary: &Address,
+    amount: i128,
+    secondary: &Option<AcceptedToken>,
+    rate_limited: bool,
+    dry_run: bool,
+) -> Result<Accepted, ContractError> {
+    contribution_window(env, payer, amount, rate_limited)?;
+    if !dry_run {
+        payer.require_auth();
+    }
+    check_attestation(env, beneficiary, !dry_run)?;
 
-        let min_contribution: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::MinContribution)
-            .unwrap();
-        if amount < min_contribution {
-            panic!("amount below minimum");
-        }
+    let total: i128 = get_moved(env, &DataKey::TotalRaised).unwrap();
+    let hard_cap: i128 = env.storage().instance().get(&DataKey::HardCap).unwrap();
 
-        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
-        if env.ledger().timestamp() > deadline {
-            return Err(ContractError::CampaignEnded);
-        }
+    if total >= hard_cap {
+        return Err(ContractError::HardCapExceeded);
+    }
 
-        let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
-        let hard_cap: i128 = env.storage().instance().get(&DataKey::HardCap).unwrap();
+    // When the remaining headroom is smaller than the minimum, a final
+    // top-up of exactly the headroom is still allowed to close the cap.
+    let headroom = hard_cap - total;
+    let min_contribution: i128 = env
+        .storage()
+        .instance()
+        .get(&DataKey::MinContribution)
+        .unwrap();
+    // Limits and totals are goal-denominated; secondary-token amounts are
+    // converted at their static rate.
+    let requested = match secondary {
+        Some(ref accepted) => to_goal_units(amount, accepted.rate)?,
+        None => amount,
+    };
+    if requested < min_contribution.min(headroom) {
+        return Err(ContractError::BelowMinimum);
+    }
 
-        if total >= hard_cap {
-            return Err(ContractError::HardCapExceeded);
+    let (effective_amount, token_amount) = match secondary {
+        None => {
+            let effective = if amount <= headroom { amount } else { headroom };
+            (effective, effective)
+        }
+        Some(ref accepted) => {
+            let raw = if requested <= headroom {
+                amount
+            } else {
+                headroom
+                    .checked_mul(RATE_SCALE)
+                    .ok_or(ContractError::Overflow)?
+                    / accepted.rate
+            };
+            (to_goal_units(raw, accepted.rate)?, raw)
         }
+    };
+    if token_amount == 0 {
+        return Err(ContractError::BelowMinimum);
+    }
+    check_address_cap(env, beneficiary, effective_amount, 0)?;
+    check_contributor_slot(env, beneficiary)?;
+
+    Ok(Accepted {
+        requested,
+        amount: effective_amount,
+        token_amount,
+    })
+}
 
-        let headroom = hard_cap - total;
-        let effective_amount = if amount <= headroom { amount } else { headroom };
+/// Credit `amount` to the tally of `tag`, or of `other` when the tag is
+/// unregistered or the list of seen tags is full. Returns the tag credited.
+fn credit_source_tag(env: &Env, tag: Symbol, amount: i128) -> Result<Symbol, ContractError> {
+    let other = Symbol::new(env, "other");
+    let registered = env
+        .storage()
+        .persistent()
+        .has(&RecordKey::SourceTagRegistered(tag.clone()));
+    let mut seen: Vec<Symbol> = env
+        .storage()
+        .persistent()
+        .get(&RecordKey::SourceTags)
+        .unwrap_or_else(|| Vec::new(env));
+    let named = seen.iter().filter(|t| *t != other).count() as u32;
+
+    let tag = if registered && (seen.contains(&tag) || named < MAX_SOURCE_TAGS) {
+        tag
+    } else {
+        other
+    };
+    if !seen.contains(&tag) {
+        seen.push_back(tag.clone());
+        env.storage()
+            .persistent()
+            .set(&RecordKey::SourceTags, &seen);
+    }
+    extend_active_record(env, &RecordKey::SourceTags);
 
-        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let token_client = token::Client::new(&env, &token_address);
+    let key = RecordKey::SourceTally(tag.clone());
+    let tally: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    let tally = tally.checked_add(amount).ok_or(ContractError::Overflow)?;
+    env.storage().persistent().set(&key, &tally);
+    extend_active_record(env, &key);
 
-        // Transfer tokens from the contributor to this contract.
-        token_client.transfer(
-            &contributor,
-            &env.current_contract_address(),
-            &effective_amount,
-        );
+    Ok(tag)
+}
 
-        // Update the contributor's running total with overflow protection.
-        let contribution_key = DataKey::Contribution(contributor.clone());
-        let prev: i128 = env
-            .storage()
-            .persistent()
-            .get(&contribution_key)
-            .unwrap_or(0);
+/// Shared contribution logic for `contribute` and `contribute_for`.
+///
+/// The `payer` authorizes and funds the transfer and is subject to the rate
+/// limiter; the `beneficiary` is credited with the contribution. Returns
+/// `(taken, credited)`: the amount taken from the payer, truncated to the
+/// remaining hard-cap headroom, and the part credited after any entry fee.
+fn record_contribution(
+    env: &Env,
+    payer: &Address,
+    beneficiary: &Address,
+    amount: i128,
+    token_index: u32,
+    referral: Option<Address>,
+    anonymous: bool,
+    memo: Option<String>,
+    source_tag: Option<Symbol>,
+    idempotency_key: Option<BytesN<32>>,
+    funding: Funding,
+) -> Result<(i128, i128), ContractError> {
+    if let Some(ref key) = idempotency_key {
+        if idempotent_result(env, key).is_some() {
+            return Err(ContractError::DuplicateIdempotencyKey);
+        }
+    }
+    let secondary = secondary_token(env, token_index)?;
 
-        let new_contribution = prev
-            .checked_add(effective_amount)
-            .ok_or(ContractError::Overflow)?;
+    if let Some(ref text) = memo {
+        if text.len() > MAX_MEMO_LEN {
+            return Err(ContractError::InvalidInput);
+        }
+    }
 
-        env.storage()
-            .persistent()
-            .set(&contribution_key, &new_contribution);
-        env.storage()
-            .persistent()
-            .extend_ttl(&contribution_key, 100, 100);
+    // The creator funding gifts via `contribute_for` skips the cooldown.
+    // Installments were authorized with the plan, so they are checked
+    // without asking the payer again.
+    let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+    let creator_gift = payer != beneficiary && *payer == creator;
+    let installment = funding == Funding::Allowance;
+    let checked = check_contribution(
+        env,
+        payer,
+        beneficiary,
+        amount,
+        &secondary,
+        !creator_gift && !installment,
+        installment,
+    );
+    let Accepted {
+        requested,
+        amount: accepted,
+        token_amount,
+    } = match checked {
+        Err(ContractError::BelowMinimum) => panic!("amount below minimum"),
+        // Failing would roll the count back, so a contribution bounced by
+        // the cooldown is refused by accepting nothing.
+        Err(ContractError::RateLimitExceeded) => {
+            payer.require_auth();
+            note_rate_limit_hit(env, payer);
+            return Ok((0, 0));
+        }
+        checked => checked?,
+    };
+    // Failing the call would roll the pause back with it, so the
+    // contribution that trips the breaker is refused by accepting nothing.
+    if trip_circuit_breaker(env, accepted) {
+        return Ok((0, 0));
+    }
+    let total: i128 = get_moved(env, &DataKey::TotalRaised).unwrap();
+    let hard_cap: i128 = env.storage().instance().get(&DataKey::HardCap).unwrap();
+
+    let token_address: Address = match secondary {
+        Some(ref accepted) => accepted.token.clone(),
+        None => env.storage().instance().get(&DataKey::Token).unwrap(),
+    };
+    let token_client = token::Client::new(env, &token_address);
+
+    // Transfer tokens from the payer to this contract.
+    let contract = env.current_contract_address();
+    match funding {
+        Funding::Transfer => token_client.transfer(payer, &contract, &token_amount),
+        Funding::Allowance => {
+            token_client.transfer_from(&contract, payer, &contract, &token_amount)
+        }
+        Funding::Escrowed => {}
+    }
 
-        // Update the global total raised with overflow protection.
-        let new_total = total
+    // A fee taken on entry leaves only the net on record.
+    let token_fee = entry_fee(env, token_amount)?;
+    forward_entry_fee(env, &token_client, beneficiary, token_amount, token_fee);
+    let token_amount = token_amount - token_fee;
+    let effective_amount = accepted - entry_fee(env, accepted)?;
+
+    credit_contributor(env, beneficiary, effective_amount)?;
+    note_first_seen(env, payer);
+    // Anonymous contributions do not reveal the backer through a tier change.
+    note_tier_change(env, beneficiary, anonymous);
+    if secondary.is_some() {
+        record_token_contribution(
+            env,
+            beneficiary,
+            token_index,
+            token_amount,
+            effective_amount,
+        )?;
+        events::emit(
+            env,
+            "token_contributed",
+            TokenContributedEvent {
+                contributor: beneficiary.clone(),
+                token_index,
+                amount: token_amount,
+                credited: effective_amount,
+            },
+        );
+    }
+
+    // Update the global total raised with overflow protection.
+    let new_total = total
+        .checked_add(effective_amount)
+        .ok_or(ContractError::Overflow)?;
+
+    set_total_raised(env, new_total);
+    record_daily_total(env, beneficiary, effective_amount);
+    record_excess_demand(env, requested - accepted);
+
+    note_goal_reached(env, new_total);
+    if new_total == hard_cap {
+        events::emit(env, "hard_cap_reached", hard_cap);
+    }
+    raise_new_alerts(env);
+
+    if anonymous {
+        let anon_key = DataKey::AnonymousAmount(beneficiary.clone());
+        let prev_anon: i128 = env.storage().persistent().get(&anon_key).unwrap_or(0);
+        let new_anon = prev_anon
             .checked_add(effective_amount)
             .ok_or(ContractError::Overflow)?;
+        env.storage().persistent().set(&anon_key, &new_anon);
+        extend_active_record(env, &anon_key);
 
+        let anon_total: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::AnonymousTotal)
+            .unwrap_or(0);
+        let new_anon_total = anon_total
+            .checked_add(effective_amount)
+            .ok_or(ContractError::Overflow)?;
         env.storage()
             .instance()
-            .set(&DataKey::TotalRaised, &new_total);
+            .set(&DataKey::AnonymousTotal, &new_anon_total);
+    }
 
-        if new_total == hard_cap {
-            env.events()
-                .publish(("campaign", "hard_cap_reached"), hard_cap);
-        }
+    if let Some(ref text) = memo {
+        store_memo(env, beneficiary, text, anonymous);
+    }
 
-        // Track contributor address if new.
-        let mut contributors: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Contributors)
-            .unwrap();
-        if !contributors.contains(&contributor) {
-            contributors.push_back(contributor.clone());
-            env.storage()
-                .persistent()
-                .set(&DataKey::Contributors, &contributors);
-            env.storage()
-                .persistent()
-                .extend_ttl(&DataKey::Contributors, 100, 100);
-        }
+    let source_tag = match source_tag {
+        Some(tag) => Some(credit_source_tag(env, tag, effective_amount)?),
+        None => None,
+    };
 
-        // Emit contribution event
-        env.events()
-            .publish(("campaign", "contributed"), (contributor.clone(), effective_amount));
+    let receipt_id = issue_receipt(
+        env,
+        if anonymous {
+            None
+        } else {
+            Some(beneficiary.clone())
+        },
+        effective_amount,
+        referral.clone(),
+    );
+
+    // Emit contribution event (redacted to the amount only when anonymous)
+    if anonymous {
+        events::emit(env, "anon_contributed", effective_amount);
+    } else {
+        events::emit(
+            env,
+            if payer == beneficiary {
+                "contributed"
+            } else {
+                "contributed_for"
+            },
+            ContributedEvent {
+                contributor: beneficiary.clone(),
+                payer: payer.clone(),
+                requested,
+                accepted: effective_amount,
+                total_raised: new_total,
+                receipt_id,
+                memo,
+                source_tag,
+            },
+        );
+    }
 
-        // Update referral tally if referral provided
-        if let Some(referrer) = referral {
-            if referrer != contributor {
-                let referral_key = DataKey::ReferralTally(referrer.clone());
-                let current_tally: i128 = env
-                    .storage()
-                    .persistent()
-                    .get(&referral_key)
-                    .unwrap_or(0);
-                
-                let new_tally = current_tally
-                    .checked_add(effective_amount)
-                    .ok_or(ContractError::Overflow)?;
-                
-                env.storage()
-                    .persistent()
-                    .set(&referral_key, &new_tally);
-                env.storage()
-                    .persistent()
-                    .extend_ttl(&referral_key, 100, 100);
+    if let Some(referrer) = referral.filter(|_| bonus_allowed(env, payer, "referral", anonymous)) {
+        credit_referral(
+            env,
+            &referrer,
+            payer,
+            beneficiary,
+            effective_amount,
+            new_total,
+            anonymous,
+        )?;
+    }
 
-                // Emit referral event
-                env.events()
-                    .publish(("campaign", "referral"), (referrer, contributor, effective_amount));
-            }
-        }
+    touch_last_contribution(env, payer, effective_amount);
 
-        // Update last contribution time for rate limiting
-        env.storage().persistent().set(&last_time_key, &now);
+    if let Some(key) = idempotency_key {
+        let record_key = RecordKey::IdempotencyKey(key);
         env.storage()
             .persistent()
-            .extend_ttl(&last_time_key, 100, 100);
+            .set(&record_key, &(effective_amount, env.ledger().timestamp()));
+        env.storage().persistent().extend_ttl(
+            &record_key,
+            IDEMPOTENCY_KEY_TTL,
+            IDEMPOTENCY_KEY_TTL,
+        );
+    }
 
-        Ok(())
+    Ok((accepted, effective_amount))
+}
+
+/// Amount accepted for the contribution submitted with `key`, unless the
+/// key is unknown or older than `IDEMPOTENCY_WINDOW_SECS`.
+fn idempotent_result(env: &Env, key: &BytesN<32>) -> Option<i128> {
+    let (accepted, recorded_at): (i128, u64) = env
+        .storage()
+        .persistent()
+        .get(&RecordKey::IdempotencyKey(key.clone()))?;
+    let expired = env.ledger().timestamp() > recorded_at.saturating_add(IDEMPOTENCY_WINDOW_SECS);
+    (!expired).then_some(accepted)
+}
+
+/// Count a contribution from `payer` bounced by its cooldown and emit
+/// `rate_limited`, at most once per cooldown.
+fn note_rate_limit_hit(env: &Env, payer: &Address) {
+    let now = env.ledger().timestamp();
+    let key = ExtraKey::RateLimitHitsFor(payer.clone());
+    let (hits, counted_until): (u32, u64) = env.storage().persistent().get(&key).unwrap_or((0, 0));
+    if now < counted_until {
+        return;
     }
+    let remaining = cooldown_remaining_for(env, payer);
+    env.storage()
+        .persistent()
+        .set(&key, &(hits.saturating_add(1), now + remaining));
+    extend_active_record(env, &key);
+
+    let total: u32 = env
+        .storage()
+        .instance()
+        .get(&ExtraKey::RateLimitHits)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&ExtraKey::RateLimitHits, &total.saturating_add(1));
+
+    events::emit(
+        env,
+        "rate_limited",
+        RateLimitedEvent {
+            addr: payer.clone(),
+            remaining_secs: remaining,
+        },
+    );
+}
 
-    /// Pledge tokens to the campaign without transferring them immediately.
-    ///
-    /// The pledger must authorize the call. Pledges are recorded off-chain
-    /// and only collected if the goal is met after the deadline.
-    pub fn pledge(env: Env, pledger: Address, amount: i128) -> Result<(), ContractError> {
-        pledger.require_auth();
+/// Checks shared by every contribution path: cooldown (when `rate_limited`),
+/// pause, scheduled start, payer authorization, and deadline.
+fn check_contribution_open(
+    env: &Env,
+    payer: &Address,
+    amount: i128,
+    rate_limited: bool,
+) -> Result<(), ContractError> {
+    contribution_window(env, payer, amount, rate_limited)?;
+    payer.require_auth();
+    Ok(())
+}
 
-        let min_contribution: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::MinContribution)
-            .unwrap();
-        if amount < min_contribution {
-            panic!("amount below minimum");
+/// The checks of `check_contribution_open` that need no authorization.
+/// During the cooldown, `amount` is still accepted if it fits within the
+/// payer's remaining `cooldown_bypass_amount`.
+fn contribution_window(
+    env: &Env,
+    payer: &Address,
+    amount: i128,
+    rate_limited: bool,
+) -> Result<(), ContractError> {
+    // A cancelled or settled campaign has no path left to pay money out.
+    let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+    if status != Status::Active {
+        return Err(ContractError::CampaignNotActive);
+    }
+
+    // ── Rate limiting: enforce cooldown between contributions ──
+    // `record_contribution` accepts nothing instead of failing here, so
+    // the hit can be counted (see `note_rate_limit_hit`).
+    if rate_limited
+        && cooldown_remaining_for(env, payer) > 0
+        && amount > cooldown_bypass_remaining_for(env, payer)
+    {
+        return Err(ContractError::RateLimitExceeded);
+    }
+
+    let paused: bool = env
+        .storage()
+        .instance()
+        .get(&DataKey::Paused)
+        .unwrap_or(false);
+    if paused {
+        return Err(ContractError::ContractPaused);
+    }
+
+    if env.ledger().timestamp() < load_options(env).start_time {
+        return Err(ContractError::CampaignNotStarted);
+    }
+
+    if is_after_deadline(env) {
+        if auto_refund_due(env) {
+            return Err(ContractError::CampaignExpired);
         }
+        return Err(ContractError::CampaignEnded);
+    }
 
-        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
-        if env.ledger().timestamp() > deadline {
-            return Err(ContractError::CampaignEnded);
+    Ok(())
+}
+
+// ── Deadline Boundary ───────────────────────────────────────────────────────
+//
+// The deadline second is the campaign's last: contributions and pledges
+// are accepted up to and including it, and withdrawals, refunds, pledge
+// collection and finalization open the second after.
+
+/// Whether the campaign is still running: at or before the deadline.
+fn is_before_deadline(env: &Env) -> bool {
+    let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+    env.ledger().timestamp() <= deadline
+}
+
+/// Whether the campaign has ended: strictly after the deadline.
+fn is_after_deadline(env: &Env) -> bool {
+    !is_before_deadline(env)
+}
+
+/// Whether an Active campaign that missed its goal has passed its
+/// `auto_refund_after_secs` window.
+fn auto_refund_due(env: &Env) -> bool {
+    let window = load_options(env).auto_refund_after_secs;
+    if window == 0 {
+        return false;
+    }
+    let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+    let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+    let total: i128 = get_moved(env, &DataKey::TotalRaised).unwrap();
+    status == Status::Active
+        && env.ledger().timestamp() > deadline.saturating_add(window)
+        && !(goal_met(env, total) && quorum_met(env))
+}
+
+/// Add `amount` to the beneficiary's contribution record and track them in
+/// the contributor list if new.
+fn credit_contributor(env: &Env, beneficiary: &Address, amount: i128) -> Result<(), ContractError> {
+    // Update the beneficiary's running total with overflow protection.
+    let contribution_key = DataKey::Contribution(beneficiary.clone());
+    let prev: i128 = env
+        .storage()
+        .persistent()
+        .get(&contribution_key)
+        .unwrap_or(0);
+
+    let new_contribution = prev.checked_add(amount).ok_or(ContractError::Overflow)?;
+    if prev == 0 && new_contribution > 0 {
+        adjust_active_contributors(env, 1);
+        if slots_remaining(env) == Some(0) {
+            let max: u32 = env
+                .storage()
+                .instance()
+                .get(&RecordKey::MaxContributors)
+                .unwrap();
+            events::emit(env, "contributor_slots_exhausted", max);
         }
+    }
 
-        // Update the pledger's running total.
-        let pledge_key = DataKey::Pledge(pledger.clone());
-        let prev: i128 = env.storage().persistent().get(&pledge_key).unwrap_or(0);
+    let first_key = RecordKey::FirstContribution(beneficiary.clone());
+    if !env.storage().persistent().has(&first_key) {
         env.storage()
             .persistent()
-            .set(&pledge_key, &(prev + amount));
-        env.storage().persistent().extend_ttl(&pledge_key, 100, 100);
+            .set(&first_key, &env.ledger().timestamp());
+        extend_audit_record(env, &first_key);
+    }
 
-        // Update the global total pledged.
-        let total_pledged: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TotalPledged)
-            .unwrap_or(0);
+    env.storage()
+        .persistent()
+        .set(&contribution_key, &new_contribution);
+    extend_active_record(env, &contribution_key);
+
+    // Track contributor address if new.
+    let mut contributors: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Contributors)
+        .unwrap();
+    if !contributors.contains(beneficiary) {
+        contributors.push_back(beneficiary.clone());
         env.storage()
-            .instance()
-            .set(&DataKey::TotalPledged, &(total_pledged + amount));
-
-        // Track pledger address if new.
-        let mut pledgers: Vec<Address> = env
-            .storage()
             .persistent()
-            .get(&DataKey::Pledgers)
-            .unwrap_or_else(|| Vec::new(&env));
-        if !pledgers.contains(&pledger) {
-            pledgers.push_back(pledger.clone());
-            env.storage()
-                .persistent()
-                .set(&DataKey::Pledgers, &pledgers);
-            env.storage()
-                .persistent()
-                .extend_ttl(&DataKey::Pledgers, 100, 100);
-        }
+            .set(&DataKey::Contributors, &contributors);
+        extend_active_record(env, &DataKey::Contributors);
+    }
 
-        // Emit pledge event
-        env.events()
-            .publish(("campaign", "pledged"), (pledger, amount));
+    Ok(())
+}
 
+/// Contributor slots left under the `max_contributors` cap, or `None`
+/// when the campaign has no cap.
+fn slots_remaining(env: &Env) -> Option<u32> {
+    let max: u32 = env
+        .storage()
+        .instance()
+        .get(&RecordKey::MaxContributors)
+        .unwrap_or(0);
+    if max == 0 {
+        return None;
+    }
+    let count: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::ActiveContributorCount)
+        .unwrap_or(0);
+    Some(max.saturating_sub(count))
+}
+
+/// Reject a first-time contributor once every slot is taken; existing
+/// contributors may still top up.
+fn check_contributor_slot(env: &Env, beneficiary: &Address) -> Result<(), ContractError> {
+    if slots_remaining(env) != Some(0) {
+        return Ok(());
+    }
+    let contribution: i128 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Contribution(beneficiary.clone()))
+        .unwrap_or(0);
+    if contribution > 0 {
         Ok(())
+    } else {
+        Err(ContractError::ContributorLimitReached)
     }
+}
 
-    /// Collect all pledges after the deadline when the goal is met.
-    ///
-    /// This function transfers tokens from all pledgers to the contract.
-    /// Only callable after the deadline and when the combined total of
-    /// contributions and pledges meets or exceeds the goal.
-    pub fn collect_pledges(env: Env) -> Result<(), ContractError> {
-        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
-        if status != Status::Active {
-            panic!("campaign is not active");
-        }
+/// Apply `delta` to `ActiveContributorCount`.
+fn adjust_active_contributors(env: &Env, delta: i32) {
+    let count: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::ActiveContributorCount)
+        .unwrap_or(0);
+    env.storage().instance().set(
+        &DataKey::ActiveContributorCount,
+        &count.saturating_add_signed(delta),
+    );
+    refresh_state_digest(env);
+}
 
-        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
-        if env.ledger().timestamp() <= deadline {
-            return Err(ContractError::CampaignStillActive);
+/// Update last contribution time for rate limiting, and add `amount` to
+/// the payer's cooldown window (opening a new one if the last has passed).
+fn touch_last_contribution(env: &Env, payer: &Address, amount: i128) {
+    let last_time_key = DataKey::LastContributionTime(payer.clone());
+    let now = env.ledger().timestamp();
+    env.storage().persistent().set(&last_time_key, &now);
+    extend_active_record(env, &last_time_key);
+
+    let window_key = RecordKey::CooldownWindow(payer.clone());
+    let window = match env
+        .storage()
+        .persistent()
+        .get::<_, (u64, i128)>(&window_key)
+    {
+        Some((start, total)) if now < start.saturating_add(CONTRIBUTION_COOLDOWN) => {
+            (start, total.saturating_add(amount))
         }
+        _ => (now, amount),
+    };
+    env.storage().persistent().set(&window_key, &window);
+    extend_active_record(env, &window_key);
+}
 
-        let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
-        let total_raised: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
-        let total_pledged: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TotalPledged)
-            .unwrap_or(0);
-
-        // Check if combined total meets the goal
-        if total_raised + total_pledged < goal {
-            return Err(ContractError::GoalNotReached);
-        }
+/// Forget `addr`'s cooldown once their contribution is returned, so an
+/// earlier contribution no longer throttles them.
+fn clear_cooldown(env: &Env, addr: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::LastContributionTime(addr.clone()));
+    env.storage()
+        .persistent()
+        .remove(&RecordKey::CooldownWindow(addr.clone()));
+}
 
-        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let token_client = token::Client::new(&env, &token_address);
+/// The current inflow window as `(start, total)`, or a fresh one starting
+/// now once `breaker.window_secs` have passed.
+fn inflow_window(env: &Env, breaker: &CircuitBreaker) -> (u64, i128) {
+    let now = env.ledger().timestamp();
+    match env
+        .storage()
+        .instance()
+        .get::<_, (u64, i128)>(&RecordKey::InflowWindow)
+    {
+        Some((start, total)) if now < start.saturating_add(breaker.window_secs) => (start, total),
+        _ => (now, 0),
+    }
+}
 
-        let pledgers: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Pledgers)
-            .unwrap_or_else(|| Vec::new(&env));
+/// Count `amount` against the circuit breaker's inflow window. Returns
+/// true, without counting it, when it would push the window past
+/// `max_inflow`: the campaign is then paused and `circuit_breaker_tripped`
+/// carries the window total the contribution would have reached.
+fn trip_circuit_breaker(env: &Env, amount: i128) -> bool {
+    let Some(breaker) = env
+        .storage()
+        .instance()
+        .get::<_, CircuitBreaker>(&RecordKey::CircuitBreaker)
+    else {
+        return false;
+    };
+    let (start, total) = inflow_window(env, &breaker);
+    let new_total = total.saturating_add(amount);
+    if new_total > breaker.max_inflow {
+        set_state(env, &DataKey::Paused, &true);
+        events::emit(env, "circuit_breaker_tripped", new_total);
+        return true;
+    }
+    env.storage()
+        .instance()
+        .set(&RecordKey::InflowWindow, &(start, new_total));
+    false
+}
 
-        // Collect pledges from all pledgers
-        for pledger in pledgers.iter() {
-            let pledge_key = DataKey::Pledge(pledger.clone());
-            let amount: i128 = env.storage().persistent().get(&pledge_key).unwrap_or(0);
-            if amount > 0 {
-                // Transfer tokens from pledger to contract
-                token_client.transfer(&pledger, &env.current_contract_address(), &amount);
+/// Add `amount` of demand clipped by the hard cap to `ExcessDemand`.
+fn record_excess_demand(env: &Env, amount: i128) {
+    if amount <= 0 {
+        return;
+    }
+    let excess: i128 = env
+        .storage()
+        .instance()
+        .get(&RecordKey::ExcessDemand)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&RecordKey::ExcessDemand, &excess.saturating_add(amount));
+}
 
-                // Clear the pledge
-                env.storage().persistent().set(&pledge_key, &0i128);
-                env.storage().persistent().extend_ttl(&pledge_key, 100, 100);
-            }
+/// Reject `addr` with `KycRequired` unless the campaign's attestation
+/// contract verifies it; a failing call counts as unverified. With
+/// `cache`, positive answers are cached for `attestation_cache_secs`.
+fn check_attestation(env: &Env, addr: &Address, cache: bool) -> Result<(), ContractError> {
+    let options = load_options(env);
+    let Some(attestation) = options.attestation_contract else {
+        return Ok(());
+    };
+
+    let now = env.ledger().timestamp();
+    let cache_key = RecordKey::KycVerified(addr.clone());
+    if let Some((source, verified_at)) = env
+        .storage()
+        .persistent()
+        .get::<_, (Address, u64)>(&cache_key)
+    {
+        let expires = verified_at.saturating_add(options.attestation_cache_secs);
+        if source == attestation && now <= expires {
+            return Ok(());
         }
+    }
 
-        // Update total raised to include collected pledges
+    // An attestation contract that fails or answers garbage verifies no one.
+    let verified = env.try_invoke_contract::<bool, soroban_sdk::Error>(
+        &attestation,
+        &Symbol::new(env, "is_verified"),
+        Vec::from_array(env, [addr.into_val(env)]),
+    );
+    if !matches!(verified, Ok(Ok(true))) {
+        return Err(ContractError::KycRequired);
+    }
+    if cache && options.attestation_cache_secs > 0 {
         env.storage()
-            .instance()
-            .set(&DataKey::TotalRaised, &(total_raised + total_pledged));
+            .persistent()
+            .set(&cache_key, &(attestation, now));
+        // Outliving `attestation_cache_secs` would keep nothing usable.
+        let ttl = u32::try_from(options.attestation_cache_secs / LEDGER_SECS + 1)
+            .unwrap_or(u32::MAX)
+            .min(env.storage().max_ttl());
+        env.storage().persistent().extend_ttl(&cache_key, ttl, ttl);
+    }
+    Ok(())
+}
 
-        // Reset total pledged
-        env.storage().instance().set(&DataKey::TotalPledged, &0i128);
+// ── Storage Layout ──────────────────────────────────────────────────────────
+//
+// Instance storage is rewritten as one entry on every change, so it holds
+// only configuration and metadata. Counters written on every contribution
+// and lists that grow with the campaign live under the same `DataKey`s in
+// persistent storage. Campaigns initialized before the split keep them in
+// instance storage until `migrate` moves them; reads fall back to it.
+
+/// Keys kept in persistent storage since the layout split.
+fn moved_keys(env: &Env) -> Vec<DataKey> {
+    Vec::from_array(
+        env,
+        [
+            DataKey::TotalRaised,
+            DataKey::TotalPledged,
+            DataKey::GoalReachedEmitted,
+            DataKey::Roadmap,
+            DataKey::RewardTiers,
+            DataKey::StretchGoals,
+        ],
+    )
+}
 
-        // Emit pledges collected event
-        env.events()
-            .publish(("campaign", "pledges_collected"), total_pledged);
+/// Read a moved key, falling back to its pre-migration instance entry.
+fn get_moved<V: TryFromVal<Env, Val>>(env: &Env, key: &DataKey) -> Option<V> {
+    env.storage()
+        .persistent()
+        .get(key)
+        .or_else(|| env.storage().instance().get(key))
+}
 
-        Ok(())
+/// Write `TotalRaised`, reporting the change to the factory while the
+/// campaign is Active so its total value locked stays current.
+fn set_total_raised(env: &Env, total: i128) {
+    let before: i128 = get_moved(env, &DataKey::TotalRaised).unwrap_or(0);
+    set_moved(env, &DataKey::TotalRaised, &total);
+    let status: Option<Status> = env.storage().instance().get(&DataKey::Status);
+    if total != before && status == Some(Status::Active) {
+        notify_factory(env, "raised", total - before);
     }
+}
 
-    /// Withdraw raised funds — only callable by the creator after the
-    /// deadline, and only if the goal has been met.
-    ///
-    /// If a platform fee is configured, deducts the fee and transfers it to
-    /// the platform address, then sends the remainder to the creator.
-    pub fn withdraw(env: Env) -> Result<(), ContractError> {
-        let paused: bool = env
-            .storage()
-            .instance()
-            .get(&DataKey::Paused)
-            .unwrap_or(false);
-        if paused {
-            return Err(ContractError::ContractPaused);
-        }
+/// Write a moved key to persistent storage.
+fn set_moved<V: IntoVal<Env, Val>>(env: &Env, key: &DataKey, value: &V) {
+    env.storage().persistent().set(key, value);
+    extend_active_record(env, key);
+    if matches!(key, DataKey::TotalRaised | DataKey::TotalPledged) {
+        refresh_state_digest(env);
+    }
+}
 
-        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
-        if status != Status::Active {
-            panic!("campaign is not active");
-        }
+// ── State Digest ────────────────────────────────────────────────────────────
+//
+// `StateDigest` lets a light client tell whether anything it polls changed
+// with one read. Every write to a covered field goes through `set_moved`,
+// `set_state` or `adjust_active_contributors`, which recompute it.
 
-        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
-        creator.require_auth();
+/// Write `Status`, `Paused` or `Deadline` and recompute the state digest.
+fn set_state<V: IntoVal<Env, Val>>(env: &Env, key: &DataKey, value: &V) {
+    env.storage().instance().set(key, value);
+    refresh_state_digest(env);
+}
 
-        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
-        if env.ledger().timestamp() <= deadline {
-            return Err(ContractError::CampaignStillActive);
-        }
+/// Recompute `StateDigest` as the sha256 of the XDR encoding of
+/// `(total_raised, total_pledged, status, deadline, paused,
+/// contributor_count, version)`.
+fn refresh_state_digest(env: &Env) {
+    let storage = env.storage().instance();
+    let state = (
+        get_moved::<i128>(env, &DataKey::TotalRaised).unwrap_or(0),
+        get_moved::<i128>(env, &DataKey::TotalPledged).unwrap_or(0),
+        storage.get::<_, Status>(&DataKey::Status),
+        storage.get::<_, u64>(&DataKey::Deadline).unwrap_or(0),
+        storage.get::<_, bool>(&DataKey::Paused).unwrap_or(false),
+        storage
+            .get::<_, u32>(&DataKey::ActiveContributorCount)
+            .unwrap_or(0),
+        CONTRACT_VERSION,
+    );
+    let digest: BytesN<32> = env.crypto().sha256(&state.to_xdr(env)).into();
+    storage.set(&RecordKey::StateDigest, &digest);
+}
 
-        let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
-        let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
-        if total < goal {
-            return Err(ContractError::GoalNotReached);
-        }
+/// Publish a `checkpoint` event with the current totals.
+fn emit_checkpoint(env: &Env) {
+    let storage = env.storage().instance();
+    events::emit(
+        env,
+        "checkpoint",
+        CheckpointEvent {
+            total_raised: get_moved(env, &DataKey::TotalRaised).unwrap_or(0),
+            total_pledged: get_moved(env, &DataKey::TotalPledged).unwrap_or(0),
+            paid_to_creator: storage.get(&RecordKey::PaidToCreator).unwrap_or(0),
+            active_contributors: storage.get(&DataKey::ActiveContributorCount).unwrap_or(0),
+        },
+    );
+}
 
-        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let token_client = token::Client::new(&env, &token_address);
+// ── Storage TTLs ────────────────────────────────────────────────────────────
+//
+// Records the campaign needs to settle (contributions, pledges, tallies)
+// live until `ACTIVE_RECORD_BUFFER` ledgers past the deadline. Records kept
+// as history once money has moved (refunds, receipts, tier snapshots,
+// disputes, expenses) get `AUDIT_RECORD_TTL`, and `extend_storage` can
+// bump them again.
+
+/// Ledgers from now until `ACTIVE_RECORD_BUFFER` past the deadline.
+fn active_record_ttl(env: &Env) -> u32 {
+    let deadline: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::Deadline)
+        .unwrap_or(0);
+    let until_deadline = deadline.saturating_sub(env.ledger().timestamp()) / LEDGER_SECS;
+    let ttl = u32::try_from(until_deadline)
+        .unwrap_or(u32::MAX)
+        .saturating_add(ACTIVE_RECORD_BUFFER);
+    ttl.min(audit_record_ttl(env))
+}
 
-        // Calculate and transfer platform fee if configured.
-        let platform_config: Option<PlatformConfig> =
-            env.storage().instance().get(&DataKey::PlatformConfig);
+/// `AUDIT_RECORD_TTL`, clamped to the network's maximum.
+fn audit_record_ttl(env: &Env) -> u32 {
+    AUDIT_RECORD_TTL.min(env.storage().max_ttl())
+}
 
-        let creator_payout = if let Some(config) = platform_config {
-            // Calculate fee using checked arithmetic to prevent overflow.
-            let fee = total
-                .checked_mul(config.fee_bps as i128)
-                .expect("fee calculation overflow")
-                .checked_div(10_000)
-                .expect("fee division by zero");
+/// Keep a persistent record alive until the campaign has settled.
+fn extend_active_record<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+    let ttl = active_record_ttl(env);
+    env.storage().persistent().extend_ttl(key, ttl, ttl);
+}
 
-            // Transfer fee to platform.
-            token_client.transfer(&env.current_contract_address(), &config.address, &fee);
+/// Keep a persistent audit record alive for `AUDIT_RECORD_TTL`.
+fn extend_audit_record<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+    let ttl = audit_record_ttl(env);
+    env.storage().persistent().extend_ttl(key, ttl, ttl);
+}
 
-            // Emit event with fee details.
-            env.events()
-                .publish(("campaign", "fee_transferred"), (&config.address, fee));
+/// Load the campaign options, defaulting every feature to disabled.
+fn load_options(env: &Env) -> CampaignOptions {
+    env.storage()
+        .instance()
+        .get(&DataKey::Options)
+        .unwrap_or_default()
+}
 
-            // Calculate creator payout.
-            total.checked_sub(fee).expect("creator payout underflow")
-        } else {
-            total
-        };
+fn load_update_posts(env: &Env) -> Vec<UpdatePost> {
+    env.storage()
+        .persistent()
+        .get(&RecordKey::UpdatePosts)
+        .unwrap_or_else(|| Vec::new(env))
+}
 
-        // Transfer remainder to creator.
-        token_client.transfer(&env.current_contract_address(), &creator, &creator_payout);
+/// Resolve `token_index` to a secondary token, or `None` for the primary
+/// token (index 0).
+fn secondary_token(env: &Env, token_index: u32) -> Result<Option<AcceptedToken>, ContractError> {
+    if token_index == 0 {
+        return Ok(None);
+    }
+    load_options(env)
+        .secondary_tokens
+        .and_then(|tokens| tokens.get(token_index - 1))
+        .map(Some)
+        .ok_or(ContractError::UnsupportedToken)
+}
 
-        env.storage().instance().set(&DataKey::TotalRaised, &0i128);
-        env.storage()
-            .instance()
-            .set(&DataKey::Status, &Status::Successful);
+/// Convert a secondary-token amount to goal units at `rate`.
+fn to_goal_units(amount: i128, rate: i128) -> Result<i128, ContractError> {
+    Ok(amount.checked_mul(rate).ok_or(ContractError::Overflow)? / RATE_SCALE)
+}
 
-        // Emit withdrawal event
-        env.events()
-            .publish(("campaign", "withdrawn"), (creator.clone(), total));
+fn load_token_totals(env: &Env) -> Map<u32, TokenTally> {
+    env.storage()
+        .instance()
+        .get(&RecordKey::TokenTotals)
+        .unwrap_or_else(|| Map::new(env))
+}
 
+/// Add a secondary-token contribution to the contributor's and the
+/// campaign's tallies.
+fn record_token_contribution(
+    env: &Env,
+    contributor: &Address,
+    token_index: u32,
+    raised: i128,
+    credited: i128,
+) -> Result<(), ContractError> {
+    let add = |tallies: &mut Map<u32, TokenTally>| -> Result<(), ContractError> {
+        let tally = tallies.get(token_index).unwrap_or_default();
+        tallies.set(
+            token_index,
+            TokenTally {
+                raised: tally
+                    .raised
+                    .checked_add(raised)
+                    .ok_or(ContractError::Overflow)?,
+                credited: tally
+                    .credited
+                    .checked_add(credited)
+                    .ok_or(ContractError::Overflow)?,
+            },
+        );
         Ok(())
+    };
+
+    let key = RecordKey::TokenContribution(contributor.clone());
+    let mut mine: Map<u32, TokenTally> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Map::new(env));
+    add(&mut mine)?;
+    env.storage().persistent().set(&key, &mine);
+    extend_active_record(env, &key);
+
+    let mut totals = load_token_totals(env);
+    add(&mut totals)?;
+    env.storage()
+        .instance()
+        .set(&RecordKey::TokenTotals, &totals);
+    Ok(())
+}
+
+/// Goal-denominated share of `TotalRaised` held in secondary tokens.
+fn secondary_credited(env: &Env) -> i128 {
+    load_token_totals(env)
+        .values()
+        .iter()
+        .map(|tally| tally.credited)
+        .sum()
+}
+
+/// Return a contributor's secondary-token contributions in full and drop
+/// them from the campaign tallies. Returns the goal-denominated amount
+/// they were credited as.
+fn refund_token_contributions(env: &Env, contributor: &Address) -> i128 {
+    let key = RecordKey::TokenContribution(contributor.clone());
+    let Some(mine) = env
+        .storage()
+        .persistent()
+        .get::<_, Map<u32, TokenTally>>(&key)
+    else {
+        return 0;
+    };
+
+    let tokens = load_options(env).secondary_tokens.unwrap();
+    let mut totals = load_token_totals(env);
+    let mut credited = 0;
+    for (token_index, tally) in mine.iter() {
+        let token = tokens.get(token_index - 1).unwrap().token;
+        token::Client::new(env, &token).transfer(
+            &env.current_contract_address(),
+            contributor,
+            &tally.raised,
+        );
+        let total = totals.get(token_index).unwrap_or_default();
+        totals.set(
+            token_index,
+            TokenTally {
+                raised: total.raised - tally.raised,
+                credited: total.credited - tally.credited,
+            },
+        );
+        credited += tally.credited;
     }
+    env.storage()
+        .instance()
+        .set(&RecordKey::TokenTotals, &totals);
+    env.storage().persistent().remove(&key);
+    credited
+}
 
-    /// Refund all contributors — callable by anyone after the deadline
-    /// if the goal was **not** met.
-    pub fn refund(env: Env) -> Result<(), ContractError> {
-        let paused: bool = env
-            .storage()
-            .instance()
-            .get(&DataKey::Paused)
-            .unwrap_or(false);
-        if paused {
-            return Err(ContractError::ContractPaused);
-        }
-
-        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
-        if status != Status::Active {
-            panic!("campaign is not active");
-        }
-
-        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
-        if env.ledger().timestamp() <= deadline {
-            return Err(ContractError::CampaignStillActive);
-        }
+/// Pay every secondary-token balance raised to the creator, less the
+/// platform fee, and clear the campaign tallies.
+fn pay_out_secondary_tokens(env: &Env, creator: &Address) {
+    let totals = load_token_totals(env);
+    if totals.is_empty() {
+        return;
+    }
+    let tokens = load_options(env).secondary_tokens.unwrap();
+    let platform_config = withdraw_fee_config(env);
 
-        let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
-        let total: i128 = env.storage().instance().get(&DataKey::TotalRaised).unwrap();
-        if total >= goal {
-            return Err(ContractError::GoalReached);
+    for (token_index, tally) in totals.iter() {
+        let token = tokens.get(token_index - 1).unwrap().token;
+        let split = split_payout(env, tally.raised);
+        if let Some(ref config) = platform_config {
+            queue_payout(env, &config.address, &token, split.platform_fee, false);
         }
+        queue_payout(env, creator, &token, split.net_to_creator, false);
+        events::emit(
+            env,
+            "token_withdrawn",
+            TokenWithdrawnEvent {
+                token,
+                amount: tally.raised,
+            },
+        );
+    }
+    env.storage().instance().remove(&RecordKey::TokenTotals);
+}
 
-        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let token_client = token::Client::new(&env, &token_address);
+fn load_pending_payouts(env: &Env) -> Vec<Payout> {
+    env.storage()
+        .persistent()
+        .get(&RecordKey::PendingPayouts)
+        .unwrap_or_else(|| Vec::new(env))
+}
 
-        let contributors: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Contributors)
-            .unwrap();
+/// Append a transfer to the settlement queue.
+fn queue_payout(env: &Env, recipient: &Address, token: &Address, amount: i128, principal: bool) {
+    let mut pending = load_pending_payouts(env);
+    pending.push_back(Payout {
+        recipient: recipient.clone(),
+        token: token.clone(),
+        amount,
+        principal,
+    });
+    env.storage()
+        .persistent()
+        .set(&RecordKey::PendingPayouts, &pending);
+    extend_audit_record(env, &RecordKey::PendingPayouts);
+}
 
-        for contributor in contributors.iter() {
-            let contribution_key = DataKey::Contribution(contributor.clone());
-            let amount: i128 = env
+/// Pay up to `limit` queued payouts in order. A transfer that fails is
+/// credited to the recipient for `claim_payout` instead of aborting the
+/// batch. Once the creator's principal payout is settled or made
+/// claimable, an Active campaign becomes Successful.
+///
+/// Returns the number of payouts processed.
+fn settle_payouts(env: &Env, limit: u32) -> u32 {
+    let mut pending = load_pending_payouts(env);
+    let mut processed = 0;
+    while processed < limit {
+        let payout = match pending.pop_front() {
+            Some(payout) => payout,
+            None => break,
+        };
+        processed += 1;
+
+        let settled = payout.amount <= 0
+            || env
+                .try_invoke_contract::<(), soroban_sdk::Error>(
+                    &payout.token,
+                    &Symbol::new(env, "transfer"),
+                    (
+                        env.current_contract_address(),
+                        payout.recipient.clone(),
+                        payout.amount,
+                    )
+                        .into_val(env),
+                )
+                .is_ok_and(|result| result.is_ok());
+        if !settled {
+            let claim_key = RecordKey::ClaimablePayouts(payout.recipient.clone());
+            let mut claimable: Map<Address, i128> = env
                 .storage()
                 .persistent()
-                .get(&contribution_key)
-                .unwrap_or(0);
-            if amount > 0 {
-                token_client.transfer(&env.current_contract_address(), &contributor, &amount);
-                env.storage().persistent().set(&contribution_key, &0i128);
-                env.storage()
-                    .persistent()
-                    .extend_ttl(&contribution_key, 100, 100);
+                .get(&claim_key)
+                .unwrap_or_else(|| Map::new(env));
+            let owed = claimable.get(payout.token.clone()).unwrap_or(0);
+            claimable.set(payout.token.clone(), owed + payout.amount);
+            env.storage().persistent().set(&claim_key, &claimable);
+            extend_audit_record(env, &claim_key);
+            events::emit(
+                env,
+                "payout_failed",
+                PayoutEvent {
+                    recipient: payout.recipient.clone(),
+                    token: payout.token.clone(),
+                    amount: payout.amount,
+                },
+            );
+        }
+
+        if payout.principal {
+            let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+            if status == Status::Active {
+                close_campaign(env, Status::Successful);
             }
         }
+    }
 
-        env.storage().instance().set(&DataKey::TotalRaised, &0i128);
+    if pending.is_empty() {
         env.storage()
-            .instance()
-            .set(&DataKey::Status, &Status::Refunded);
+            .persistent()
+            .remove(&RecordKey::PendingPayouts);
+    } else {
+        env.storage()
+            .persistent()
+            .set(&RecordKey::PendingPayouts, &pending);
+    }
+    processed
+}
 
-        Ok(())
+/// The platform config, if the platform fee is taken at withdrawal.
+fn withdraw_fee_config(env: &Env) -> Option<PlatformConfig> {
+    env.storage()
+        .instance()
+        .get::<_, PlatformConfig>(&DataKey::PlatformConfig)
+        .filter(|config| config.fee_mode == FeeMode::OnWithdraw)
+}
+
+/// Platform fee on a contribution of `amount` — zero unless the fee is
+/// taken as contributions arrive.
+fn entry_fee(env: &Env, amount: i128) -> Result<i128, ContractError> {
+    let config: Option<PlatformConfig> = env.storage().instance().get(&DataKey::PlatformConfig);
+    match config.filter(|config| config.fee_mode == FeeMode::OnContribute) {
+        Some(config) => Ok(amount
+            .checked_mul(config.fee_bps as i128)
+            .ok_or(ContractError::Overflow)?
+            / 10_000),
+        None => Ok(0),
     }
+}
 
-    /// Cancel the campaign and refund all contributors — callable only by
-    /// the creator while the campaign is still Active.
-    pub fn cancel(env: Env) {
-        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
-        if status != Status::Active {
-            panic!("campaign is not active");
+/// Forward `fee` tokens held by the contract to the platform and report
+/// the fee charged on `contributor`'s contribution of `gross`.
+fn forward_entry_fee(
+    env: &Env,
+    token_client: &token::Client,
+    contributor: &Address,
+    gross: i128,
+    fee: i128,
+) {
+    if fee == 0 {
+        return;
+    }
+    let config: PlatformConfig = env
+        .storage()
+        .instance()
+        .get(&DataKey::PlatformConfig)
+        .unwrap();
+    token_client.transfer(&env.current_contract_address(), &config.address, &fee);
+    events::emit(
+        env,
+        "entry_fee_charged",
+        EntryFeeEvent {
+            contributor: contributor.clone(),
+            gross,
+            fee,
+            net: gross - fee,
+        },
+    );
+}
+
+/// Split a payout of `gross` into the platform fee and the creator's
+/// share. `withdraw` and `withdraw_preview` both use this.
+fn split_payout(env: &Env, gross: i128) -> WithdrawPreview {
+    let platform_fee = match withdraw_fee_config(env) {
+        Some(config) => {
+            gross
+                .checked_mul(config.fee_bps as i128)
+                .expect("fee calculation overflow")
+                / 10_000
         }
+        None => 0,
+    };
+    WithdrawPreview {
+        gross,
+        platform_fee,
+        referral_rewards: 0,
+        escrow: 0,
+        net_to_creator: gross
+            .checked_sub(platform_fee)
+            .expect("creator payout underflow"),
+    }
+}
 
-        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
-        creator.require_auth();
+/// Move `fulfillment_escrow_bps` of `split.gross` from the creator's share
+/// into escrow.
+fn hold_escrow(env: &Env, mut split: WithdrawPreview) -> WithdrawPreview {
+    split.escrow = split
+        .gross
+        .checked_mul(load_options(env).fulfillment_escrow_bps as i128)
+        .expect("escrow calculation overflow")
+        / 10_000;
+    split.net_to_creator -= split.escrow;
+    split
+}
 
-        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let token_client = token::Client::new(&env, &token_address);
+/// The escrow held since `withdraw`, if any.
+fn load_escrow(env: &Env) -> Option<Escrow> {
+    env.storage()
+        .instance()
+        .get::<_, Escrow>(&RecordKey::Escrow)
+        .filter(|escrow| escrow.amount > 0)
+}
 
-        let contributors: Vec<Address> = env
+/// Whether approving backers hold `approval_required_bps` of what was raised,
+/// so the creator may release the escrow early. False when the approval
+/// vote is disabled.
+fn escrow_release_approved(env: &Env, escrow: &Escrow) -> bool {
+    let required = load_options(env).approval_required_bps as i128;
+    required > 0
+        && escrow.raised > 0
+        && escrow.approved.saturating_mul(10_000) >= escrow.raised.saturating_mul(required)
+}
+
+/// Pay the escrow back to contributors in proportion to their
+/// contributions; the last also receives the rounding remainder.
+fn refund_escrow(env: &Env, token_client: &token::Client, escrow: &Escrow) {
+    let contributors: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Contributors)
+        .unwrap_or_else(|| Vec::new(env));
+    let mut weights: Vec<(Address, i128)> = Vec::new(env);
+    for contributor in contributors.iter() {
+        let weight: i128 = env
             .storage()
             .persistent()
-            .get(&DataKey::Contributors)
-            .unwrap();
-
-        for contributor in contributors.iter() {
-            let contribution_key = DataKey::Contribution(contributor.clone());
-            let amount: i128 = env
-                .storage()
-                .persistent()
-                .get(&contribution_key)
-                .unwrap_or(0);
-            if amount > 0 {
-                token_client.transfer(&env.current_contract_address(), &contributor, &amount);
-                env.storage().persistent().set(&contribution_key, &0i128);
-                env.storage()
-                    .persistent()
-                    .extend_ttl(&contribution_key, 100, 100);
-            }
+            .get(&DataKey::Contribution(contributor.clone()))
+            .unwrap_or(0);
+        if weight > 0 {
+            weights.push_back((contributor, weight));
         }
+    }
 
-        env.storage().instance().set(&DataKey::TotalRaised, &0i128);
-        env.storage()
-            .instance()
-            .set(&DataKey::Status, &Status::Cancelled);
+    let mut paid = 0;
+    for (i, (contributor, weight)) in weights.iter().enumerate() {
+        let share = if i as u32 == weights.len() - 1 {
+            escrow.amount - paid
+        } else {
+            weight * escrow.amount / escrow.raised
+        };
+        paid += share;
+        if share > 0 {
+            token_client.transfer(&env.current_contract_address(), &contributor, &share);
+        }
     }
+}
 
-    /// Upgrade the contract to a new WASM implementation — admin-only.
-    ///
-    /// This function allows the designated admin to upgrade the contract's WASM code
-    /// without changing the contract's address or storage. The new WASM hash must be
-    /// provided and the caller must be authorized as the admin.
-    ///
-    /// # Arguments
-    /// * `new_wasm_hash` – The SHA-256 hash of the new WASM binary to deploy.
-    ///
-    /// # Panics
-    /// * If the caller is not the admin.
-    pub fn upgrade(env: Env, new_wasm_hash: soroban_sdk::BytesN<32>) {
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
+/// Whether approving contributors hold at least `approval_required_bps` of
+/// `TotalRaised`. Always true when the approval vote is disabled.
+fn approval_reached(env: &Env, options: &CampaignOptions) -> bool {
+    if options.approval_required_bps == 0 {
+        return true;
+    }
+    let total: i128 = get_moved(env, &DataKey::TotalRaised).unwrap();
+    let approved: i128 = env
+        .storage()
+        .instance()
+        .get(&DataKey::ApprovedWeight)
+        .unwrap_or(0);
+    let lhs = approved
+        .checked_mul(10_000)
+        .expect("approval weight overflow");
+    let rhs = total
+        .checked_mul(options.approval_required_bps as i128)
+        .expect("approval threshold overflow");
+    total > 0 && lhs >= rhs
+}
 
-        env.deployer().update_current_contract_wasm(new_wasm_hash);
+/// Whether the approval voting window is still open. A window of `0` never
+/// closes.
+fn approval_window_open(env: &Env, options: &CampaignOptions) -> bool {
+    if options.approval_window_secs == 0 {
+        return true;
     }
+    let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+    env.ledger().timestamp() <= deadline.saturating_add(options.approval_window_secs)
+}
 
-    /// Pause or unpause the contract — creator-only.
-    ///
-    /// When paused, all contributions, withdrawals, and refunds are blocked.
-    /// This is a security mechanism to halt operations in case of detected
-    /// vulnerabilities or external threats.
-    ///
-    /// # Arguments
-    /// * `paused` – True to pause, false to unpause.
-    pub fn set_paused(env: Env, paused: bool) {
-        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
-        creator.require_auth();
+/// Whether the voting window closed without reaching the approval
+/// threshold, entitling contributors to a refund despite a met goal.
+fn approval_vote_failed(env: &Env, options: &CampaignOptions) -> bool {
+    options.approval_required_bps > 0
+        && !approval_window_open(env, options)
+        && !approval_reached(env, options)
+}
+
+/// Creator (and co-signer) authorization plus every precondition shared by
+/// `withdraw` and `withdraw_vested`: deadline passed, goal met, timelock
+/// elapsed, no dispute freeze, and approval threshold reached.
+///
+/// Returns the creator and `TotalRaised`.
+fn authorize_withdrawal(
+    env: &Env,
+    options: &CampaignOptions,
+) -> Result<(Address, i128), ContractError> {
+    let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+    creator.require_auth();
+
+    // High-value campaigns may require a second signature.
+    if let Some(ref cosigner) = options.withdraw_cosigner {
+        cosigner.require_auth();
+    }
 
-        env.storage().instance().set(&DataKey::Paused, &paused);
+    let total = check_payout_due(env, options)?;
+    Ok((creator, total))
+}
 
-        let event_name = if paused { "paused" } else { "unpaused" };
-        env.events().publish(("campaign", event_name), ());
+/// USD value of `amount` of the campaign token, or `None` when the
+/// campaign has no oracle or its price is missing or stale.
+fn usd_value(env: &Env, options: &CampaignOptions, amount: i128) -> Option<i128> {
+    let oracle = options.oracle.as_ref()?;
+    let token: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+    let asset = OracleAsset::Stellar(token);
+    let quote = env
+        .try_invoke_contract::<Option<PriceData>, soroban_sdk::Error>(
+            oracle,
+            &Symbol::new(env, "lastprice"),
+            Vec::from_array(env, [asset.into_val(env)]),
+        )
+        .ok()?
+        .ok()??;
+
+    let max_age = if options.oracle_max_age_secs == 0 {
+        DEFAULT_ORACLE_MAX_AGE_SECS
+    } else {
+        options.oracle_max_age_secs
+    };
+    if env.ledger().timestamp() > quote.timestamp.saturating_add(max_age) {
+        return None;
     }
 
-    /// Update campaign metadata — only callable by the creator while the
-    /// campaign is still Active.
-    ///
-    /// # Arguments
-    /// * `creator`     – The campaign creator's address (for authentication).
-    /// * `title`       – Optional new title (None to keep existing).
-    /// * `description` – Optional new description (None to keep existing).
-    /// * `socials`    – Optional new social links (None to keep existing).
-    pub fn update_metadata(
-        env: Env,
-        creator: Address,
-        title: Option<String>,
-        description: Option<String>,
-        socials: Option<String>,
-    ) {
-        // Check campaign is active.
-        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
-        if status != Status::Active {
-            panic!("campaign is not active");
-        }
+    let decimals = env
+        .try_invoke_contract::<u32, soroban_sdk::Error>(
+            oracle,
+            &Symbol::new(env, "decimals"),
+            Vec::new(env),
+        )
+        .ok()?
+        .ok()?;
+    amount
+        .checked_mul(quote.price)?
+        .checked_div(10i128.checked_pow(decimals)?)
+}
 
-        // Require creator authentication and verify caller is the creator.
-        let stored_creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
-        if creator != stored_creator {
-            panic!("not authorized");
+/// Whether `total` meets the goal: in USD when the oracle has a fresh
+/// price, in tokens otherwise.
+fn goal_met(env: &Env, total: i128) -> bool {
+    let options = load_options(env);
+    match usd_value(env, &options, total) {
+        Some(value) => value >= options.goal_usd,
+        None => {
+            let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
+            total >= goal
         }
-        creator.require_auth();
+    }
+}
 
-        // Track which fields were updated for the event.
-        let mut updated_fields: Vec<Symbol> = Vec::new(&env);
+/// Whether at least `min_contributors` contributors are still in.
+fn quorum_met(env: &Env) -> bool {
+    let count: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::ActiveContributorCount)
+        .unwrap_or(0);
+    count >= load_options(env).min_contributors
+}
 
-        // Update title if provided.
-        if let Some(new_title) = title {
-            env.storage().instance().set(&DataKey::Title, &new_title);
-            updated_fields.push_back(Symbol::new(&env, "title"));
-        }
+/// `part` in basis points of `whole`, or `0` when `whole` is not positive.
+///
+/// Never overflows: when `part * 10_000` does not fit in an i128 the
+/// quotient and remainder are scaled separately, and a result beyond the
+/// i128 range saturates.
+fn bps_of(part: i128, whole: i128) -> i128 {
+    if whole <= 0 {
+        return 0;
+    }
+    if let Some(scaled) = part.checked_mul(10_000) {
+        return scaled / whole;
+    }
 
-        // Update description if provided.
-        if let Some(new_description) = description {
-            env.storage()
-                .instance()
-                .set(&DataKey::Description, &new_description);
-            updated_fields.push_back(Symbol::new(&env, "description"));
+    // Multiply the remainder by 10_000 one bit at a time, keeping `rest`
+    // below `whole` so no step can overflow.
+    let (quotient, remainder) = (part / whole, (part % whole).unsigned_abs());
+    let whole = whole.unsigned_abs();
+    let (mut fraction, mut rest) = (0i128, 0u128);
+    for bit in (0..14).rev() {
+        fraction *= 2;
+        if rest >= whole - rest {
+            fraction += 1;
+            rest -= whole - rest;
+        } else {
+            rest *= 2;
         }
-
-        // Update social links if provided.
-        if let Some(new_socials) = socials {
-            env.storage()
-                .instance()
-                .set(&DataKey::SocialLinks, &new_socials);
-            updated_fields.push_back(Symbol::new(&env, "socials"));
+        if (10_000 >> bit) & 1 == 1 {
+            if rest >= whole - remainder {
+                fraction += 1;
+                rest -= whole - remainder;
+            } else {
+                rest += remainder;
+            }
         }
+    }
+    if part < 0 {
+        fraction = -fraction;
+    }
+    quotient.saturating_mul(10_000).saturating_add(fraction)
+}
 
-        // Emit metadata_updated event with the list of updated field names.
-        env.events().publish(
-            (
-                Symbol::new(&env, "campaign"),
-                Symbol::new(&env, "metadata_updated"),
-            ),
-            updated_fields,
-        );
+/// Whether the goal can still be met: false once the deadline passed with
+/// contributions and pledges short of it, when the hard cap is below the
+/// goal, or when the campaign closed without succeeding.
+fn goal_reachable(env: &Env) -> bool {
+    let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+    match status {
+        Status::Successful => return true,
+        Status::Refunded | Status::Cancelled => return false,
+        Status::Active => {}
+    }
+    let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
+    let hard_cap: i128 = env.storage().instance().get(&DataKey::HardCap).unwrap();
+    if hard_cap < goal {
+        return false;
     }
+    let total: i128 = get_moved(env, &DataKey::TotalRaised).unwrap();
+    let pledged: i128 = get_moved(env, &DataKey::TotalPledged).unwrap_or(0);
+    is_before_deadline(env) || (goal_met(env, total.saturating_add(pledged)) && quorum_met(env))
+}
 
-    /// Update the campaign deadline — only callable by the creator while the
-    /// campaign is still Active.
-    ///
-    /// # Arguments
-    /// * `new_deadline` – The new deadline as a ledger timestamp (must be greater than current deadline).
-    ///
-    /// # Panics
-    /// * If the campaign is not Active.
-    /// * If new_deadline is less than or equal to the current deadline.
-    pub fn update_deadline(env: Env, new_deadline: u64) {
-        // Check campaign is active.
-        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
-        if status != Status::Active {
-            panic!("campaign is not active");
-        }
+/// Whether the campaign has settled in the creator's favour: the deadline
+/// and dispute window have passed, the goal and any contributor quorum are
+/// met, no dispute froze the funds, and any approval vote passed. Returns
+/// `TotalRaised`.
+fn check_payout_due(env: &Env, options: &CampaignOptions) -> Result<i128, ContractError> {
+    if is_before_deadline(env) {
+        return Err(ContractError::CampaignStillActive);
+    }
+    let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
 
-        // Require creator authentication.
-        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
-        creator.require_auth();
+    let total: i128 = get_moved(env, &DataKey::TotalRaised).unwrap();
+    if !goal_met(env, total) {
+        return Err(ContractError::GoalNotReached);
+    }
+    if !quorum_met(env) {
+        return Err(ContractError::QuorumNotMet);
+    }
 
-        // Get the current deadline.
-        let current_deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+    // Give backers a dispute window before funds leave.
+    if env.ledger().timestamp() <= deadline.saturating_add(options.withdraw_delay_secs) {
+        return Err(ContractError::WithdrawTimelocked);
+    }
 
-        // Ensure new_deadline is greater than current_deadline (only extensions allowed).
-        if new_deadline <= current_deadline {
-            panic!("new deadline must be after current deadline");
-        }
+    let frozen: bool = env
+        .storage()
+        .instance()
+        .get(&DataKey::DisputeFrozen)
+        .unwrap_or(false);
+    if frozen {
+        return Err(ContractError::WithdrawalDisputed);
+    }
 
-        // Update the deadline.
-        env.storage()
-            .instance()
-            .set(&DataKey::Deadline, &new_deadline);
+    // Backer-gated campaigns need enough approving contribution weight.
+    if !approval_reached(env, options) {
+        return Err(ContractError::WithdrawalNotApproved);
+    }
+
+    Ok(total)
+}
 
-        // Emit deadline_updated event with old and new deadline values.
-        env.events().publish(
-            ("campaign", "deadline_updated"),
-            (current_deadline, new_deadline),
+/// Emit `goal_reached` the first time `TotalRaised` meets the goal.
+fn note_goal_reached(env: &Env, new_total: i128) {
+    let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
+    if new_total < goal {
+        return;
+    }
+    let emitted: bool = get_moved(env, &DataKey::GoalReachedEmitted).unwrap_or(false);
+    if !emitted {
+        set_moved(env, &DataKey::GoalReachedEmitted, &true);
+        events::emit(
+            env,
+            "goal_reached",
+            GoalReachedEvent {
+                goal,
+                total_raised: new_total,
+            },
         );
+        notify_factory(env, "goal_reached", new_total);
     }
+}
 
-    // ── View helpers ────────────────────────────────────────────────────
-
-    /// Add a roadmap item to the campaign timeline.
-    ///
-    /// Only the creator can add roadmap items. The date must be in the future
-    /// and the description must not be empty.
-    pub fn add_roadmap_item(env: Env, date: u64, description: String) {
-        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
-        creator.require_auth();
+/// `ALERT_*` flags for the creator alerts that hold right now.
+fn current_alerts(env: &Env) -> u32 {
+    let total: i128 = get_moved(env, &DataKey::TotalRaised).unwrap();
+    let hard_cap: i128 = env.storage().instance().get(&DataKey::HardCap).unwrap();
+    let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+    let reached = goal_met(env, total);
 
-        let current_timestamp = env.ledger().timestamp();
-        if date <= current_timestamp {
-            panic!("date must be in the future");
+    let mut alerts = 0;
+    if reached {
+        alerts |= ALERT_GOAL_REACHED;
+    }
+    if status == Status::Active && !reached && is_before_deadline(env) {
+        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+        let window = match load_options(env).alert_window_secs {
+            0 => DEFAULT_ALERT_WINDOW_SECS,
+            secs => secs,
+        };
+        if deadline - env.ledger().timestamp() <= window {
+            alerts |= ALERT_DEADLINE_NEAR;
         }
+    }
+    if total.saturating_mul(10_000) >= hard_cap.saturating_mul(10_000 - HARD_CAP_ALERT_BPS) {
+        alerts |= ALERT_HARD_CAP_NEAR;
+    }
+    alerts
+}
 
-        if description.is_empty() {
-            panic!("description cannot be empty");
+/// Names of the alerts set in `flags`, in flag order.
+fn alert_names(env: &Env, flags: u32) -> Vec<Symbol> {
+    let mut names = Vec::new(env);
+    for (flag, name) in [
+        (ALERT_GOAL_REACHED, "goal_reached"),
+        (ALERT_DEADLINE_NEAR, "deadline_near"),
+        (ALERT_HARD_CAP_NEAR, "hard_cap_near"),
+    ] {
+        if flags & flag != 0 {
+            names.push_back(Symbol::new(env, name));
         }
+    }
+    names
+}
 
-        let mut roadmap: Vec<RoadmapItem> = env
-            .storage()
-            .instance()
-            .get(&DataKey::Roadmap)
-            .unwrap_or_else(|| Vec::new(&env));
+/// Emit an `alert` event for each creator alert holding for the first time.
+fn raise_new_alerts(env: &Env) {
+    let raised: u32 = env
+        .storage()
+        .instance()
+        .get(&RecordKey::AlertsRaised)
+        .unwrap_or(0);
+    let new = current_alerts(env) & !raised;
+    if new == 0 {
+        return;
+    }
+    env.storage()
+        .instance()
+        .set(&RecordKey::AlertsRaised, &(raised | new));
+    for name in alert_names(env, new).iter() {
+        events::emit(env, "alert", name);
+    }
+}
 
-        let item = RoadmapItem {
-            date,
-            description: description.clone(),
-        };
+/// Report a lifecycle transition to the factory, if any, which re-emits it
+/// for off-chain watchers. A failing factory never blocks the campaign.
+fn notify_factory(env: &Env, kind: &str, data: i128) {
+    let Some(factory) = load_options(env).factory else {
+        return;
+    };
+    let args: Vec<Val> =
+        (env.current_contract_address(), Symbol::new(env, kind), data).into_val(env);
+    let _ = env.try_invoke_contract::<(), soroban_sdk::Error>(
+        &factory,
+        &Symbol::new(env, "notify"),
+        args,
+    );
+}
+
+/// Tell the factory, if any, that the campaign was archived. A failing
+/// factory never blocks the campaign.
+fn notify_factory_archived(env: &Env) {
+    let Some(factory) = load_options(env).factory else {
+        return;
+    };
+    let args: Vec<Val> = (env.current_contract_address(),).into_val(env);
+    let _ = env.try_invoke_contract::<(), soroban_sdk::Error>(
+        &factory,
+        &Symbol::new(env, "notify_archived"),
+        args,
+    );
+}
+
+/// Portion of `total` vested at the current ledger time under the linear
+/// schedule in `options`, starting at the deadline.
+fn vested_total(env: &Env, options: &CampaignOptions, total: i128) -> i128 {
+    if options.vest_duration_secs == 0 {
+        return 0;
+    }
+    let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+    let elapsed = env.ledger().timestamp().saturating_sub(deadline);
+    if elapsed < options.cliff_secs {
+        return 0;
+    }
+    if elapsed >= options.vest_duration_secs {
+        return total;
+    }
+    total
+        .checked_mul(elapsed as i128)
+        .expect("vesting calculation overflow")
+        / options.vest_duration_secs as i128
+}
+
+/// Split a base-unit `amount` into whole tokens and the fractional
+/// remainder using the stored token decimals.
+fn to_display(env: &Env, amount: i128) -> (i128, i128, u32) {
+    let decimals: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::TokenDecimals)
+        .unwrap_or(0);
+    let scale = 10i128.pow(decimals);
+    (amount / scale, amount % scale, decimals)
+}
+
+/// Highest reward tier `contribution` qualifies for, if any.
+fn tier_for(env: &Env, contribution: i128) -> Option<String> {
+    if contribution <= 0 {
+        return None;
+    }
+
+    let tiers = load_reward_tiers(env);
+
+    let mut best: Option<RewardTier> = None;
+    for tier in tiers.iter() {
+        if contribution >= tier.min_amount {
+            let is_better = match &best {
+                None => true,
+                Some(ref b) => tier.min_amount > b.min_amount,
+            };
+            if is_better {
+                best = Some(tier.clone());
+            }
+        }
+    }
+
+    best.map(|t| t.name)
+}
+
+/// `tag` lowercased for storage. Only ASCII letters are folded.
+///
+/// # Errors
+/// * `InvalidInput` if the tag is empty, longer than `MAX_TAG_LEN` bytes or
+///   contains whitespace.
+fn normalize_tag(env: &Env, tag: &String) -> Result<String, ContractError> {
+    let len = tag.len() as usize;
+    if len == 0 || len > MAX_TAG_LEN as usize {
+        return Err(ContractError::InvalidInput);
+    }
+    let mut buf = [0u8; MAX_TAG_LEN as usize];
+    let bytes = &mut buf[..len];
+    tag.copy_into_slice(bytes);
+    if bytes.iter().any(u8::is_ascii_whitespace) {
+        return Err(ContractError::InvalidInput);
+    }
+    bytes.make_ascii_lowercase();
+    Ok(String::from_bytes(env, bytes))
+}
+
+/// Panic unless `min_amount` and `estimated_delivery` make a valid reward
+/// tier. A delivery estimate must fall after the deadline.
+fn check_reward_tier(env: &Env, min_amount: i128, estimated_delivery: u64) {
+    if min_amount <= 0 {
+        panic!("min_amount must be greater than 0");
+    }
+    let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+    if estimated_delivery != 0 && estimated_delivery <= deadline {
+        panic!("estimated delivery must be after the deadline");
+    }
+}
+
+/// The stored reward tiers, with tiers written before delivery tracking
+/// read as pending and without an estimate.
+fn load_reward_tiers(env: &Env) -> Vec<RewardTier> {
+    let stored: Vec<Val> = get_moved(env, &DataKey::RewardTiers).unwrap_or_else(|| Vec::new(env));
+    let current = Symbol::new(env, "delivery_status");
+    let mut tiers = Vec::new(env);
+    for value in stored.iter() {
+        let fields = Map::<Symbol, Val>::try_from_val(env, &value).unwrap();
+        let tier = if fields.contains_key(current.clone()) {
+            RewardTier::try_from_val(env, &value).unwrap()
+        } else {
+            let legacy = LegacyRewardTier::try_from_val(env, &value).unwrap();
+            RewardTier {
+                name: legacy.name,
+                min_amount: legacy.min_amount,
+                estimated_delivery: 0,
+                delivery_status: DELIVERY_PENDING,
+            }
+        };
+        tiers.push_back(tier);
+    }
+    tiers
+}
+
+/// Panic unless `date` and `description` make a valid roadmap item.
+fn check_roadmap_item(env: &Env, date: u64, description: &String) {
+    if date <= env.ledger().timestamp() {
+        panic!("date must be in the future");
+    }
+    if description.is_empty() {
+        panic!("description cannot be empty");
+    }
+}
+
+/// Compare `addr`'s current tier with the last one recorded for them and,
+/// if it changed, record it and emit `tier_changed` unless `silent`.
+fn note_tier_change(env: &Env, addr: &Address, silent: bool) {
+    let contribution: i128 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Contribution(addr.clone()))
+        .unwrap_or(0);
+    let new_tier = tier_for(env, contribution);
+
+    let key = RecordKey::KnownTier(addr.clone());
+    let old_tier: Option<String> = env.storage().persistent().get(&key);
+    if old_tier == new_tier {
+        return;
+    }
+    match new_tier {
+        Some(ref tier) => {
+            env.storage().persistent().set(&key, tier);
+            extend_active_record(env, &key);
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+
+    if !silent {
+        events::emit(
+            env,
+            "tier_changed",
+            TierChangedEvent {
+                contributor: addr.clone(),
+                old_tier,
+                new_tier,
+            },
+        );
+    }
+}
+
+/// Record each contributor's final tier under `TierSnapshot` and index
+/// them by tier name under `TierRoster`.
+fn snapshot_tiers(env: &Env) {
+    let contributors: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Contributors)
+        .unwrap_or_else(|| Vec::new(env));
+
+    for contributor in contributors.iter() {
+        let contribution: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contribution(contributor.clone()))
+            .unwrap_or(0);
+        let Some(tier) = tier_for(env, contribution) else {
+            continue;
+        };
+
+        let snapshot_key = DataKey::TierSnapshot(contributor.clone());
+        env.storage().persistent().set(&snapshot_key, &tier);
+        extend_audit_record(env, &snapshot_key);
+
+        let roster_key = DataKey::TierRoster(tier);
+        let mut roster: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&roster_key)
+            .unwrap_or_else(|| Vec::new(env));
+        roster.push_back(contributor);
+        env.storage().persistent().set(&roster_key, &roster);
+        extend_audit_record(env, &roster_key);
+    }
+}
+
+/// Move the campaign into a terminal `status` and void any outstanding
+/// pledges, which can no longer be collected. A successful campaign also
+/// snapshots each backer's reward tier for fulfillment.
+///
+/// Individual `Pledge` records are cleared lazily: `pledge_amount` and
+/// `pledger_count` read zero once the campaign is no longer Active.
+fn close_campaign(env: &Env, status: Status) {
+    set_state(env, &DataKey::Status, &status);
+    match status {
+        Status::Successful => notify_factory(
+            env,
+            "successful",
+            env.storage()
+                .instance()
+                .get(&RecordKey::PaidToCreator)
+                .unwrap_or(0),
+        ),
+        Status::Refunded => notify_factory(
+            env,
+            "refunded",
+            get_moved(env, &DataKey::TotalRaised).unwrap_or(0),
+        ),
+        Status::Cancelled => notify_factory(
+            env,
+            "cancelled",
+            get_moved(env, &DataKey::TotalRaised).unwrap_or(0),
+        ),
+        Status::Active => {}
+    }
+
+    if status == Status::Successful {
+        snapshot_tiers(env);
+        if !env.storage().instance().has(&RecordKey::LastHeartbeat) {
+            env.storage()
+                .instance()
+                .set(&RecordKey::LastHeartbeat, &env.ledger().timestamp());
+        }
+    }
+    if status == Status::Refunded {
+        env.storage()
+            .instance()
+            .set(&RecordKey::RefundedAt, &env.ledger().timestamp());
+        load_refund_pool(env);
+    }
+
+    let total_pledged: i128 = get_moved(env, &DataKey::TotalPledged).unwrap_or(0);
+    if total_pledged > 0 {
+        set_moved(env, &DataKey::TotalPledged, &0i128);
+        events::emit(env, "pledges_voided", total_pledged);
+    }
+}
+
+/// Move an Active campaign whose refunds are due into `Refunded`, or accept
+/// one that is already refunding.
+///
+/// Refunds are due after the deadline when the goal was not met or the
+/// withdrawal approval vote failed.
+fn enter_refund_mode(env: &Env) -> Result<(), ContractError> {
+    let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+    match status {
+        Status::Refunded => Ok(()),
+        Status::Active => {
+            if is_before_deadline(env) {
+                return Err(ContractError::CampaignStillActive);
+            }
+
+            let total: i128 = get_moved(env, &DataKey::TotalRaised).unwrap();
+            if goal_met(env, total)
+                && quorum_met(env)
+                && !approval_vote_failed(env, &load_options(env))
+            {
+                return Err(ContractError::GoalReached);
+            }
+
+            close_campaign(env, Status::Refunded);
+            Ok(())
+        }
+        _ => panic!("campaign is not refundable"),
+    }
+}
+
+/// Whether a Successful campaign's creator has gone longer than the grace
+/// period without a heartbeat.
+fn heartbeat_overdue(env: &Env) -> bool {
+    let interval = load_options(env).heartbeat_interval_secs;
+    let Some(last) = env
+        .storage()
+        .instance()
+        .get::<_, u64>(&RecordKey::LastHeartbeat)
+    else {
+        return false;
+    };
+    if interval == 0 {
+        return false;
+    }
+    let grace = interval.saturating_mul(HEARTBEAT_GRACE_BPS) / 10_000;
+    env.ledger().timestamp() > last.saturating_add(grace)
+}
+
+/// Whether a contributor has flagged the creator as inactive.
+fn creator_flagged_inactive(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&RecordKey::CreatorInactive)
+        .unwrap_or(false)
+}
+
+/// Whether the refund claim window of a `Refunded` campaign has closed.
+fn refund_window_closed(env: &Env) -> bool {
+    let window = load_options(env).refund_claim_window_secs;
+    if window == 0 {
+        return false;
+    }
+    let refunded_at: u64 = env
+        .storage()
+        .instance()
+        .get(&RecordKey::RefundedAt)
+        .unwrap_or(0);
+    env.ledger().timestamp() > refunded_at.saturating_add(window)
+}
+
+/// Add `delta` to the primary-token amount held for unspent vouchers.
+fn adjust_voucher_escrow(env: &Env, delta: i128) {
+    let escrow: i128 = env
+        .storage()
+        .instance()
+        .get(&ExtraKey::VoucherEscrow)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&ExtraKey::VoucherEscrow, &(escrow + delta));
+}
+
+/// The contract's primary-token balance, less what unspent vouchers hold.
+fn campaign_balance(env: &Env, token_client: &token::Client) -> i128 {
+    let escrow: i128 = env
+        .storage()
+        .instance()
+        .get(&ExtraKey::VoucherEscrow)
+        .unwrap_or(0);
+    token_client.balance(&env.current_contract_address()) - escrow
+}
+
+/// Load the refund pool, freezing it from `campaign_balance` on first
+/// use. The principal is the primary-token share of
+/// `TotalRaised`.
+fn load_refund_pool(env: &Env) -> RefundPool {
+    if let Some(pool) = env.storage().instance().get(&RecordKey::RefundPool) {
+        return pool;
+    }
+    let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+    let total: i128 = get_moved(env, &DataKey::TotalRaised).unwrap_or(0);
+    let pool = RefundPool {
+        balance: campaign_balance(env, &token::Client::new(env, &token_address)),
+        principal: total - secondary_credited(env),
+        refunded: 0,
+        paid: 0,
+        bounties: 0,
+        funded: 0,
+        topped_up: 0,
+        funders: Map::new(env),
+    };
+    env.storage().instance().set(&RecordKey::RefundPool, &pool);
+    pool
+}
+
+/// Add `shortfall` to what `contributor` was refunded short, withholding
+/// `bounty` of it as a keeper bounty.
+fn record_refund_shortfall(
+    env: &Env,
+    contributor: &Address,
+    shortfall: i128,
+    bounty: i128,
+) -> Result<(), ContractError> {
+    if bounty > 0 {
+        let mut pool = load_refund_pool(env);
+        pool.bounties = pool
+            .bounties
+            .checked_add(bounty)
+            .ok_or(ContractError::Overflow)?;
+        env.storage().instance().set(&RecordKey::RefundPool, &pool);
+    }
+    if shortfall <= 0 {
+        return Ok(());
+    }
+    let key = RecordKey::RefundShortfall(contributor.clone());
+    let (recorded, topped_up): (i128, i128) =
+        env.storage().persistent().get(&key).unwrap_or((0, 0));
+    let recorded = recorded
+        .checked_add(shortfall)
+        .ok_or(ContractError::Overflow)?;
+    env.storage().persistent().set(&key, &(recorded, topped_up));
+    extend_audit_record(env, &key);
+    Ok(())
+}
+
+/// Pay `contributor` their share of the refund top-up: the top-up times
+/// their shortfall over the total shortfall (proration plus bounties
+/// withheld so far), capped at their own shortfall. Returns the amount
+/// paid.
+fn pay_refund_top_up(
+    env: &Env,
+    token_client: &token::Client,
+    contributor: &Address,
+) -> Result<i128, ContractError> {
+    let key = RecordKey::RefundShortfall(contributor.clone());
+    let Some((shortfall, topped_up)) = env.storage().persistent().get::<_, (i128, i128)>(&key)
+    else {
+        return Ok(0);
+    };
+    let mut pool = load_refund_pool(env);
+    let total_shortfall = (pool.principal - pool.balance).max(0) + pool.bounties;
+    if pool.funded == 0 || total_shortfall <= 0 {
+        return Ok(0);
+    }
+
+    let entitled = shortfall
+        .checked_mul(pool.funded)
+        .ok_or(ContractError::Overflow)?
+        / total_shortfall;
+    let due = (entitled.min(shortfall) - topped_up).min(pool.funded - pool.topped_up);
+    if due <= 0 {
+        return Ok(0);
+    }
+
+    token_client.transfer(&env.current_contract_address(), contributor, &due);
+    pool.topped_up += due;
+    env.storage().instance().set(&RecordKey::RefundPool, &pool);
+    env.storage()
+        .persistent()
+        .set(&key, &(shortfall, topped_up + due));
+    extend_audit_record(env, &key);
+
+    events::emit(
+        env,
+        "refund_topped_up",
+        RefundTopUpEvent {
+            recipient: contributor.clone(),
+            amount: due,
+        },
+    );
+    Ok(due)
+}
+
+/// Return the unpaid refund top-up to its funders, in proportion to what
+/// each added; the last funder also receives the rounding remainder.
+fn return_refund_top_up(env: &Env, token_client: &token::Client) {
+    let Some(mut pool) = env
+        .storage()
+        .instance()
+        .get::<_, RefundPool>(&RecordKey::RefundPool)
+    else {
+        return;
+    };
+    let leftover = pool.funded - pool.topped_up;
+    if leftover <= 0 {
+        return;
+    }
+
+    let mut returned = 0;
+    for (i, (funder, added)) in pool.funders.iter().enumerate() {
+        let amount = if i as u32 == pool.funders.len() - 1 {
+            leftover - returned
+        } else {
+            added * leftover / pool.funded
+        };
+        returned += amount;
+        if amount > 0 {
+            token_client.transfer(&env.current_contract_address(), &funder, &amount);
+            events::emit(
+                env,
+                "top_up_returned",
+                RefundTopUpEvent {
+                    recipient: funder,
+                    amount,
+                },
+            );
+        }
+    }
+    pool.topped_up = pool.funded;
+    env.storage().instance().set(&RecordKey::RefundPool, &pool);
+}
+
+/// The campaign's `RefundOrder`.
+fn refund_order(env: &Env) -> RefundOrder {
+    env.storage()
+        .instance()
+        .get(&RecordKey::RefundOrder)
+        .unwrap_or_default()
+}
+
+/// Index into `Contributors` of the `position`-th contributor to refund
+/// among `len`.
+fn refund_index(env: &Env, position: u32, len: u32) -> u32 {
+    match refund_order(env) {
+        RefundOrder::Lifo => len - 1 - position,
+        RefundOrder::Fifo | RefundOrder::ProRataOnly => position,
+    }
+}
+
+/// Primary-token refund owed on `primary` of principal.
+///
+/// Paid in full when the frozen balance covers the principal, unless the
+/// campaign refunds `ProRataOnly`. Otherwise
+/// each refund is `primary * balance / principal`, rounded down, and the
+/// last claimant also receives the rounding remainder, so the refunds sum
+/// to the balance.
+fn prorate_refund(env: &Env, contributor: &Address, primary: i128) -> Result<i128, ContractError> {
+    let mut pool = load_refund_pool(env);
+    if pool.balance >= pool.principal && refund_order(env) != RefundOrder::ProRataOnly {
+        return Ok(primary);
+    }
+
+    pool.refunded = pool
+        .refunded
+        .checked_add(primary)
+        .ok_or(ContractError::Overflow)?;
+    let refund = if pool.refunded >= pool.principal {
+        pool.balance - pool.paid
+    } else {
+        primary
+            .checked_mul(pool.balance)
+            .ok_or(ContractError::Overflow)?
+            / pool.principal
+    };
+    pool.paid += refund;
+    env.storage().instance().set(&RecordKey::RefundPool, &pool);
+
+    events::emit(
+        env,
+        "prorated_refund",
+        ProratedRefundEvent {
+            contributor: contributor.clone(),
+            contribution: primary,
+            refunded: refund,
+            balance: pool.balance,
+            total_raised: pool.principal,
+        },
+    );
+    Ok(refund)
+}
+
+/// Refund `contributor`'s whole contribution, withholding `bounty_bps` of
+/// it for the keeper, and clear their contribution, cooldown and anonymous
+/// records.
+///
+/// The primary-token part is prorated when the refund pool is short, and
+/// any shortfall (proration plus bounty) is recorded for a later top-up.
+/// Returns `(refunded, bounty)` where `refunded` is the gross amount
+/// removed from the contribution. The caller pays the bounty and adjusts
+/// `TotalRaised`.
+fn refund_contributor(
+    env: &Env,
+    token_client: &token::Client,
+    contributor: &Address,
+    bounty_bps: u32,
+) -> Result<(i128, i128), ContractError> {
+    let contribution_key = DataKey::Contribution(contributor.clone());
+    let amount: i128 = env
+        .storage()
+        .persistent()
+        .get(&contribution_key)
+        .unwrap_or(0);
+
+    let mut bounty = 0;
+    if amount > 0 {
+        // Secondary tokens are returned in full; the keeper bounty comes
+        // out of the primary-token part.
+        let owed = amount - refund_token_contributions(env, contributor);
+        let primary = prorate_refund(env, contributor, owed)?;
+        bounty = primary
+            .checked_mul(bounty_bps as i128)
+            .ok_or(ContractError::Overflow)?
+            / 10_000;
+        token_client.transfer(
+            &env.current_contract_address(),
+            contributor,
+            &(primary - bounty),
+        );
+        record_refund_shortfall(env, contributor, owed - (primary - bounty), bounty)?;
+        env.storage().persistent().set(&contribution_key, &0i128);
+        extend_audit_record(env, &contribution_key);
+        clear_cooldown(env, contributor);
+        adjust_active_contributors(env, -1);
+        // Anonymous backers are not revealed through a tier change.
+        let anonymous = env
+            .storage()
+            .persistent()
+            .has(&DataKey::AnonymousAmount(contributor.clone()));
+        note_tier_change(env, contributor, anonymous);
+        reverse_referral_credits(env, contributor)?;
+        unbook_daily_total(env, contributor, amount);
+
+        let refunded_key = RecordKey::Refunded(contributor.clone());
+        let refunded: i128 = env.storage().persistent().get(&refunded_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&refunded_key, &(refunded + amount));
+        extend_audit_record(env, &refunded_key);
+    }
+
+    let anon_key = DataKey::AnonymousAmount(contributor.clone());
+    if let Some(anon_amount) = env.storage().persistent().get::<_, i128>(&anon_key) {
+        let anon_total: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::AnonymousTotal)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::AnonymousTotal, &(anon_total - anon_amount));
+        env.storage().persistent().remove(&anon_key);
+    }
+
+    Ok((amount, bounty))
+}
+
+/// Record a contributor's latest memo and push it onto the bounded
+/// `RecentMemos` ring buffer, evicting the oldest entry when full.
+fn store_memo(env: &Env, contributor: &Address, memo: &String, anonymous: bool) {
+    let memo_key = DataKey::Memo(contributor.clone());
+    env.storage().persistent().set(&memo_key, memo);
+    extend_audit_record(env, &memo_key);
+
+    let mut recent: Vec<MemoEntry> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::RecentMemos)
+        .unwrap_or_else(|| Vec::new(env));
+    if recent.len() >= MEMO_BUFFER_SIZE {
+        recent.pop_front();
+    }
+    recent.push_back(MemoEntry {
+        contributor: if anonymous {
+            None
+        } else {
+            Some(contributor.clone())
+        },
+        memo: memo.clone(),
+        timestamp: env.ledger().timestamp(),
+    });
+    env.storage()
+        .persistent()
+        .set(&DataKey::RecentMemos, &recent);
+    extend_audit_record(env, &DataKey::RecentMemos);
+}
+
+/// Store a `Receipt` under the next sequential id and return the id.
+fn issue_receipt(
+    env: &Env,
+    contributor: Option<Address>,
+    amount: i128,
+    referral: Option<Address>,
+) -> u64 {
+    let id: u64 = env
+        .storage()
+        .instance()
+        .get(&RecordKey::ReceiptCount)
+        .unwrap_or(0);
+    let receipt_key = RecordKey::Receipt(id);
+    env.storage().persistent().set(
+        &receipt_key,
+        &Receipt {
+            id,
+            contributor,
+            amount,
+            timestamp: env.ledger().timestamp(),
+            referral,
+        },
+    );
+    extend_audit_record(env, &receipt_key);
+    env.storage()
+        .instance()
+        .set(&RecordKey::ReceiptCount, &(id + 1));
+    id
+}
+
+/// Whether `addr` asked to be forgotten.
+fn is_forgotten(env: &Env, addr: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&RecordKey::Forgotten(addr.clone()))
+}
+
+/// `receipt` with forgotten addresses blanked out.
+fn redact_receipt(env: &Env, mut receipt: Receipt) -> Receipt {
+    if receipt
+        .contributor
+        .as_ref()
+        .is_some_and(|addr| is_forgotten(env, addr))
+    {
+        receipt.contributor = None;
+    }
+    if receipt
+        .referral
+        .as_ref()
+        .is_some_and(|addr| is_forgotten(env, addr))
+    {
+        receipt.referral = None;
+    }
+    receipt
+}
+
+/// Add `amount` to the running total transferred to the creator.
+fn add_paid_to_creator(env: &Env, amount: i128) {
+    let paid: i128 = env
+        .storage()
+        .instance()
+        .get(&RecordKey::PaidToCreator)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&RecordKey::PaidToCreator, &(paid + amount));
+}
+
+/// Add `amount` credited to `contributor` to the current day's bucket in
+/// `DailyTotals`, and to their own `ContributionDays`. Either map evicts
+/// its oldest day once more than `MAX_DAILY_BUCKETS` are held.
+fn record_daily_total(env: &Env, contributor: &Address, amount: i128) {
+    let day = env.ledger().timestamp() / SECONDS_PER_DAY;
+    let mut totals = load_daily_totals(env);
+    totals.set(day, totals.get(day).unwrap_or(0) + amount);
+    if totals.len() > MAX_DAILY_BUCKETS {
+        let oldest = totals.keys().first().unwrap();
+        totals.remove(oldest);
+    }
+    save_daily_totals(env, &totals);
+
+    let days_key = ExtraKey::ContributionDays(contributor.clone());
+    let mut days: Map<u64, i128> = env
+        .storage()
+        .persistent()
+        .get(&days_key)
+        .unwrap_or_else(|| Map::new(env));
+    days.set(day, days.get(day).unwrap_or(0) + amount);
+    if days.len() > MAX_DAILY_BUCKETS {
+        let oldest = days.keys().first().unwrap();
+        days.remove(oldest);
+    }
+    env.storage().persistent().set(&days_key, &days);
+    extend_active_record(env, &days_key);
+}
+
+/// Take `amount` refunded to or reversed from `contributor` back out of the
+/// days they contributed on, most recent first. Days already evicted from
+/// `DailyTotals` are left alone, so no bucket is created or goes negative.
+fn unbook_daily_total(env: &Env, contributor: &Address, amount: i128) {
+    let days_key = ExtraKey::ContributionDays(contributor.clone());
+    let Some(mut days) = env
+        .storage()
+        .persistent()
+        .get::<_, Map<u64, i128>>(&days_key)
+    else {
+        return;
+    };
+    let mut totals = load_daily_totals(env);
+    let mut left = amount;
+    while left > 0 {
+        let Some(day) = days.keys().last() else {
+            break;
+        };
+        let booked = days.get(day).unwrap();
+        let taken = booked.min(left);
+        if booked > taken {
+            days.set(day, booked - taken);
+        } else {
+            days.remove(day);
+        }
+        if let Some(bucket) = totals.get(day) {
+            totals.set(day, (bucket - taken).max(0));
+        }
+        left -= taken;
+    }
+    save_daily_totals(env, &totals);
+    if days.is_empty() {
+        env.storage().persistent().remove(&days_key);
+    } else {
+        env.storage().persistent().set(&days_key, &days);
+    }
+}
+
+fn load_daily_totals(env: &Env) -> Map<u64, i128> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DailyTotals)
+        .unwrap_or_else(|| Map::new(env))
+}
+
+fn save_daily_totals(env: &Env, totals: &Map<u64, i128>) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::DailyTotals, totals);
+    extend_audit_record(env, &DataKey::DailyTotals);
+}
+
+// ── Contract ────────────────────────────────────────────────────────────────
+
+/// The main crowdfund contract implementation.
+#[contract]
+pub struct CrowdfundContract;
+
+#[contractimpl]
+impl CrowdfundContract {
+    /// Initializes a new crowdfunding campaign.
+    ///
+    /// # Arguments
+    /// * `creator`            – The campaign creator's address.
+    /// * `token`              – The token contract address used for contributions.
+    /// * `goal`               – The funding goal (in the token's smallest unit).
+    /// * `hard_cap`           – Maximum total amount that can be raised (must be >= goal).
+    /// * `deadline`           – The campaign deadline as a ledger timestamp.
+    /// * `min_contribution`   – The minimum contribution amount.
+    /// * `platform_config`    – Optional platform configuration (address and fee in basis points).
+    /// * `options`            – Optional campaign behaviour (co-signer, approval vote, etc.).
+    /// * `init_nonce`         – Optional caller-chosen nonce. Repeating a call with the
+    ///   same nonce and arguments succeeds without changes, so deployers can retry safely.
+    ///
+    /// # Errors
+    /// * `AlreadyInitialized` if the campaign is already initialized, unless
+    ///   this call repeats the original arguments and `init_nonce`.
+    /// * `InvalidToken` if `token` does not respond to the token interface's
+    ///   `decimals()` call.
+    /// * `SuspiciouslySmallGoal` if `goal` is below one whole token
+    ///   (`10^decimals`) and `allow_dust_goal` is not set.
+    ///
+    /// # Panics
+    /// * If platform fee exceeds 10,000 (100%).
+    /// * If the approval, dispute or referral share exceeds 10,000 (100%).
+    /// * If a dispute threshold is set without an admin.
+    /// * If the vesting cliff is longer than the vesting duration.
+    /// * If the start time is not before the deadline.
+    /// * If the keeper bounty exceeds 50 bps.
+    /// * If the pledge deadline is not before the deadline.
+    /// * If a fulfillment escrow lacks an admin or release delay, is
+    ///   combined with vesting, or exceeds 100% with the platform fee.
+    pub fn initialize(
+        env: Env,
+        creator: Address,
+        token: Address,
+        goal: i128,
+        hard_cap: i128,
+        deadline: u64,
+        min_contribution: i128,
+        platform_config: Option<PlatformConfig>,
+        options: Option<CampaignOptions>,
+        init_nonce: Option<soroban_sdk::BytesN<32>>,
+    ) -> Result<(), ContractError> {
+        let init_hash: soroban_sdk::BytesN<32> = env
+            .crypto()
+            .sha256(
+                &(
+                    creator.clone(),
+                    token.clone(),
+                    goal,
+                    hard_cap,
+                    deadline,
+                    min_contribution,
+                    platform_config.clone(),
+                    options.clone(),
+                    init_nonce.clone(),
+                )
+                    .to_xdr(&env),
+            )
+            .into();
+
+        // Prevent re-initialization, except for a retry of the same call.
+        if env.storage().instance().has(&DataKey::Creator) {
+            let stored: Option<soroban_sdk::BytesN<32>> =
+                env.storage().instance().get(&RecordKey::InitHash);
+            if init_nonce.is_some() && stored == Some(init_hash) {
+                return Ok(());
+            }
+            return Err(ContractError::AlreadyInitialized);
+        }
+
+        creator.require_auth();
+
+        if hard_cap < goal {
+            return Err(ContractError::InvalidHardCap);
+        }
+
+        // Sanity-check the token by calling its `decimals()`; a typo'd
+        // address would otherwise trap inside every later transfer.
+        let decimals = match token::Client::new(&env, &token).try_decimals() {
+            Ok(Ok(decimals)) => decimals,
+            _ => return Err(ContractError::InvalidToken),
+        };
+
+        // Goals are in base units; a goal below one whole token almost always
+        // means the creator forgot to scale by the token's decimals.
+        let options = options.unwrap_or_default();
+        let one_token = 10i128
+            .checked_pow(decimals)
+            .ok_or(ContractError::InvalidToken)?;
+        if goal < one_token && !options.allow_dust_goal {
+            return Err(ContractError::SuspiciouslySmallGoal);
+        }
+
+        // Validate platform fee if provided.
+        if let Some(ref config) = platform_config {
+            if config.fee_bps > 10_000 {
+                panic!("platform fee cannot exceed 100%");
+            }
+        }
+
+        env.storage().instance().set(&DataKey::Creator, &creator);
+        env.storage()
+            .instance()
+            .set(&RecordKey::InitHash, &init_hash);
+        env.storage()
+            .instance()
+            .set(&RecordKey::InitializedAt, &env.ledger().timestamp());
+        env.storage().instance().set(&DataKey::Token, &token);
+        env.storage()
+            .instance()
+            .set(&DataKey::TokenDecimals, &decimals);
+
+        env.storage().instance().set(&DataKey::Goal, &goal);
+        env.storage().instance().set(&DataKey::HardCap, &hard_cap);
+        set_state(&env, &DataKey::Deadline, &deadline);
+        env.storage()
+            .instance()
+            .set(&DataKey::MinContribution, &min_contribution);
+        set_total_raised(&env, 0);
+        set_state(&env, &DataKey::Status, &Status::Active);
+        set_state(&env, &DataKey::Paused, &false);
+
+        if let Some(config) = platform_config {
+            env.storage()
+                .instance()
+                .set(&DataKey::PlatformConfig, &config);
+        }
+
+        if options.approval_required_bps > 10_000 {
+            panic!("approval threshold cannot exceed 100%");
+        }
+        if options.dispute_threshold_bps > 10_000 {
+            panic!("dispute threshold cannot exceed 100%");
+        }
+        if options.dispute_threshold_bps > 0 && options.admin.is_none() {
+            panic!("dispute resolution requires an admin");
+        }
+        if options.cliff_secs > options.vest_duration_secs {
+            panic!("vesting cliff cannot exceed duration");
+        }
+        if options.start_time != 0 && options.start_time >= deadline {
+            panic!("start time must be before the deadline");
+        }
+        if options.max_referral_share_bps > 10_000 {
+            panic!("referral share cannot exceed 100%");
+        }
+        if options.keeper_bounty_bps > MAX_KEEPER_BOUNTY_BPS {
+            panic!("keeper bounty cannot exceed 0.5%");
+        }
+        if options.pledge_deadline != 0 && options.pledge_deadline >= deadline {
+            panic!("pledge deadline must be before the deadline");
+        }
+        if options.report_referrals_to_factory && options.factory.is_none() {
+            panic!("referral reporting requires a factory");
+        }
+        if options.max_contribution_per_address != 0
+            && options.max_contribution_per_address < min_contribution
+        {
+            panic!("per-address cap below minimum contribution");
+        }
+        if options.refund_claim_window_secs > 0
+            && (options.sweep_recipient.is_none() || options.admin.is_none())
+        {
+            panic!("refund claim window requires an admin and a sweep recipient");
+        }
+        if options.oracle.is_some() != (options.goal_usd > 0) {
+            panic!("USD goal requires an oracle and a positive goal");
+        }
+        if options.fulfillment_escrow_bps > 0 {
+            if options.admin.is_none() || options.escrow_release_delay_secs == 0 {
+                panic!("fulfillment escrow requires an admin and a release delay");
+            }
+            if options.vest_duration_secs > 0 {
+                panic!("vesting campaigns cannot hold an escrow");
+            }
+            let fee_bps = withdraw_fee_config(&env).map_or(0, |config| config.fee_bps);
+            if fee_bps + options.fulfillment_escrow_bps > 10_000 {
+                panic!("platform fee and escrow cannot exceed 100%");
+            }
+        }
+        if let Some(ref tokens) = options.secondary_tokens {
+            if tokens.len() > MAX_SECONDARY_TOKENS {
+                panic!("at most 3 accepted tokens");
+            }
+            if options.vest_duration_secs > 0 {
+                panic!("multi-token campaigns cannot vest");
+            }
+            for (i, accepted) in tokens.iter().enumerate() {
+                if accepted.rate <= 0 {
+                    panic!("conversion rate must be positive");
+                }
+                if accepted.token == token
+                    || tokens
+                        .iter()
+                        .skip(i + 1)
+                        .any(|other| other.token == accepted.token)
+                {
+                    panic!("duplicate accepted token");
+                }
+            }
+        }
+        if let Some(ref admin) = options.admin {
+            env.storage().instance().set(&DataKey::Admin, admin);
+        }
+        env.storage().instance().set(&DataKey::Options, &options);
+
+        let empty_contributors: Vec<Address> = Vec::new(&env);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Contributors, &empty_contributors);
+
+        let empty_roadmap: Vec<RoadmapItem> = Vec::new(&env);
+        set_moved(&env, &DataKey::Roadmap, &empty_roadmap);
+
+        let empty_reward_tiers: Vec<RewardTier> = Vec::new(&env);
+        set_moved(&env, &DataKey::RewardTiers, &empty_reward_tiers);
+
+        Ok(())
+    }
+
+    /// Contribute tokens to the campaign.
+    ///
+    /// The contributor must authorize the call. Contributions are rejected
+    /// after the deadline has passed.
+    ///
+    /// When `anonymous` is true the contribution is still recorded under the
+    /// contributor's address (so refunds work), but the address is excluded
+    /// from `get_contributors` and the emitted event carries the amount only.
+    ///
+    /// An optional public `memo` of at most 140 bytes may be attached;
+    /// longer memos are rejected with `InvalidInput`.
+    ///
+    /// An optional `source_tag` attributes the contribution to a marketing
+    /// channel registered with `register_source_tag`; unregistered tags are
+    /// credited to `other`.
+    ///
+    /// An optional `idempotency_key` lets payment processors retry safely:
+    /// a later call reusing the key within `IDEMPOTENCY_WINDOW_SECS` is
+    /// rejected, and `idempotency_result` returns the amount originally
+    /// accepted.
+    ///
+    /// `token_index` selects the token paid in: `0` is the primary token,
+    /// `1` and `2` the campaign's `secondary_tokens`. A secondary-token
+    /// `amount` is counted toward the goal at its static rate; limits and
+    /// the returned amount are goal-denominated.
+    ///
+    /// Returns the accepted amount: contributions beyond the hard cap are
+    /// truncated to the remaining headroom, and when that headroom is below
+    /// `min_contribution` a top-up of exactly the headroom is accepted.
+    /// A contribution that trips the circuit breaker accepts 0 rather than
+    /// failing, since failing would undo the pause; later calls then fail
+    /// with `ContractPaused`. A contribution made during the contributor's
+    /// cooldown also accepts 0, and is counted in `rate_limit_hits`.
+    ///
+    /// # Errors
+    /// * `CampaignNotActive` if the campaign was cancelled or settled.
+    /// * `UnsupportedToken` if `token_index` names no accepted token.
+    /// * `DuplicateIdempotencyKey` if `idempotency_key` was already used.
+    pub fn contribute(
+        env: Env,
+        contributor: Address,
+        amount: i128,
+        token_index: u32,
+        referral: Option<Address>,
+        anonymous: bool,
+        memo: Option<String>,
+        source_tag: Option<Symbol>,
+        idempotency_key: Option<BytesN<32>>,
+    ) -> Result<i128, ContractError> {
+        record_contribution(
+            &env,
+            &contributor,
+            &contributor,
+            amount,
+            token_index,
+            referral,
+            anonymous,
+            memo,
+            source_tag,
+            idempotency_key,
+            Funding::Transfer,
+        )
+        .map(|(_, credited)| credited)
+    }
+
+    /// Dry-run `contribute` for `amount` of the primary token without
+    /// authorization or state changes.
+    ///
+    /// Runs the same checks in the same order as `contribute` and reports
+    /// the amount that would be accepted after hard-cap truncation, or the
+    /// error it would fail with. An amount below the minimum, which
+    /// `contribute` rejects with a panic, is reported as `BelowMinimum`, and
+    /// one it would accept as 0 during the cooldown as `RateLimitExceeded`.
+    pub fn can_contribute(env: Env, contributor: Address, amount: i128) -> ContributeCheck {
+        match check_contribution(&env, &contributor, &contributor, amount, &None, true, true) {
+            Ok(accepted) => ContributeCheck {
+                ok: true,
+                accepted_amount: accepted.amount,
+                reason: 0,
+            },
+            Err(err) => ContributeCheck {
+                ok: false,
+                accepted_amount: 0,
+                reason: err as u32,
+            },
+        }
+    }
+
+    /// Contribute tokens on behalf of a different beneficiary.
+    ///
+    /// The payer authorizes and funds the transfer, while the contribution
+    /// record, contributor list entry, reward-tier qualification, and refund
+    /// rights all accrue to the beneficiary. Rate limiting is keyed on the
+    /// payer.
+    ///
+    /// # Arguments
+    /// * `payer`       – The address funding the contribution (must authorize).
+    /// * `beneficiary` – The address credited with the contribution.
+    /// * `amount`      – The amount to contribute.
+    /// * `referral`    – Optional referrer address.
+    /// * `idempotency_key` – Optional retry key (see `contribute`).
+    ///
+    /// Returns the accepted amount (see `contribute`).
+    pub fn contribute_for(
+        env: Env,
+        payer: Address,
+        beneficiary: Address,
+        amount: i128,
+        referral: Option<Address>,
+        idempotency_key: Option<BytesN<32>>,
+    ) -> Result<i128, ContractError> {
+        record_contribution(
+            &env,
+            &payer,
+            &beneficiary,
+            amount,
+            0,
+            referral,
+            false,
+            None,
+            None,
+            idempotency_key,
+            Funding::Transfer,
+        )
+        .map(|(_, credited)| credited)
+    }
+
+    /// Escrow `amount` of the primary token as a voucher that whoever holds
+    /// the code hashing to `code_hash` can redeem as their contribution.
+    ///
+    /// # Errors
+    /// * `CampaignNotActive` if the campaign is no longer Active.
+    /// * `CampaignEnded` if the deadline has passed.
+    /// * `InvalidInput` if `amount` is below the minimum contribution or a
+    ///   voucher already uses `code_hash`.
+    pub fn create_voucher(
+        env: Env,
+        funder: Address,
+        amount: i128,
+        code_hash: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        funder.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            return Err(ContractError::CampaignNotActive);
+        }
+        if is_after_deadline(&env) {
+            return Err(ContractError::CampaignEnded);
+        }
+        let min_contribution: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MinContribution)
+            .unwrap();
+        let key = ExtraKey::Voucher(code_hash.clone());
+        if amount < min_contribution || env.storage().persistent().has(&key) {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        token::Client::new(&env, &token_address).transfer(
+            &funder,
+            &env.current_contract_address(),
+            &amount,
+        );
+        adjust_voucher_escrow(&env, amount);
+        let voucher = Voucher {
+            funder: funder.clone(),
+            amount,
+            spent: false,
+        };
+        env.storage().persistent().set(&key, &voucher);
+        extend_audit_record(&env, &key);
+
+        events::emit(
+            &env,
+            "voucher_created",
+            VoucherEvent {
+                code_hash,
+                account: funder,
+                amount,
+            },
+        );
+        Ok(())
+    }
+
+    /// Redeem the voucher whose code is `code_preimage`, crediting its
+    /// amount to `redeemer` as a primary-token contribution under the usual
+    /// checks. Any part the hard cap turns away goes back to the funder.
+    ///
+    /// Returns the accepted amount. Nothing is accepted, and the voucher
+    /// stays unspent, when the contribution trips the circuit breaker.
+    ///
+    /// # Errors
+    /// * `InvalidVoucher` if no unspent voucher matches the code.
+    /// * Any error `contribute` returns for the redeemer.
+    pub fn redeem_voucher(
+        env: Env,
+        redeemer: Address,
+        code_preimage: Bytes,
+    ) -> Result<i128, ContractError> {
+        let code_hash: BytesN<32> = env.crypto().sha256(&code_preimage).into();
+        let key = ExtraKey::Voucher(code_hash.clone());
+        let mut voucher: Voucher = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(ContractError::InvalidVoucher)?;
+        if voucher.spent {
+            return Err(ContractError::InvalidVoucher);
+        }
+
+        let (taken, accepted) = record_contribution(
+            &env,
+            &redeemer,
+            &redeemer,
+            voucher.amount,
+            0,
+            None,
+            false,
+            None,
+            None,
+            None,
+            Funding::Escrowed,
+        )?;
+        if accepted == 0 {
+            return Ok(0);
+        }
+
+        // Any entry fee was paid out of the escrow too, so only what was
+        // not taken goes back to the funder.
+        adjust_voucher_escrow(&env, -voucher.amount);
+        let returned = voucher.amount - taken;
+        if returned > 0 {
+            let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+            token::Client::new(&env, &token_address).transfer(
+                &env.current_contract_address(),
+                &voucher.funder,
+                &returned,
+            );
+        }
+        voucher.spent = true;
+        env.storage().persistent().set(&key, &voucher);
+
+        events::emit(
+            &env,
+            "voucher_redeemed",
+            VoucherEvent {
+                code_hash,
+                account: redeemer,
+                amount: accepted,
+            },
+        );
+        Ok(accepted)
+    }
+
+    /// Return an unspent voucher's escrow to its funder once the deadline
+    /// has passed or the campaign closed, alongside contributors' refunds.
+    ///
+    /// # Errors
+    /// * `InvalidVoucher` if no unspent voucher has `code_hash`.
+    /// * `CampaignStillActive` while the campaign is Active before its
+    ///   deadline.
+    pub fn reclaim_voucher(env: Env, code_hash: BytesN<32>) -> Result<(), ContractError> {
+        let key = ExtraKey::Voucher(code_hash.clone());
+        let mut voucher: Voucher = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(ContractError::InvalidVoucher)?;
+        if voucher.spent {
+            return Err(ContractError::InvalidVoucher);
+        }
+        voucher.funder.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Active && !is_after_deadline(&env) {
+            return Err(ContractError::CampaignStillActive);
+        }
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        token::Client::new(&env, &token_address).transfer(
+            &env.current_contract_address(),
+            &voucher.funder,
+            &voucher.amount,
+        );
+        adjust_voucher_escrow(&env, -voucher.amount);
+        voucher.spent = true;
+        env.storage().persistent().set(&key, &voucher);
+
+        events::emit(
+            &env,
+            "voucher_reclaimed",
+            VoucherEvent {
+                code_hash,
+                account: voucher.funder,
+                amount: voucher.amount,
+            },
+        );
+        Ok(())
+    }
+
+    /// The voucher with `code_hash`, if one was created.
+    pub fn voucher(env: Env, code_hash: BytesN<32>) -> Option<Voucher> {
+        env.storage()
+            .persistent()
+            .get(&ExtraKey::Voucher(code_hash))
+    }
+
+    /// Amount accepted for the contribution submitted with `key`, while
+    /// the key is remembered, so a processor whose retry was rejected with
+    /// `DuplicateIdempotencyKey` can reconcile.
+    pub fn idempotency_result(env: Env, key: BytesN<32>) -> Option<i128> {
+        idempotent_result(&env, &key)
+    }
+
+    /// Remove the records of `keys` older than `IDEMPOTENCY_WINDOW_SECS`
+    /// before they expire on their own. Anyone may call this.
+    ///
+    /// Returns the number of records removed.
+    pub fn prune_idempotency_keys(env: Env, keys: Vec<BytesN<32>>) -> u32 {
+        let mut removed = 0;
+        for key in keys.iter() {
+            let record_key = RecordKey::IdempotencyKey(key.clone());
+            if env.storage().persistent().has(&record_key)
+                && idempotent_result(&env, &key).is_none()
+            {
+                env.storage().persistent().remove(&record_key);
+                removed += 1;
+            }
+        }
+        removed
+    }
+
+    /// Contribute on behalf of many beneficiaries in one aggregate transfer.
+    ///
+    /// Intended for custodial integrators batching their users' deposits.
+    /// The payer authorizes and funds a single transfer of the accepted
+    /// total; each beneficiary is credited individually. Entries are
+    /// processed in order against the hard-cap headroom: the entry that
+    /// reaches the cap is truncated and later entries are skipped. Rate
+    /// limiting applies to the payer once per batch: a batch made during
+    /// the payer's cooldown accepts 0 for every entry and is counted in
+    /// `rate_limit_hits`. The circuit breaker applies to the batch total: a
+    /// batch that trips it also accepts 0 for every entry.
+    ///
+    /// Returns the accepted amount for each entry, in order.
+    ///
+    /// # Errors
+    /// * `InvalidLimit` if `entries` is empty or longer than 20.
+    /// * `HardCapExceeded` if the cap is already reached.
+    /// * `ContributorLimitReached` if a new beneficiary would exceed
+    ///   `max_contributors`.
+    /// * Any error `contribute` returns for the payer.
+    ///
+    /// # Panics
+    /// * If an entry is below the minimum contribution (or the remaining
+    ///   headroom, when that is smaller).
+    pub fn contribute_batch(
+        env: Env,
+        payer: Address,
+        entries: Vec<(Address, i128)>,
+    ) -> Result<Vec<i128>, ContractError> {
+        if entries.is_empty() || entries.len() > MAX_BATCH_ENTRIES {
+            return Err(ContractError::InvalidLimit);
+        }
+
+        let requested = entries
+            .iter()
+            .fold(0i128, |sum, (_, amount)| sum.saturating_add(amount));
+        let mut accepted = Vec::new(&env);
+        // As in `record_contribution`, a batch bounced by the payer's
+        // cooldown is refused by accepting nothing so the hit is counted.
+        match check_contribution_open(&env, &payer, requested, true) {
+            Err(ContractError::RateLimitExceeded) => {
+                payer.require_auth();
+                note_rate_limit_hit(&env, &payer);
+                for _ in entries.iter() {
+                    accepted.push_back(0);
+                }
+                return Ok(accepted);
+            }
+            checked => checked?,
+        }
+
+        let total: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap();
+        let hard_cap: i128 = env.storage().instance().get(&DataKey::HardCap).unwrap();
+        if total >= hard_cap {
+            return Err(ContractError::HardCapExceeded);
+        }
+        let min_contribution: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MinContribution)
+            .unwrap();
+
+        // As in `record_contribution`, a tripping batch is refused by
+        // accepting nothing so the pause is kept.
+        if trip_circuit_breaker(&env, requested.min(hard_cap - total)) {
+            for _ in entries.iter() {
+                accepted.push_back(0);
+            }
+            return Ok(accepted);
+        }
+        let mut batch_total: i128 = 0;
+        let mut batch_net: i128 = 0;
+        let mut entry_fees = Vec::new(&env);
+        for (beneficiary, amount) in entries.iter() {
+            let headroom = hard_cap - total - batch_total;
+            if headroom == 0 {
+                record_excess_demand(&env, amount);
+                accepted.push_back(0);
+                continue;
+            }
+            if amount < min_contribution.min(headroom) {
+                panic!("amount below minimum");
+            }
+            check_attestation(&env, &beneficiary, true)?;
+
+            let gross = amount.min(headroom);
+            check_address_cap(&env, &beneficiary, gross, 0)?;
+            check_contributor_slot(&env, &beneficiary)?;
+            record_excess_demand(&env, amount - gross);
+            let fee = entry_fee(&env, gross)?;
+            let effective_amount = gross - fee;
+            credit_contributor(&env, &beneficiary, effective_amount)?;
+            record_daily_total(&env, &beneficiary, effective_amount);
+            note_tier_change(&env, &beneficiary, false);
+            batch_total += gross;
+            batch_net += effective_amount;
+            entry_fees.push_back((beneficiary.clone(), gross, fee));
+            accepted.push_back(effective_amount);
+
+            let receipt_id = issue_receipt(&env, Some(beneficiary.clone()), effective_amount, None);
+            events::emit(
+                &env,
+                if payer == beneficiary {
+                    "contributed"
+                } else {
+                    "contributed_for"
+                },
+                ContributedEvent {
+                    contributor: beneficiary,
+                    payer: payer.clone(),
+                    requested: amount,
+                    accepted: effective_amount,
+                    total_raised: total + batch_net,
+                    receipt_id,
+                    memo: None,
+                    source_tag: None,
+                },
+            );
+        }
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+        token_client.transfer(&payer, &env.current_contract_address(), &batch_total);
+        for (beneficiary, gross, fee) in entry_fees.iter() {
+            forward_entry_fee(&env, &token_client, &beneficiary, gross, fee);
+        }
+
+        let new_total = total
+            .checked_add(batch_net)
+            .ok_or(ContractError::Overflow)?;
+        set_total_raised(&env, new_total);
+        note_goal_reached(&env, new_total);
+        if new_total == hard_cap {
+            events::emit(&env, "hard_cap_reached", hard_cap);
+        }
+        raise_new_alerts(&env);
+
+        note_first_seen(&env, &payer);
+        touch_last_contribution(&env, &payer, batch_total);
+
+        Ok(accepted)
+    }
+
+    /// Reveal a previously anonymous contribution — contributor-only.
+    ///
+    /// The contributor's address becomes visible in `get_contributors` again
+    /// and their anonymous amount is removed from `anonymous_total`.
+    pub fn reveal_contribution(env: Env, contributor: Address) {
+        contributor.require_auth();
+
+        let anon_key = DataKey::AnonymousAmount(contributor.clone());
+        let amount: i128 = env.storage().persistent().get(&anon_key).unwrap_or(0);
+        if amount == 0 {
+            panic!("no anonymous contribution");
+        }
+        env.storage().persistent().remove(&anon_key);
+
+        let anon_total: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::AnonymousTotal)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::AnonymousTotal, &(anon_total - amount));
+
+        events::emit(
+            &env,
+            "contribution_revealed",
+            ContributionRevealedEvent {
+                contributor,
+                amount,
+            },
+        );
+    }
+
+    /// Pledge tokens to the campaign without transferring them immediately.
+    ///
+    /// The pledger must authorize the call. Pledges are recorded off-chain
+    /// and only collected if the goal is met after the deadline.
+    pub fn pledge(env: Env, pledger: Address, amount: i128) -> Result<(), ContractError> {
+        pledger.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            return Err(ContractError::CampaignNotActive);
+        }
+
+        let min_contribution: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MinContribution)
+            .unwrap();
+        if amount < min_contribution {
+            panic!("amount below minimum");
+        }
+
+        if env.ledger().timestamp() < load_options(&env).start_time {
+            return Err(ContractError::CampaignNotStarted);
+        }
+
+        // An earlier pledge deadline follows the same inclusive convention.
+        let pledges_closed = match load_options(&env).pledge_deadline {
+            0 => is_after_deadline(&env),
+            pledge_deadline => env.ledger().timestamp() > pledge_deadline,
+        };
+        if pledges_closed {
+            if auto_refund_due(&env) {
+                return Err(ContractError::CampaignExpired);
+            }
+            return Err(ContractError::CampaignEnded);
+        }
+        check_attestation(&env, &pledger, true)?;
+        check_address_cap(&env, &pledger, 0, amount)?;
+        note_first_seen(&env, &pledger);
+
+        // Update the pledger's running total.
+        let pledge_key = DataKey::Pledge(pledger.clone());
+        let prev: i128 = env.storage().persistent().get(&pledge_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&pledge_key, &(prev + amount));
+        extend_active_record(&env, &pledge_key);
+
+        // Topping up re-affirms the pledge, so it restarts its validity window.
+        let time_key = DataKey::PledgeTime(pledger.clone());
+        env.storage()
+            .persistent()
+            .set(&time_key, &env.ledger().timestamp());
+        extend_active_record(&env, &time_key);
+
+        // Update the global total pledged.
+        let total_pledged: i128 = get_moved(&env, &DataKey::TotalPledged).unwrap_or(0);
+        set_moved(&env, &DataKey::TotalPledged, &(total_pledged + amount));
+
+        // Track pledger address if new.
+        let mut pledgers: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pledgers)
+            .unwrap_or_else(|| Vec::new(&env));
+        if !pledgers.contains(&pledger) {
+            pledgers.push_back(pledger.clone());
+            env.storage()
+                .persistent()
+                .set(&DataKey::Pledgers, &pledgers);
+            extend_active_record(&env, &DataKey::Pledgers);
+        }
+
+        // Emit pledge event
+        events::emit(
+            &env,
+            "pledged",
+            PledgedEvent {
+                pledger,
+                amount,
+                total_pledged: total_pledged + amount,
+                total_raised: get_moved(&env, &DataKey::TotalRaised).unwrap_or(0),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// `pledge`, rejecting a pledge the pledger's token balance could not
+    /// cover at collection.
+    ///
+    /// # Errors
+    /// * `InsufficientBalanceForPledge` if the pledger's outstanding pledge
+    ///   plus `amount` exceeds their balance.
+    /// * Any error `pledge` returns.
+    pub fn pledge_with_check(
+        env: Env,
+        pledger: Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let balance = token::Client::new(&env, &token_address).balance(&pledger);
+        let pledged: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pledge(pledger.clone()))
+            .unwrap_or(0);
+        if pledged.saturating_add(amount) > balance {
+            return Err(ContractError::InsufficientBalanceForPledge);
+        }
+        Self::pledge(env, pledger, amount)
+    }
+
+    /// Withdraw the pledger's whole pledge. Allowed until pledges are
+    /// collected, including the grace period after the deadline.
+    ///
+    /// # Errors
+    /// * `CampaignNotActive` if the campaign is no longer Active.
+    /// * `InvalidInput` if the pledger has no outstanding pledge.
+    pub fn cancel_pledge(env: Env, pledger: Address) -> Result<(), ContractError> {
+        pledger.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            return Err(ContractError::CampaignNotActive);
+        }
+        let pledge_key = DataKey::Pledge(pledger.clone());
+        let amount: i128 = env.storage().persistent().get(&pledge_key).unwrap_or(0);
+        if amount == 0 {
+            return Err(ContractError::InvalidInput);
+        }
+
+        env.storage().persistent().set(&pledge_key, &0i128);
+        let total_pledged: i128 = get_moved(&env, &DataKey::TotalPledged).unwrap_or(0);
+        set_moved(&env, &DataKey::TotalPledged, &(total_pledged - amount));
+        let flag_key = RecordKey::PledgeFlagged(pledger.clone());
+        if env.storage().persistent().has(&flag_key) {
+            env.storage().persistent().remove(&flag_key);
+            let mut flagged: Vec<Address> = env
+                .storage()
+                .persistent()
+                .get(&RecordKey::FlaggedPledges)
+                .unwrap_or_else(|| Vec::new(&env));
+            if let Some(index) = flagged.first_index_of(&pledger) {
+                flagged.remove(index);
+                env.storage()
+                    .persistent()
+                    .set(&RecordKey::FlaggedPledges, &flagged);
+            }
+        }
+
+        events::emit(
+            &env,
+            "pledge_cancelled",
+            PledgedEvent {
+                pledger,
+                amount,
+                total_pledged: total_pledged - amount,
+                total_raised: get_moved(&env, &DataKey::TotalRaised).unwrap_or(0),
+            },
+        );
+        Ok(())
+    }
+
+    /// Re-check up to `limit` pledges starting at index `offset` of the
+    /// pledger list against the pledgers' token balances. Anyone may call
+    /// this.
+    ///
+    /// An underfunded pledge gets a `pledge_flagged` event and is listed by
+    /// `flagged_pledges` until a later check finds it covered. With
+    /// `void_underfunded_pledges`, a pledge already flagged by an earlier
+    /// check is voided instead, with a `pledge_voided` event.
+    ///
+    /// Returns the number of underfunded pledges found.
+    ///
+    /// # Errors
+    /// * `CampaignNotActive` if the campaign is no longer Active.
+    pub fn revalidate_pledges(env: Env, offset: u32, limit: u32) -> Result<u32, ContractError> {
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            return Err(ContractError::CampaignNotActive);
+        }
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+        let void_underfunded = load_options(&env).void_underfunded_pledges;
+        let now = env.ledger().timestamp();
+        let pledgers: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pledgers)
+            .unwrap_or_else(|| Vec::new(&env));
+        let mut flagged: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&RecordKey::FlaggedPledges)
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let mut underfunded = 0;
+        let end = offset.saturating_add(limit).min(pledgers.len());
+        for i in offset..end {
+            let pledger = pledgers.get(i).unwrap();
+            let pledge_key = DataKey::Pledge(pledger.clone());
+            let pledged: i128 = env.storage().persistent().get(&pledge_key).unwrap_or(0);
+            let balance = token_client.balance(&pledger);
+            let flag_key = RecordKey::PledgeFlagged(pledger.clone());
+            let flagged_at: Option<u64> = env.storage().persistent().get(&flag_key);
+            if pledged == 0 || balance >= pledged {
+                if flagged_at.is_some() {
+                    env.storage().persistent().remove(&flag_key);
+                    if let Some(index) = flagged.first_index_of(&pledger) {
+                        flagged.remove(index);
+                    }
+                }
+                continue;
+            }
+
+            underfunded += 1;
+            let event = PledgeFlaggedEvent {
+                pledger: pledger.clone(),
+                pledged,
+                balance,
+            };
+            match flagged_at {
+                // A check in the same ledger is not a second check.
+                Some(flagged_at) if void_underfunded && now > flagged_at => {
+                    env.storage().persistent().set(&pledge_key, &0i128);
+                    let total_pledged: i128 = get_moved(&env, &DataKey::TotalPledged).unwrap_or(0);
+                    set_moved(&env, &DataKey::TotalPledged, &(total_pledged - pledged));
+                    env.storage().persistent().remove(&flag_key);
+                    if let Some(index) = flagged.first_index_of(&pledger) {
+                        flagged.remove(index);
+                    }
+                    events::emit(&env, "pledge_voided", event);
+                }
+                Some(_) => events::emit(&env, "pledge_flagged", event),
+                None => {
+                    env.storage().persistent().set(&flag_key, &now);
+                    extend_active_record(&env, &flag_key);
+                    flagged.push_back(pledger);
+                    events::emit(&env, "pledge_flagged", event);
+                }
+            }
+        }
+
+        env.storage()
+            .persistent()
+            .set(&RecordKey::FlaggedPledges, &flagged);
+        extend_active_record(&env, &RecordKey::FlaggedPledges);
+        Ok(underfunded)
+    }
+
+    /// Set up a plan contributing `amount_per_period` every `period_secs`,
+    /// replacing any earlier plan. The first installment is due at once.
+    ///
+    /// Installments are pulled from the contributor's token allowance to
+    /// this contract by `execute_installments`, so the contributor must
+    /// `approve` enough to cover them.
+    ///
+    /// # Errors
+    /// * `CampaignNotActive` if the campaign is no longer Active.
+    /// * `CampaignEnded` if the deadline has passed.
+    /// * `InvalidInput` if `amount_per_period` is below the minimum
+    ///   contribution or `period_secs` is zero.
+    pub fn create_installment_plan(
+        env: Env,
+        contributor: Address,
+        amount_per_period: i128,
+        period_secs: u64,
+    ) -> Result<(), ContractError> {
+        contributor.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            return Err(ContractError::CampaignNotActive);
+        }
+        if is_after_deadline(&env) {
+            return Err(ContractError::CampaignEnded);
+        }
+        let min_contribution: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MinContribution)
+            .unwrap();
+        if amount_per_period < min_contribution || period_secs == 0 {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let key = RecordKey::InstallmentPlan(contributor.clone());
+        let plan = InstallmentPlan {
+            amount_per_period,
+            period_secs,
+            next_due: env.ledger().timestamp(),
+            failures: 0,
+        };
+        env.storage().persistent().set(&key, &plan);
+        extend_active_record(&env, &key);
+
+        let mut plans: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&RecordKey::InstallmentPlans)
+            .unwrap_or_else(|| Vec::new(&env));
+        if !plans.contains(&contributor) {
+            plans.push_back(contributor);
+            env.storage()
+                .persistent()
+                .set(&RecordKey::InstallmentPlans, &plans);
+        }
+        extend_active_record(&env, &RecordKey::InstallmentPlans);
+        Ok(())
+    }
+
+    /// Cancel the contributor's installment plan. Installments already
+    /// collected stay credited.
+    ///
+    /// # Errors
+    /// * `InvalidInput` if the contributor has no plan.
+    pub fn cancel_installment_plan(env: Env, contributor: Address) -> Result<(), ContractError> {
+        contributor.require_auth();
+
+        let key = RecordKey::InstallmentPlan(contributor.clone());
+        let plan: InstallmentPlan = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(ContractError::InvalidInput)?;
+        env.storage().persistent().remove(&key);
+        events::emit(
+            &env,
+            "installment_plan_cancelled",
+            InstallmentEvent {
+                contributor,
+                amount: plan.amount_per_period,
+            },
+        );
+        Ok(())
+    }
+
+    /// Collect due installments for up to `limit` plans starting at index
+    /// `offset` of the plan list. Anyone may call this.
+    ///
+    /// Each due installment is credited like a contribution, with an
+    /// `installment_collected` event, and the plan's next due time moves on
+    /// by one period. An installment the contributor's allowance or balance
+    /// cannot cover, or that the campaign would refuse, gets an
+    /// `installment_failed` event instead; a plan failing twice in a row is
+    /// dropped.
+    ///
+    /// Returns the number of installments collected.
+    ///
+    /// # Errors
+    /// * `CampaignNotActive` if the campaign is no longer Active.
+    /// * `ContractPaused` while the campaign is paused.
+    pub fn execute_installments(env: Env, offset: u32, limit: u32) -> Result<u32, ContractError> {
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            return Err(ContractError::CampaignNotActive);
+        }
+        let paused: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false);
+        if paused {
+            return Err(ContractError::ContractPaused);
+        }
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+        let contract = env.current_contract_address();
+        let now = env.ledger().timestamp();
+        let plans: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&RecordKey::InstallmentPlans)
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let mut collected = 0;
+        let end = offset.saturating_add(limit).min(plans.len());
+        for i in offset..end {
+            let contributor = plans.get(i).unwrap();
+            let key = RecordKey::InstallmentPlan(contributor.clone());
+            let Some(mut plan) = env.storage().persistent().get::<_, InstallmentPlan>(&key) else {
+                continue;
+            };
+            if plan.next_due > now {
+                continue;
+            }
+
+            let amount = plan.amount_per_period;
+            let collectable = token_client.allowance(&contributor, &contract) >= amount
+                && token_client.balance(&contributor) >= amount
+                && check_contribution(&env, &contributor, &contributor, amount, &None, false, true)
+                    .is_ok();
+            if !collectable {
+                plan.failures += 1;
+                events::emit(
+                    &env,
+                    "installment_failed",
+                    InstallmentFailedEvent {
+                        contributor: contributor.clone(),
+                        amount,
+                        consecutive_failures: plan.failures,
+                    },
+                );
+                if plan.failures >= MAX_INSTALLMENT_FAILURES {
+                    env.storage().persistent().remove(&key);
+                    events::emit(
+                        &env,
+                        "installment_plan_cancelled",
+                        InstallmentEvent {
+                            contributor,
+                            amount,
+                        },
+                    );
+                } else {
+                    env.storage().persistent().set(&key, &plan);
+                }
+                continue;
+            }
+
+            let (_, accepted) = record_contribution(
+                &env,
+                &contributor,
+                &contributor,
+                amount,
+                0,
+                None,
+                false,
+                None,
+                None,
+                None,
+                Funding::Allowance,
+            )?;
+            // Nothing accepted means the circuit breaker paused the campaign.
+            if accepted == 0 {
+                break;
+            }
+            collected += 1;
+            plan.failures = 0;
+            plan.next_due += plan.period_secs;
+            env.storage().persistent().set(&key, &plan);
+            extend_active_record(&env, &key);
+            events::emit(
+                &env,
+                "installment_collected",
+                InstallmentEvent {
+                    contributor,
+                    amount: accepted,
+                },
+            );
+        }
+        Ok(collected)
+    }
+
+    /// Collect all pledges after the deadline when the goal is met —
+    /// callable by the creator or a keeper they allowed with
+    /// `set_pledge_keeper`.
+    ///
+    /// This function transfers tokens from all pledgers to the contract.
+    /// Only callable once the grace period set with
+    /// `set_pledge_grace_period` has passed after the deadline, leaving
+    /// pledgers a last chance to `cancel_pledge`, and when the combined
+    /// total of contributions and pledges meets or exceeds the goal. The
+    /// `pledges_collected` event names the caller.
+    ///
+    /// When `pledge_valid_secs` is configured, pledges older than that
+    /// window are cleared without being collected, do not count toward the
+    /// goal, and are added to `expired_pledges`.
+    ///
+    /// With `collect_pledges_to_goal`, collection stops once the goal is
+    /// met: the crossing pledge is collected only in part, later pledges
+    /// are voided, and each gets a `pledge_trimmed` event recording the
+    /// collected and pledged amounts.
+    ///
+    /// # Errors
+    /// * `NotAuthorized` if `caller` is neither the creator nor a keeper.
+    /// * `CampaignStillActive` before the grace period has passed.
+    pub fn collect_pledges(env: Env, caller: Address) -> Result<(), ContractError> {
+        caller.require_auth();
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        let keeper: bool = env
+            .storage()
+            .instance()
+            .get(&ExtraKey::PledgeKeeper(caller.clone()))
+            .unwrap_or(false);
+        if caller != creator && !keeper {
+            return Err(ContractError::NotAuthorized);
+        }
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            return Err(ContractError::CampaignNotActive);
+        }
+
+        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+        let grace_secs: u64 = env
+            .storage()
+            .instance()
+            .get(&ExtraKey::PledgeGraceSecs)
+            .unwrap_or(0);
+        if env.ledger().timestamp() <= deadline.saturating_add(grace_secs) {
+            return Err(ContractError::CampaignStillActive);
+        }
+
+        let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
+        let total_raised: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap();
+
+        let pledgers: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pledgers)
+            .unwrap_or_else(|| Vec::new(&env));
+
+        // Pledges older than the validity window are dropped, not collected.
+        let pledge_valid_secs = load_options(&env).pledge_valid_secs;
+        let now = env.ledger().timestamp();
+        let is_expired = |pledger: &Address| -> bool {
+            if pledge_valid_secs == 0 {
+                return false;
+            }
+            let pledged_at: u64 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PledgeTime(pledger.clone()))
+                .unwrap_or(0);
+            now > pledged_at.saturating_add(pledge_valid_secs)
+        };
+
+        let mut total_pledged: i128 = 0;
+        for pledger in pledgers.iter() {
+            if !is_expired(&pledger) {
+                let amount: i128 = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::Pledge(pledger.clone()))
+                    .unwrap_or(0);
+                total_pledged = total_pledged
+                    .checked_add(amount)
+                    .ok_or(ContractError::Overflow)?;
+            }
+        }
+
+        // Check if combined total meets the goal
+        if !goal_met(&env, total_raised + total_pledged) {
+            return Err(ContractError::GoalNotReached);
+        }
+        if !quorum_met(&env) {
+            return Err(ContractError::QuorumNotMet);
+        }
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+
+        // Collect fresh pledges and clear expired ones. When collecting only
+        // up to the goal, whatever a pledge is not needed for is released.
+        let to_goal = load_options(&env).collect_pledges_to_goal;
+        let mut expired_total: i128 = 0;
+        let mut collected: i128 = 0;
+        for pledger in pledgers.iter() {
+            let pledge_key = DataKey::Pledge(pledger.clone());
+            let amount: i128 = env.storage().persistent().get(&pledge_key).unwrap_or(0);
+            if amount > 0 {
+                if is_expired(&pledger) {
+                    expired_total += amount;
+                    events::emit(
+                        &env,
+                        "pledge_expired",
+                        PledgeExpiredEvent {
+                            pledger: pledger.clone(),
+                            amount,
+                        },
+                    );
+                } else {
+                    let take = if to_goal {
+                        amount.min((goal - total_raised - collected).max(0))
+                    } else {
+                        amount
+                    };
+                    if take > 0 {
+                        // Transfer tokens from pledger to contract
+                        token_client.transfer(&pledger, &env.current_contract_address(), &take);
+                        let fee = entry_fee(&env, take)?;
+                        forward_entry_fee(&env, &token_client, &pledger, take, fee);
+                        record_daily_total(&env, &pledger, take - fee);
+                        collected += take - fee;
+                    }
+                    if take < amount {
+                        events::emit(
+                            &env,
+                            "pledge_trimmed",
+                            PledgeTrimmedEvent {
+                                pledger: pledger.clone(),
+                                collected: take,
+                                pledged: amount,
+                            },
+                        );
+                    }
+                }
+
+                // Clear the pledge
+                env.storage().persistent().set(&pledge_key, &0i128);
+                extend_active_record(&env, &pledge_key);
+            }
+        }
+
+        if expired_total > 0 {
+            let expired: i128 = env
+                .storage()
+                .instance()
+                .get(&DataKey::ExpiredPledges)
+                .unwrap_or(0);
+            env.storage()
+                .instance()
+                .set(&DataKey::ExpiredPledges, &(expired + expired_total));
+        }
+
+        // Update total raised to include collected pledges
+        set_total_raised(&env, total_raised + collected);
+        note_goal_reached(&env, total_raised + collected);
+
+        // Reset total pledged
+        set_moved(&env, &DataKey::TotalPledged, &0i128);
+
+        // Emit pledges collected event
+        events::emit(
+            &env,
+            "pledges_collected",
+            PledgesCollectedEvent {
+                caller,
+                collected,
+                total_raised: total_raised + collected,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw raised funds — only callable by the creator after the
+    /// deadline, and only if the goal has been met.
+    ///
+    /// If a platform fee is taken at withdrawal, deducts the fee and
+    /// transfers it to the platform address, then sends the remainder to the
+    /// creator. Fees taken on entry were already paid.
+    ///
+    /// The payout is reconciled against the contract's token balance: at
+    /// most the balance is paid out, and any tokens held above
+    /// `total_raised` are set aside for `claim_surplus`.
+    ///
+    /// Callable on an Active campaign that passes the payout checks, or on
+    /// one already marked Successful by `finalize`.
+    ///
+    /// # Panics
+    /// * If the campaign uses a vesting schedule (see `withdraw_vested`).
+    /// * If the funds were already withdrawn.
+    pub fn withdraw(env: Env) -> Result<(), ContractError> {
+        let paused: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false);
+        if paused {
+            return Err(ContractError::ContractPaused);
+        }
+
+        let options = load_options(&env);
+        if options.vest_duration_secs > 0 {
+            panic!("campaign pays out via withdraw_vested");
+        }
+
+        // A finalized campaign has already passed the payout checks.
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        let (creator, total) = match status {
+            Status::Active if auto_refund_due(&env) => return Err(ContractError::CampaignExpired),
+            Status::Active => authorize_withdrawal(&env, &options)?,
+            Status::Successful => {
+                let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+                creator.require_auth();
+                if let Some(ref cosigner) = options.withdraw_cosigner {
+                    cosigner.require_auth();
+                }
+                let total: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap();
+                if total == 0 {
+                    panic!("funds already withdrawn");
+                }
+                (creator, total)
+            }
+            _ => panic!("campaign is not active"),
+        };
+        // Secondary-token contributions are paid out separately below.
+        let total = total - secondary_credited(&env);
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+
+        // Reconcile against the tokens actually held: pay out at most the
+        // balance, and set aside anything above `total` for `claim_surplus`.
+        let balance = campaign_balance(&env, &token_client);
+        let payable = balance.min(total);
+        if balance != total {
+            if balance > total {
+                let surplus: i128 = env.storage().instance().get(&DataKey::Surplus).unwrap_or(0);
+                env.storage()
+                    .instance()
+                    .set(&DataKey::Surplus, &(surplus + balance - total));
+            }
+            events::emit(
+                &env,
+                "balance_reconciled",
+                BalanceReconciledEvent {
+                    total_raised: total,
+                    balance,
+                    delta: balance - total,
+                },
+            );
+        }
+        let total = payable;
+        let split = hold_escrow(&env, split_payout(&env, total));
+
+        // Queue the platform fee, if taken at withdrawal, ahead of the
+        // creator.
+        if let Some(config) = withdraw_fee_config(&env) {
+            queue_payout(
+                &env,
+                &config.address,
+                &token_address,
+                split.platform_fee,
+                false,
+            );
+
+            // Emit event with fee details.
+            events::emit(
+                &env,
+                "fee_transferred",
+                FeeTransferredEvent {
+                    platform: config.address.clone(),
+                    fee: split.platform_fee,
+                },
+            );
+        }
+        let creator_payout = split.net_to_creator;
+
+        // Queue the remainder for the creator, then settle the queue.
+        // Settling the principal makes an Active campaign Successful.
+        queue_payout(&env, &creator, &token_address, creator_payout, true);
+        add_paid_to_creator(&env, creator_payout);
+        pay_out_secondary_tokens(&env, &creator);
+        if split.escrow > 0 {
+            env.storage().instance().set(
+                &RecordKey::Escrow,
+                &Escrow {
+                    amount: split.escrow,
+                    retained_at: env.ledger().timestamp(),
+                    raised: get_moved(&env, &DataKey::TotalRaised).unwrap(),
+                    approved: 0,
+                },
+            );
+            events::emit(&env, "escrow_retained", split.escrow);
+        }
+
+        // The receipt is written once and never updated.
+        if !env
+            .storage()
+            .persistent()
+            .has(&RecordKey::WithdrawalReceipt)
+        {
+            env.storage().persistent().set(
+                &RecordKey::WithdrawalReceipt,
+                &WithdrawalReceipt {
+                    timestamp: env.ledger().timestamp(),
+                    gross: split.gross,
+                    platform_fee: split.platform_fee,
+                    referral_rewards: split.referral_rewards,
+                    escrow_retained: split.escrow,
+                    net_to_creator: split.net_to_creator,
+                    payout_address: creator.clone(),
+                },
+            );
+            extend_audit_record(&env, &RecordKey::WithdrawalReceipt);
+        }
+
+        set_total_raised(&env, 0);
+        settle_payouts(&env, MAX_SETTLE_BATCH);
+
+        // Emit withdrawal event
+        events::emit(
+            &env,
+            "withdrawn",
+            WithdrawnEvent {
+                creator,
+                amount: total,
+                total_raised: 0,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Receipt of the `withdraw` payout, once it happened.
+    pub fn withdrawal_receipt(env: Env) -> Option<WithdrawalReceipt> {
+        env.storage()
+            .persistent()
+            .get(&RecordKey::WithdrawalReceipt)
+    }
+
+    /// Receipts of each `withdraw_vested` payout, oldest first.
+    pub fn vested_receipts(env: Env) -> Vec<WithdrawalReceipt> {
+        env.storage()
+            .persistent()
+            .get(&RecordKey::VestedReceipts)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Preview what `withdraw` would pay out now, without moving funds.
+    ///
+    /// Runs the same checks and fee math as `withdraw`, except for
+    /// authorization, so a preview that succeeds matches the withdrawal.
+    /// Secondary tokens of a multi-token campaign are not included.
+    ///
+    /// # Errors
+    /// Same as `withdraw`.
+    ///
+    /// # Panics
+    /// Same as `withdraw`.
+    pub fn withdraw_preview(env: Env) -> Result<WithdrawPreview, ContractError> {
+        let paused: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false);
+        if paused {
+            return Err(ContractError::ContractPaused);
+        }
+
+        let options = load_options(&env);
+        if options.vest_duration_secs > 0 {
+            panic!("campaign pays out via withdraw_vested");
+        }
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        let total = match status {
+            Status::Active if auto_refund_due(&env) => return Err(ContractError::CampaignExpired),
+            Status::Active => check_payout_due(&env, &options)?,
+            Status::Successful => {
+                let total: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap();
+                if total == 0 {
+                    panic!("funds already withdrawn");
+                }
+                total
+            }
+            _ => panic!("campaign is not active"),
+        };
+        let total = total - secondary_credited(&env);
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+        let balance = campaign_balance(&env, &token_client);
+        Ok(hold_escrow(&env, split_payout(&env, balance.min(total))))
+    }
+
+    /// Approve releasing the fulfillment escrow early, weighted by the
+    /// caller's contribution. Once approvals reach `approval_required_bps`
+    /// of what was raised, the creator may `release_escrow` before the
+    /// delay.
+    ///
+    /// # Panics
+    /// * If no escrow is held or the approval vote is not enabled.
+    /// * If the caller has no contribution or has already approved.
+    pub fn approve_escrow_release(env: Env, contributor: Address) {
+        contributor.require_auth();
+
+        let Some(mut escrow) = load_escrow(&env) else {
+            panic!("no escrow held");
+        };
+        if load_options(&env).approval_required_bps == 0 {
+            panic!("withdrawal approval is not enabled");
+        }
+        let approval_key = RecordKey::EscrowApproval(contributor.clone());
+        if env.storage().persistent().has(&approval_key) {
+            panic!("already approved");
+        }
+        let weight: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contribution(contributor.clone()))
+            .unwrap_or(0);
+        if weight == 0 {
+            panic!("only contributors can approve");
+        }
+
+        env.storage().persistent().set(&approval_key, &weight);
+        extend_active_record(&env, &approval_key);
+        escrow.approved += weight;
+        env.storage().instance().set(&RecordKey::Escrow, &escrow);
+
+        events::emit(
+            &env,
+            "escrow_approved",
+            ApprovalEvent {
+                contributor,
+                weight,
+            },
+        );
+    }
+
+    /// Release the fulfillment escrow to the creator — creator-only, once
+    /// `escrow_release_delay_secs` have passed since `withdraw` or backers
+    /// approved via `approve_escrow_release`.
+    ///
+    /// Returns the amount released.
+    ///
+    /// # Errors
+    /// * `EscrowLocked` before the delay without enough approvals.
+    ///
+    /// # Panics
+    /// * If no escrow is held.
+    pub fn release_escrow(env: Env) -> Result<i128, ContractError> {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        let Some(escrow) = load_escrow(&env) else {
+            panic!("no escrow held");
+        };
+        let unlocks_at = escrow
+            .retained_at
+            .saturating_add(load_options(&env).escrow_release_delay_secs);
+        if env.ledger().timestamp() < unlocks_at && !escrow_release_approved(&env, &escrow) {
+            return Err(ContractError::EscrowLocked);
+        }
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        token::Client::new(&env, &token_address).transfer(
+            &env.current_contract_address(),
+            &creator,
+            &escrow.amount,
+        );
+        env.storage().instance().remove(&RecordKey::Escrow);
+        add_paid_to_creator(&env, escrow.amount);
+
+        events::emit(
+            &env,
+            "escrow_released",
+            WithdrawnEvent {
+                creator,
+                amount: escrow.amount,
+                total_raised: get_moved(&env, &DataKey::TotalRaised).unwrap_or(0),
+            },
+        );
+        Ok(escrow.amount)
+    }
+
+    /// Settle an escrow the creator has not released — admin-only, once
+    /// `ESCROW_ARBITRATION_SECS` have passed since `withdraw`. Releases it
+    /// to the creator when `release` is true, otherwise refunds it to
+    /// contributors in proportion to their contributions.
+    ///
+    /// # Errors
+    /// * `EscrowLocked` before the arbitration period has passed.
+    ///
+    /// # Panics
+    /// * If no escrow is held.
+    pub fn arbitrate_escrow(env: Env, release: bool) -> Result<(), ContractError> {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        let Some(escrow) = load_escrow(&env) else {
+            panic!("no escrow held");
+        };
+        if env.ledger().timestamp() < escrow.retained_at.saturating_add(ESCROW_ARBITRATION_SECS) {
+            return Err(ContractError::EscrowLocked);
+        }
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+        env.storage().instance().remove(&RecordKey::Escrow);
+        if release {
+            let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+            token_client.transfer(&env.current_contract_address(), &creator, &escrow.amount);
+            add_paid_to_creator(&env, escrow.amount);
+            events::emit(
+                &env,
+                "escrow_released",
+                WithdrawnEvent {
+                    creator,
+                    amount: escrow.amount,
+                    total_raised: get_moved(&env, &DataKey::TotalRaised).unwrap_or(0),
+                },
+            );
+        } else {
+            refund_escrow(&env, &token_client, &escrow);
+            events::emit(&env, "escrow_refunded", escrow.amount);
+        }
+        Ok(())
+    }
+
+    /// Fulfillment escrow still held (`0` if none).
+    pub fn escrow_balance(env: Env) -> i128 {
+        load_escrow(&env).map_or(0, |escrow| escrow.amount)
+    }
+
+    /// Pay up to `limit` payouts still queued by `withdraw` — callable by
+    /// anyone. Failed transfers become claimable via `claim_payout`.
+    ///
+    /// Returns the number of payouts processed.
+    ///
+    /// # Errors
+    /// * `InvalidLimit` if `limit` is zero.
+    pub fn settle(env: Env, limit: u32) -> Result<u32, ContractError> {
+        if limit == 0 {
+            return Err(ContractError::InvalidLimit);
+        }
+        Ok(settle_payouts(&env, limit))
+    }
+
+    /// Pay `recipient` every payout whose transfer failed during settlement.
+    ///
+    /// # Errors
+    /// * `NothingToClaim` if nothing is owed to `recipient`.
+    pub fn claim_payout(env: Env, recipient: Address) -> Result<(), ContractError> {
+        recipient.require_auth();
+
+        let claim_key = RecordKey::ClaimablePayouts(recipient.clone());
+        let claimable: Map<Address, i128> = env
+            .storage()
+            .persistent()
+            .get(&claim_key)
+            .ok_or(ContractError::NothingToClaim)?;
+        env.storage().persistent().remove(&claim_key);
+
+        for (token, amount) in claimable.iter() {
+            token::Client::new(&env, &token).transfer(
+                &env.current_contract_address(),
+                &recipient,
+                &amount,
+            );
+            events::emit(
+                &env,
+                "payout_claimed",
+                PayoutEvent {
+                    recipient: recipient.clone(),
+                    token,
+                    amount,
+                },
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the payouts queued by `withdraw` and not yet settled.
+    pub fn pending_payouts(env: Env) -> Vec<Payout> {
+        load_pending_payouts(&env)
+    }
+
+    /// Returns the amount of `token` claimable by `recipient` after a
+    /// failed payout.
+    pub fn claimable_payout(env: Env, recipient: Address, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get::<_, Map<Address, i128>>(&RecordKey::ClaimablePayouts(recipient))
+            .and_then(|claimable| claimable.get(token))
+            .unwrap_or(0)
+    }
+
+    /// Extend the storage TTL of up to `limit` receipts starting at id
+    /// `offset`. Anyone may call this to keep the audit trail alive.
+    ///
+    /// Returns the number of receipts extended.
+    pub fn extend_receipts_ttl(env: Env, offset: u64, limit: u32) -> u32 {
+        let count = Self::receipt_count(env.clone());
+        let mut extended = 0;
+        let mut id = offset;
+        while id < count && extended < limit {
+            let receipt_key = RecordKey::Receipt(id);
+            if env.storage().persistent().has(&receipt_key) {
+                extend_audit_record(&env, &receipt_key);
+                extended += 1;
+            }
+            id += 1;
+        }
+        extended
+    }
+
+    /// Re-bump the audit records of up to `limit` contributors starting at
+    /// index `offset` of the contributor list — their contribution, refund,
+    /// tier snapshot and dispute records — along with the contract instance
+    /// and the campaign-wide expense log and tier rosters. Anyone may call
+    /// this; receipts are kept alive by `extend_receipts_ttl`. Publishes a
+    /// `checkpoint` event with the campaign's totals.
+    ///
+    /// Returns the number of contributors processed.
+    pub fn extend_storage(env: Env, offset: u32, limit: u32) -> u32 {
+        let ttl = audit_record_ttl(&env);
+        env.storage().instance().extend_ttl(ttl, ttl);
+
+        let persistent = env.storage().persistent();
+        let bump = |key: &Val| {
+            if persistent.has(key) {
+                extend_audit_record(&env, key);
+            }
+        };
+        if offset == 0 {
+            bump(&DataKey::Contributors.into_val(&env));
+            bump(&RecordKey::Expenses.into_val(&env));
+            for tier in Self::reward_tiers(env.clone()).iter() {
+                bump(&DataKey::TierRoster(tier.name).into_val(&env));
+            }
+        }
+
+        let contributors: Vec<Address> = persistent
+            .get(&DataKey::Contributors)
+            .unwrap_or_else(|| Vec::new(&env));
+        let end = offset.saturating_add(limit).min(contributors.len());
+        for i in offset..end {
+            let contributor = contributors.get(i).unwrap();
+            bump(&DataKey::Contribution(contributor.clone()).into_val(&env));
+            bump(&RecordKey::Refunded(contributor.clone()).into_val(&env));
+            bump(&DataKey::TierSnapshot(contributor.clone()).into_val(&env));
+            bump(&DataKey::Dispute(contributor).into_val(&env));
+        }
+        emit_checkpoint(&env);
+        end.saturating_sub(offset)
+    }
+
+    /// Mark a campaign Successful once its payout is due, without waiting
+    /// for the creator to withdraw. Anyone may call this.
+    ///
+    /// Applies the same checks as `withdraw` (deadline, goal, dispute
+    /// window, disputes, approval vote) except for the creator's signature.
+    /// Publishes a `checkpoint` event with the settled totals.
+    ///
+    /// # Errors
+    /// * `CampaignNotActive` if the campaign is no longer Active.
+    /// * `GoalNotReached` as soon as the goal is unreachable (see
+    ///   `goal_reachable`), even before the deadline.
+    /// * Any payout check `withdraw` would fail.
+    pub fn finalize(env: Env) -> Result<(), ContractError> {
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            return Err(ContractError::CampaignNotActive);
+        }
+
+        if !goal_reachable(&env) {
+            return Err(ContractError::GoalNotReached);
+        }
+        let total = check_payout_due(&env, &load_options(&env))?;
+        raise_new_alerts(&env);
+        close_campaign(&env, Status::Successful);
+
+        events::emit(&env, "finalized", total);
+        emit_checkpoint(&env);
+
+        Ok(())
+    }
+
+    /// Send tokens set aside by `withdraw` reconciliation to the creator.
+    ///
+    /// # Errors
+    /// * `NoSurplus` if there is nothing to claim.
+    ///
+    /// # Panics
+    /// * If the campaign has not been settled by `withdraw`.
+    pub fn claim_surplus(env: Env) -> Result<i128, ContractError> {
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Successful {
+            panic!("campaign is not settled");
+        }
+
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        let surplus: i128 = env.storage().instance().get(&DataKey::Surplus).unwrap_or(0);
+        if surplus <= 0 {
+            return Err(ContractError::NoSurplus);
+        }
+
+        env.storage().instance().set(&DataKey::Surplus, &0i128);
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+        token_client.transfer(&env.current_contract_address(), &creator, &surplus);
+        add_paid_to_creator(&env, surplus);
+
+        events::emit(
+            &env,
+            "surplus_claimed",
+            SurplusClaimedEvent {
+                creator,
+                amount: surplus,
+            },
+        );
+
+        Ok(surplus)
+    }
+
+    /// Report how part of the withdrawn funds was spent — creator-only,
+    /// once the campaign is Successful.
+    ///
+    /// `receipt_hash` commits to an off-chain receipt or invoice.
+    ///
+    /// # Errors
+    /// * `InvalidInput` if `amount` is not positive.
+    /// * `MetadataTooLarge` if `description` is longer than 2000 bytes.
+    /// * `ExpenseExceedsWithdrawn` if reported expenses would exceed what
+    ///   has been paid out to the creator.
+    ///
+    /// # Panics
+    /// * If the campaign is not Successful.
+    pub fn report_expense(
+        env: Env,
+        amount: i128,
+        description: String,
+        receipt_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), ContractError> {
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Successful {
+            panic!("campaign is not settled");
+        }
+
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidInput);
+        }
+        if description.len() > MAX_DESCRIPTION_LEN {
+            return Err(ContractError::MetadataTooLarge);
+        }
+
+        let reported: i128 = env
+            .storage()
+            .instance()
+            .get(&RecordKey::ExpensesReported)
+            .unwrap_or(0);
+        let new_reported = reported
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        if new_reported > Self::paid_to_creator(env.clone()) {
+            return Err(ContractError::ExpenseExceedsWithdrawn);
+        }
+
+        let mut expenses: Vec<Expense> = env
+            .storage()
+            .persistent()
+            .get(&RecordKey::Expenses)
+            .unwrap_or_else(|| Vec::new(&env));
+        expenses.push_back(Expense {
+            amount,
+            description: description.clone(),
+            receipt_hash: receipt_hash.clone(),
+            timestamp: env.ledger().timestamp(),
+        });
+        env.storage()
+            .persistent()
+            .set(&RecordKey::Expenses, &expenses);
+        extend_audit_record(&env, &RecordKey::Expenses);
+        env.storage()
+            .instance()
+            .set(&RecordKey::ExpensesReported, &new_reported);
+
+        events::emit(
+            &env,
+            "expense_reported",
+            ExpenseReportedEvent {
+                amount,
+                description,
+                receipt_hash,
+                total_reported: new_reported,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Release the newly vested portion of the raised funds to the creator.
+    ///
+    /// Only for campaigns configured with `vest_duration_secs`. The first
+    /// call is subject to the same checks as `withdraw` and marks the
+    /// campaign `Successful`; subsequent calls pay out whatever vested since
+    /// the last one. The platform fee is deducted proportionally from each
+    /// release.
+    ///
+    /// # Errors
+    /// * `NothingVested` before the cliff or when everything vested so far
+    ///   has already been withdrawn.
+    /// * `CreatorInactive` while the creator is flagged inactive.
+    /// * Any error returned by `withdraw`'s checks on the first release.
+    ///
+    /// # Panics
+    /// * If the campaign has no vesting schedule.
+    /// * If the campaign was refunded or cancelled.
+    pub fn withdraw_vested(env: Env) -> Result<(), ContractError> {
+        let paused: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false);
+        if paused {
+            return Err(ContractError::ContractPaused);
+        }
+
+        let options = load_options(&env);
+        if options.vest_duration_secs == 0 {
+            panic!("campaign has no vesting schedule");
+        }
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        let (creator, total) = match status {
+            Status::Active if auto_refund_due(&env) => return Err(ContractError::CampaignExpired),
+            Status::Active => authorize_withdrawal(&env, &options)?,
+            Status::Successful => {
+                if creator_flagged_inactive(&env) {
+                    return Err(ContractError::CreatorInactive);
+                }
+                let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+                creator.require_auth();
+                if let Some(ref cosigner) = options.withdraw_cosigner {
+                    cosigner.require_auth();
+                }
+                let total: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap();
+                (creator, total)
+            }
+            _ => panic!("campaign is not active"),
+        };
+
+        let withdrawn: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::VestedWithdrawn)
+            .unwrap_or(0);
+        let amount = vested_total(&env, &options, total)
+            .checked_sub(withdrawn)
+            .ok_or(ContractError::Overflow)?;
+        if amount <= 0 {
+            return Err(ContractError::NothingVested);
+        }
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+
+        let fee = if let Some(config) = withdraw_fee_config(&env) {
+            let fee = amount
+                .checked_mul(config.fee_bps as i128)
+                .ok_or(ContractError::Overflow)?
+                / 10_000;
+            token_client.transfer(&env.current_contract_address(), &config.address, &fee);
+            events::emit(
+                &env,
+                "fee_transferred",
+                FeeTransferredEvent {
+                    platform: config.address.clone(),
+                    fee,
+                },
+            );
+            fee
+        } else {
+            0
+        };
+        let creator_payout = amount.checked_sub(fee).ok_or(ContractError::Overflow)?;
+
+        token_client.transfer(&env.current_contract_address(), &creator, &creator_payout);
+        add_paid_to_creator(&env, creator_payout);
+
+        let mut receipts = Self::vested_receipts(env.clone());
+        receipts.push_back(WithdrawalReceipt {
+            timestamp: env.ledger().timestamp(),
+            gross: amount,
+            platform_fee: fee,
+            referral_rewards: 0,
+            escrow_retained: 0,
+            net_to_creator: creator_payout,
+            payout_address: creator.clone(),
+        });
+        env.storage()
+            .persistent()
+            .set(&RecordKey::VestedReceipts, &receipts);
+        extend_audit_record(&env, &RecordKey::VestedReceipts);
+
+        let withdrawn = withdrawn
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::VestedWithdrawn, &withdrawn);
+        close_campaign(&env, Status::Successful);
+
+        events::emit(
+            &env,
+            "vested_withdrawn",
+            WithdrawnEvent {
+                creator,
+                amount,
+                total_raised: get_moved(&env, &DataKey::TotalRaised).unwrap_or(0),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Refund all contributors — callable by anyone after the deadline
+    /// if the goal or the `min_contributors` quorum was **not** met, or if
+    /// the withdrawal approval vote failed. Refunds are paid in full;
+    /// keepers who want the configured bounty use `refund_batch`.
+    pub fn refund(env: Env) -> Result<(), ContractError> {
+        let paused: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false);
+        if paused {
+            return Err(ContractError::ContractPaused);
+        }
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        if is_before_deadline(&env) {
+            return Err(ContractError::CampaignStillActive);
+        }
+
+        let total: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap();
+        if goal_met(&env, total)
+            && quorum_met(&env)
+            && !approval_vote_failed(&env, &load_options(&env))
+        {
+            return Err(ContractError::GoalReached);
+        }
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+
+        let contributors: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contributors)
+            .unwrap();
+
+        for contributor in contributors.iter() {
+            refund_contributor(&env, &token_client, &contributor, 0)?;
+        }
+
+        close_campaign(&env, Status::Refunded);
+        set_total_raised(&env, 0);
+
+        Ok(())
+    }
+
+    /// Refund up to `limit` contributors, resuming where the previous batch
+    /// stopped — callable by anyone once refunds are due.
+    ///
+    /// Lets refunds of large campaigns be split across transactions, in the
+    /// campaign's `RefundOrder`. When
+    /// `keeper_bounty_bps` is configured and `keeper` is not the creator,
+    /// admin or platform, the keeper receives that share of the amount
+    /// refunded in this batch, deducted pro-rata from each refund.
+    ///
+    /// Returns the number of contributors processed in this batch.
+    ///
+    /// # Errors
+    /// * `ContractPaused` while the campaign is paused.
+    /// * `InvalidLimit` if `limit` is zero.
+    /// * `CampaignStillActive` / `GoalReached` if refunds are not due.
+    pub fn refund_batch(env: Env, keeper: Address, limit: u32) -> Result<u32, ContractError> {
+        let paused: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false);
+        if paused {
+            return Err(ContractError::ContractPaused);
+        }
+        if limit == 0 {
+            return Err(ContractError::InvalidLimit);
+        }
+
+        enter_refund_mode(&env)?;
+        if refund_window_closed(&env) {
+            return Err(ContractError::RefundWindowClosed);
+        }
+
+        keeper.require_auth();
+
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        let platform: Option<PlatformConfig> =
+            env.storage().instance().get(&DataKey::PlatformConfig);
+        let affiliated = keeper == creator
+            || admin.is_some_and(|a| a == keeper)
+            || platform.is_some_and(|p| p.address == keeper);
+        let bounty_bps = if affiliated {
+            0
+        } else {
+            load_options(&env).keeper_bounty_bps
+        };
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+
+        let contributors: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contributors)
+            .unwrap();
+        let start: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::RefundCursor)
+            .unwrap_or(0);
+        let end = start.saturating_add(limit).min(contributors.len());
+
+        let mut refunded: i128 = 0;
+        let mut bounty: i128 = 0;
+        for position in start..end {
+            let contributor = contributors
+                .get(refund_index(&env, position, contributors.len()))
+                .unwrap();
+            let (amount, fee) = refund_contributor(&env, &token_client, &contributor, bounty_bps)?;
+            refunded = refunded
+                .checked_add(amount)
+                .ok_or(ContractError::Overflow)?;
+            bounty = bounty.checked_add(fee).ok_or(ContractError::Overflow)?;
+        }
+
+        if bounty > 0 {
+            token_client.transfer(&env.current_contract_address(), &keeper, &bounty);
+            events::emit(&env, "keeper_bounty", KeeperBountyEvent { keeper, bounty });
+        }
+
+        let total: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap();
+        let remaining = total.checked_sub(refunded).ok_or(ContractError::Overflow)?;
+        set_total_raised(&env, remaining);
+        env.storage().instance().set(&DataKey::RefundCursor, &end);
+
+        events::emit(
+            &env,
+            "refund_batch",
+            RefundBatchEvent {
+                count: end - start,
+                refunded,
+                total_raised: remaining,
+            },
+        );
+
+        Ok(end - start)
+    }
+
+    /// Cancel the campaign and refund all contributors — callable only by
+    /// the creator while the campaign is still Active.
+    pub fn cancel(env: Env) {
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+
+        let contributors: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contributors)
+            .unwrap();
+
+        for contributor in contributors.iter() {
+            refund_contributor(&env, &token_client, &contributor, 0)
+                .expect("refund calculation overflow");
+        }
+
+        // Closing first reports the amount refunded to the factory.
+        close_campaign(&env, Status::Cancelled);
+        set_total_raised(&env, 0);
+    }
+
+    /// Tear down a misconfigured campaign — creator-only, within
+    /// `TEARDOWN_WINDOW_SECS` of initialization and before anything was
+    /// contributed or pledged.
+    ///
+    /// Wipes the campaign's metadata, tiers, roadmap, stretch goals and
+    /// updates, then cancels and archives it. Only the configuration
+    /// behind `campaign_info` is kept. When the campaign has a factory, the
+    /// factory is told to cancel and archive its registry entry; a failing
+    /// factory does not block the teardown.
+    ///
+    /// # Errors
+    /// * `CampaignNotActive` if the campaign is no longer Active.
+    /// * `CampaignHasFunds` if any contribution or pledge exists.
+    /// * `TeardownWindowClosed` once the window has passed, or for
+    ///   campaigns initialized before initialization times were recorded.
+    pub fn teardown(env: Env) -> Result<(), ContractError> {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            return Err(ContractError::CampaignNotActive);
+        }
+        let total: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap();
+        let pledged: i128 = get_moved(&env, &DataKey::TotalPledged).unwrap_or(0);
+        if total != 0 || pledged != 0 {
+            return Err(ContractError::CampaignHasFunds);
+        }
+        let initialized_at: Option<u64> = env.storage().instance().get(&RecordKey::InitializedAt);
+        match initialized_at {
+            Some(at) if env.ledger().timestamp() <= at.saturating_add(TEARDOWN_WINDOW_SECS) => {}
+            _ => return Err(ContractError::TeardownWindowClosed),
+        }
+
+        for key in [
+            DataKey::Title,
+            DataKey::Description,
+            DataKey::SocialLinks,
+            DataKey::Category,
+            DataKey::Tags,
+            DataKey::Roadmap,
+            DataKey::RewardTiers,
+            DataKey::StretchGoals,
+        ] {
+            env.storage().instance().remove(&key);
+            env.storage().persistent().remove(&key);
+        }
+        env.storage().persistent().remove(&RecordKey::UpdatePosts);
+        env.storage().instance().remove(&RecordKey::PinnedUpdate);
+        env.storage().instance().remove(&ExtraKey::Media);
+
+        close_campaign(&env, Status::Cancelled);
+        env.storage().instance().set(&RecordKey::Archived, &true);
+        notify_factory_archived(&env);
+
+        events::emit(&env, "torn_down", creator);
+        Ok(())
+    }
+
+    /// Claw back up to `amount` of `contributor`'s contribution after a
+    /// fiat chargeback — only callable by the platform, while Active.
+    ///
+    /// The reversed tokens go to the platform address. The amount is capped
+    /// at the primary-token part of the contributor's recorded contribution,
+    /// and `reason` is a platform-defined code carried by the
+    /// `contribution_reversed` event. Returns the amount reversed.
+    ///
+    /// # Errors
+    /// * `NotAuthorized` if the campaign has no platform config.
+    /// * `CampaignNotActive` if the campaign is no longer Active.
+    /// * `InvalidInput` if `amount` is not positive.
+    /// * `NothingToClaim` if the contributor has nothing to reverse.
+    pub fn reverse_contribution(
+        env: Env,
+        contributor: Address,
+        amount: i128,
+        reason: u32,
+    ) -> Result<i128, ContractError> {
+        let platform: PlatformConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::PlatformConfig)
+            .ok_or(ContractError::NotAuthorized)?;
+        platform.address.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            return Err(ContractError::CampaignNotActive);
+        }
+        if amount <= 0 {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let contribution_key = DataKey::Contribution(contributor.clone());
+        let recorded: i128 = env
+            .storage()
+            .persistent()
+            .get(&contribution_key)
+            .unwrap_or(0);
+        let secondary: Map<u32, TokenTally> = env
+            .storage()
+            .persistent()
+            .get(&RecordKey::TokenContribution(contributor.clone()))
+            .unwrap_or_else(|| Map::new(&env));
+        let secondary_credited: i128 = secondary.values().iter().map(|t| t.credited).sum();
+        let amount = amount.min(recorded - secondary_credited);
+        if amount <= 0 {
+            return Err(ContractError::NothingToClaim);
+        }
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        token::Client::new(&env, &token_address).transfer(
+            &env.current_contract_address(),
+            &platform.address,
+            &amount,
+        );
+
+        let remaining = recorded - amount;
+        env.storage()
+            .persistent()
+            .set(&contribution_key, &remaining);
+        extend_active_record(&env, &contribution_key);
+        if remaining == 0 {
+            adjust_active_contributors(&env, -1);
+        }
+
+        // Anonymous backers are not revealed through a tier change.
+        let anon_key = DataKey::AnonymousAmount(contributor.clone());
+        note_tier_change(
+            &env,
+            &contributor,
+            env.storage().persistent().has(&anon_key),
+        );
+        if let Some(anon_amount) = env.storage().persistent().get::<_, i128>(&anon_key) {
+            let removed = anon_amount.min(amount);
+            env.storage()
+                .persistent()
+                .set(&anon_key, &(anon_amount - removed));
+            let anon_total: i128 = env
+                .storage()
+                .instance()
+                .get(&DataKey::AnonymousTotal)
+                .unwrap_or(0);
+            env.storage()
+                .instance()
+                .set(&DataKey::AnonymousTotal, &(anon_total - removed));
+        }
+
+        let total: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap();
+        set_total_raised(&env, total - amount);
+        unbook_daily_total(&env, &contributor, amount);
+
+        events::emit(
+            &env,
+            "contribution_reversed",
+            ContributionReversedEvent {
+                contributor,
+                amount,
+                reason,
+            },
+        );
+
+        Ok(amount)
+    }
+
+    /// Waive the platform fee for this campaign — only callable by the
+    /// platform. Irreversible.
+    ///
+    /// Sets `fee_bps` to `0` for every later contribution and withdrawal,
+    /// and keeps the original rate for `waived_fee_bps`. Waiving again is a
+    /// no-op.
+    ///
+    /// # Errors
+    /// * `NotAuthorized` if the campaign has no platform config.
+    pub fn waive_platform_fee(env: Env) -> Result<(), ContractError> {
+        let mut config: PlatformConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::PlatformConfig)
+            .ok_or(ContractError::NotAuthorized)?;
+        config.address.require_auth();
+
+        if env.storage().instance().has(&ExtraKey::WaivedFeeBps) {
+            return Ok(());
+        }
+        let original_fee_bps = config.fee_bps;
+        env.storage()
+            .instance()
+            .set(&ExtraKey::WaivedFeeBps, &original_fee_bps);
+        config.fee_bps = 0;
+        env.storage()
+            .instance()
+            .set(&DataKey::PlatformConfig, &config);
+
+        events::emit(
+            &env,
+            "fee_waived",
+            FeeWaivedEvent {
+                platform: config.address,
+                original_fee_bps,
+            },
+        );
+        Ok(())
+    }
+
+    /// The platform fee rate in force before `waive_platform_fee`, or
+    /// `None` if the fee was never waived.
+    pub fn waived_fee_bps(env: Env) -> Option<u32> {
+        env.storage().instance().get(&ExtraKey::WaivedFeeBps)
+    }
+
+    /// Re-emit a finished campaign's history as events so it survives the
+    /// eviction of its ledger entries. Anyone may call this.
+    ///
+    /// The first page publishes `archive_config` (the `campaign_info`) and
+    /// `archive_totals`; every page publishes one `archive_entry` per
+    /// contributor from index `offset` of the contributor list, up to
+    /// `limit`. Contributors before the stored cursor were already exported
+    /// and are skipped, so repeated calls cover everyone exactly once.
+    ///
+    /// Returns the updated cursor.
+    ///
+    /// # Errors
+    /// * `CampaignStillActive` while the campaign is Active.
+    /// * `ArchiveCursorGap` if `offset` is past the cursor.
+    pub fn emit_archive_snapshot(env: Env, offset: u32, limit: u32) -> Result<u32, ContractError> {
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Active {
+            return Err(ContractError::CampaignStillActive);
+        }
+        let cursor: u32 = env
+            .storage()
+            .instance()
+            .get(&RecordKey::ArchiveCursor)
+            .unwrap_or(0);
+        if offset > cursor {
+            return Err(ContractError::ArchiveCursorGap);
+        }
+
+        let contributors: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contributors)
+            .unwrap_or_else(|| Vec::new(&env));
+        if cursor == 0 && limit > 0 {
+            events::emit(&env, "archive_config", Self::campaign_info(env.clone()));
+            events::emit(
+                &env,
+                "archive_totals",
+                ArchiveTotalsEvent {
+                    total_raised: get_moved(&env, &DataKey::TotalRaised).unwrap_or(0),
+                    paid_to_creator: env
+                        .storage()
+                        .instance()
+                        .get(&RecordKey::PaidToCreator)
+                        .unwrap_or(0),
+                    contributors: contributors.len(),
+                },
+            );
+        }
+
+        let end = offset.saturating_add(limit).min(contributors.len());
+        for i in cursor.min(end)..end {
+            let contributor = contributors.get(i).unwrap();
+            events::emit(
+                &env,
+                "archive_entry",
+                ArchiveEntryEvent {
+                    amount: Self::contribution(env.clone(), contributor.clone()),
+                    refunded: Self::refunded_amount(env.clone(), contributor.clone()),
+                    contributor,
+                },
+            );
+        }
+        let cursor = cursor.max(end);
+        env.storage()
+            .instance()
+            .set(&RecordKey::ArchiveCursor, &cursor);
+        Ok(cursor)
+    }
+
+    /// Archive a finished campaign so directories can hide it — callable
+    /// by the creator or, when the campaign has a factory, the factory
+    /// admin. History is kept; only the flag changes.
+    ///
+    /// Notifies the factory, if any, so its listing filter stays in sync;
+    /// a failing factory does not block archiving.
+    ///
+    /// # Errors
+    /// * `CampaignStillActive` while the campaign is Active.
+    ///
+    /// # Panics
+    /// * If `caller` is neither the creator nor the factory admin.
+    pub fn archive(env: Env, caller: Address) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Active {
+            return Err(ContractError::CampaignStillActive);
+        }
+
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        let factory = load_options(&env).factory;
+        let factory_admin = factory.as_ref().and_then(|factory| {
+            env.try_invoke_contract::<Address, soroban_sdk::Error>(
+                factory,
+                &Symbol::new(&env, "admin"),
+                Vec::new(&env),
+            )
+            .ok()?
+            .ok()
+        });
+        if caller != creator && Some(caller) != factory_admin {
+            panic!("not authorized to archive");
+        }
+
+        env.storage().instance().set(&RecordKey::Archived, &true);
+        notify_factory_archived(&env);
+
+        events::emit(&env, "archived", ());
+
+        Ok(())
+    }
+
+    /// Move an expired campaign to `Refunded` so contributors can claim —
+    /// callable by anyone once `auto_refund_after_secs` has passed since
+    /// the deadline without the goal being met.
+    ///
+    /// # Errors
+    /// * `CampaignStillActive` before the expiry window has passed.
+    /// * `GoalReached` if the goal was met.
+    ///
+    /// # Panics
+    /// * If the campaign has no auto-refund window or is not Active.
+    pub fn trigger_auto_refund(env: Env) -> Result<(), ContractError> {
+        let window = load_options(&env).auto_refund_after_secs;
+        if window == 0 {
+            panic!("auto refund is not enabled");
+        }
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+        if env.ledger().timestamp() <= deadline.saturating_add(window) {
+            return Err(ContractError::CampaignStillActive);
+        }
+        if !auto_refund_due(&env) {
+            return Err(ContractError::GoalReached);
+        }
+
+        close_campaign(&env, Status::Refunded);
+        events::emit(&env, "auto_refund_triggered", deadline);
+
+        Ok(())
+    }
+
+    /// Sweep refunds nobody claimed to the configured `sweep_recipient` —
+    /// admin-only, once the refund claim window of a `Refunded` campaign
+    /// has closed. Later claims fail with `RefundWindowClosed`. Any unpaid
+    /// refund top-up goes back to its funders first.
+    ///
+    /// Returns the amount swept.
+    ///
+    /// # Errors
+    /// * `RefundWindowOpen` while refunds can still be claimed.
+    ///
+    /// # Panics
+    /// * If the campaign is not Refunded or has no claim window.
+    pub fn sweep_unclaimed(env: Env) -> Result<i128, ContractError> {
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Refunded {
+            panic!("campaign is not refunded");
+        }
+
+        let options = load_options(&env);
+        let recipient = match options.sweep_recipient {
+            Some(recipient) if options.refund_claim_window_secs > 0 => recipient,
+            _ => panic!("campaign has no refund claim window"),
+        };
+
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        if !refund_window_closed(&env) {
+            return Err(ContractError::RefundWindowOpen);
+        }
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+        return_refund_top_up(&env, &token_client);
+        let amount = campaign_balance(&env, &token_client);
+        if amount > 0 {
+            token_client.transfer(&env.current_contract_address(), &recipient, &amount);
+        }
+        for accepted in options
+            .secondary_tokens
+            .unwrap_or_else(|| Vec::new(&env))
+            .iter()
+        {
+            let secondary_client = token::Client::new(&env, &accepted.token);
+            let balance = secondary_client.balance(&env.current_contract_address());
+            if balance > 0 {
+                secondary_client.transfer(&env.current_contract_address(), &recipient, &balance);
+            }
+        }
+        set_total_raised(&env, 0);
+        env.storage().instance().remove(&RecordKey::TokenTotals);
+
+        events::emit(
+            &env,
+            "unclaimed_swept",
+            UnclaimedSweptEvent { recipient, amount },
+        );
+
+        Ok(amount)
+    }
+
+    /// Claim a refund of the caller's own contribution.
+    ///
+    /// Available after the deadline when the goal was not met or the
+    /// withdrawal approval vote failed, and whenever the campaign is in
+    /// refund mode (e.g. after a dispute is resolved against the creator).
+    /// The first claim moves the campaign to `Refunded`, which blocks
+    /// withdrawal while the remaining contributors claim.
+    ///
+    /// A contributor refunded short also receives their share of any
+    /// `fund_refund_pool` top-up, and may call again after a later top-up.
+    ///
+    /// # Errors
+    /// * `ContractPaused` while the campaign is paused.
+    /// * `CampaignStillActive` before the deadline.
+    /// * `GoalReached` if the goal was met and the payout was not vetoed.
+    /// * `RefundWindowClosed` once `refund_claim_window_secs` has elapsed.
+    /// * `AlreadyRefunded` if the contributor's refund was already paid and
+    ///   no refund top-up is due.
+    ///
+    /// # Panics
+    /// * If the campaign was withdrawn or cancelled.
+    /// * If the contributor has nothing to refund.
+    pub fn claim_refund(env: Env, contributor: Address) -> Result<(), ContractError> {
+        let paused: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false);
+        if paused {
+            return Err(ContractError::ContractPaused);
+        }
+
+        enter_refund_mode(&env)?;
+        if refund_window_closed(&env) {
+            return Err(ContractError::RefundWindowClosed);
+        }
+
+        contributor.require_auth();
+
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_client = token::Client::new(&env, &token_address);
+        let (amount, _) = refund_contributor(&env, &token_client, &contributor, 0)?;
+        let topped_up = pay_refund_top_up(&env, &token_client, &contributor)?;
+        if amount == 0 {
+            if topped_up > 0 {
+                return Ok(());
+            }
+            if env
+                .storage()
+                .persistent()
+                .has(&RecordKey::Refunded(contributor))
+            {
+                return Err(ContractError::AlreadyRefunded);
+            }
+            panic!("nothing to refund");
+        }
+
+        let total: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap();
+        let remaining = total.checked_sub(amount).ok_or(ContractError::Overflow)?;
+        set_total_raised(&env, remaining);
+
+        events::emit(
+            &env,
+            "refund_claimed",
+            RefundedEvent {
+                contributor,
+                amount,
+                total_raised: remaining,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Add `amount` of the campaign token to the refund pool of a
+    /// `Refunded` campaign — `from` must authorize. Open to anyone, though
+    /// usually the creator making backers whole.
+    ///
+    /// The top-up is paid out by `claim_refund` to backers refunded short,
+    /// in proportion to their shortfall. Whatever is unpaid when
+    /// `sweep_unclaimed` runs is returned to the funders.
+    ///
+    /// # Errors
+    /// * `InvalidInput` if `amount` is not positive.
+    /// * `RefundWindowClosed` once the refund claim window has elapsed.
+    ///
+    /// # Panics
+    /// * If the campaign is not Refunded.
+    pub fn fund_refund_pool(env: Env, from: Address, amount: i128) -> Result<(), ContractError> {
+        from.require_auth();
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidInput);
+        }
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Refunded {
+            panic!("campaign is not refunded");
+        }
+        if refund_window_closed(&env) {
+            return Err(ContractError::RefundWindowClosed);
+        }
+
+        // Freeze the pool before the top-up lands so it is not prorated.
+        let mut pool = load_refund_pool(&env);
+        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        token::Client::new(&env, &token_address).transfer(
+            &from,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        pool.funded = pool
+            .funded
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        let added = pool.funders.get(from.clone()).unwrap_or(0);
+        pool.funders.set(from.clone(), added + amount);
+        env.storage().instance().set(&RecordKey::RefundPool, &pool);
+
+        events::emit(
+            &env,
+            "refund_pool_funded",
+            RefundPoolFundedEvent {
+                from,
+                amount,
+                funded: pool.funded,
+            },
+        );
+        Ok(())
+    }
+
+    /// Refund top-up added by `fund_refund_pool` and not yet paid out.
+    pub fn refund_top_up_available(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get::<_, RefundPool>(&RecordKey::RefundPool)
+            .map_or(0, |pool| pool.funded - pool.topped_up)
+    }
+
+    /// Approve the creator's withdrawal, weighted by the caller's
+    /// contribution.
+    ///
+    /// Only available on campaigns configured with `approval_required_bps`,
+    /// after the deadline with the goal met, and while the voting window is
+    /// open.
+    ///
+    /// # Errors
+    /// * `CampaignStillActive` before the deadline.
+    /// * `GoalNotReached` if the goal was not met.
+    ///
+    /// # Panics
+    /// * If the approval vote is not enabled or its window has closed.
+    /// * If the caller has no contribution or has already approved.
+    pub fn approve_withdrawal(env: Env, contributor: Address) -> Result<(), ContractError> {
+        contributor.require_auth();
+
+        let options = load_options(&env);
+        if options.approval_required_bps == 0 {
+            panic!("withdrawal approval is not enabled");
+        }
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        if is_before_deadline(&env) {
+            return Err(ContractError::CampaignStillActive);
+        }
+
+        let total: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap();
+        if !goal_met(&env, total) {
+            return Err(ContractError::GoalNotReached);
+        }
+        if !quorum_met(&env) {
+            return Err(ContractError::QuorumNotMet);
+        }
+
+        if !approval_window_open(&env, &options) {
+            panic!("approval window has closed");
+        }
+
+        let approval_key = DataKey::Approval(contributor.clone());
+        if env.storage().persistent().has(&approval_key) {
+            panic!("already approved");
+        }
+
+        let weight: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contribution(contributor.clone()))
+            .unwrap_or(0);
+        if weight == 0 {
+            panic!("only contributors can approve");
+        }
+
+        env.storage().persistent().set(&approval_key, &weight);
+        extend_active_record(&env, &approval_key);
+
+        let approved: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ApprovedWeight)
+            .unwrap_or(0);
+        let approved = approved
+            .checked_add(weight)
+            .ok_or(ContractError::Overflow)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::ApprovedWeight, &approved);
+
+        events::emit(
+            &env,
+            "withdrawal_approved",
+            ApprovalEvent {
+                contributor,
+                weight,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw a previous approval — allowed until the withdrawal executes
+    /// and while the voting window is open.
+    ///
+    /// # Panics
+    /// * If the campaign is no longer active or the window has closed.
+    /// * If the caller has not approved.
+    pub fn revoke_approval(env: Env, contributor: Address) {
+        contributor.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        if !approval_window_open(&env, &load_options(&env)) {
+            panic!("approval window has closed");
+        }
+
+        let approval_key = DataKey::Approval(contributor.clone());
+        let weight: i128 = env
+            .storage()
+            .persistent()
+            .get(&approval_key)
+            .expect("no approval to revoke");
+        env.storage().persistent().remove(&approval_key);
+
+        let approved: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ApprovedWeight)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::ApprovedWeight, &(approved - weight));
+
+        events::emit(
+            &env,
+            "approval_revoked",
+            ApprovalEvent {
+                contributor,
+                weight,
+            },
+        );
+    }
+
+    /// Flag the campaign during the post-deadline dispute window, weighted by
+    /// the caller's contribution.
+    ///
+    /// Once disputed weight exceeds `dispute_threshold_bps` of `TotalRaised`,
+    /// withdrawal is frozen until the admin calls `resolve_dispute`.
+    ///
+    /// # Arguments
+    /// * `contributor` – The disputing contributor (must authorize).
+    /// * `reason_hash` – Hash of an off-chain description of the issue.
+    ///
+    /// # Panics
+    /// * If the campaign is not active or outside the dispute window.
+    /// * If the dispute was already resolved in the creator's favour.
+    /// * If the caller has no contribution or has already disputed.
+    pub fn raise_dispute(
+        env: Env,
+        contributor: Address,
+        reason_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), ContractError> {
+        contributor.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        let options = load_options(&env);
+        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+        let now = env.ledger().timestamp();
+        if now <= deadline || now > deadline.saturating_add(options.withdraw_delay_secs) {
+            panic!("not within the dispute window");
+        }
+
+        let resolved: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::DisputeResolved)
+            .unwrap_or(false);
+        if resolved {
+            panic!("dispute already resolved");
+        }
+
+        let dispute_key = DataKey::Dispute(contributor.clone());
+        if env.storage().persistent().has(&dispute_key) {
+            panic!("already disputed");
+        }
+
+        let weight: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contribution(contributor.clone()))
+            .unwrap_or(0);
+        if weight == 0 {
+            panic!("only contributors can dispute");
+        }
+
+        env.storage().persistent().set(&dispute_key, &weight);
+        extend_audit_record(&env, &dispute_key);
+
+        let disputed: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::DisputedWeight)
+            .unwrap_or(0);
+        let disputed = disputed
+            .checked_add(weight)
+            .ok_or(ContractError::Overflow)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::DisputedWeight, &disputed);
+
+        if options.dispute_threshold_bps > 0 {
+            let total: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap();
+            let lhs = disputed
+                .checked_mul(10_000)
+                .ok_or(ContractError::Overflow)?;
+            let rhs = total
+                .checked_mul(options.dispute_threshold_bps as i128)
+                .ok_or(ContractError::Overflow)?;
+            if lhs > rhs {
+                env.storage().instance().set(&DataKey::DisputeFrozen, &true);
+            }
+        }
+
+        events::emit(
+            &env,
+            "dispute_raised",
+            DisputeRaisedEvent {
+                contributor,
+                reason_hash,
+                weight,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Resolve a dispute that froze withdrawal — admin-only.
+    ///
+    /// `allow = true` lifts the freeze and closes further disputes;
+    /// `allow = false` moves the campaign to `Refunded` so contributors can
+    /// `claim_refund`.
+    ///
+    /// # Panics
+    /// * If withdrawal is not frozen by a dispute.
+    pub fn resolve_dispute(env: Env, allow: bool) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        let frozen: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::DisputeFrozen)
+            .unwrap_or(false);
+        if !frozen {
+            panic!("no dispute to resolve");
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::DisputeFrozen, &false);
+        if allow {
+            env.storage()
+                .instance()
+                .set(&DataKey::DisputeResolved, &true);
+        } else {
+            close_campaign(&env, Status::Refunded);
+        }
+
+        events::emit(&env, "dispute_resolved", allow);
+    }
+
+    /// Record that the creator is still around — creator only, on a
+    /// Successful campaign with `heartbeat_interval_secs` set.
+    ///
+    /// Clears any inactivity flag, which lifts the freeze on
+    /// `withdraw_vested`.
+    ///
+    /// # Panics
+    /// * If the campaign has no heartbeat interval or is not Successful.
+    pub fn heartbeat(env: Env) {
+        if load_options(&env).heartbeat_interval_secs == 0 {
+            panic!("campaign has no heartbeat interval");
+        }
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Successful {
+            panic!("campaign is not successful");
+        }
+
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        let now = env.ledger().timestamp();
+        env.storage()
+            .instance()
+            .set(&RecordKey::LastHeartbeat, &now);
+        env.storage().instance().remove(&RecordKey::CreatorInactive);
+
+        events::emit(&env, "heartbeat", now);
+    }
+
+    /// Flag the creator as inactive once a heartbeat is overdue by more
+    /// than the grace period — callable by any contributor.
+    ///
+    /// Freezes `withdraw_vested` until the creator's next `heartbeat`.
+    /// Flagging an already flagged creator does nothing.
+    ///
+    /// # Errors
+    /// * `HeartbeatNotOverdue` while the grace period is running.
+    ///
+    /// # Panics
+    /// * If the caller has no contribution.
+    pub fn flag_inactive(env: Env, contributor: Address) -> Result<(), ContractError> {
+        contributor.require_auth();
+
+        let contribution: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contribution(contributor.clone()))
+            .unwrap_or(0);
+        if contribution <= 0 {
+            panic!("no contribution to flag with");
+        }
+        if !heartbeat_overdue(&env) {
+            return Err(ContractError::HeartbeatNotOverdue);
+        }
+        if creator_flagged_inactive(&env) {
+            return Ok(());
+        }
+
+        env.storage()
+            .instance()
+            .set(&RecordKey::CreatorInactive, &true);
+        let last_heartbeat: u64 = env
+            .storage()
+            .instance()
+            .get(&RecordKey::LastHeartbeat)
+            .unwrap();
+        events::emit(
+            &env,
+            "inactive_creator",
+            InactiveCreatorEvent {
+                contributor,
+                last_heartbeat,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Timestamp of the creator's last heartbeat, or of the campaign
+    /// turning Successful if they have not sent one yet. `None` before then.
+    pub fn last_heartbeat(env: Env) -> Option<u64> {
+        env.storage().instance().get(&RecordKey::LastHeartbeat)
+    }
+
+    /// Whether the creator is flagged inactive or their heartbeat is overdue
+    /// past the grace period.
+    pub fn is_inactive(env: Env) -> bool {
+        creator_flagged_inactive(&env) || heartbeat_overdue(&env)
+    }
+
+    /// Move counters and growing lists out of instance storage into their
+    /// own persistent entries — callable by anyone after an upgrade.
+    ///
+    /// Reward tiers stored without delivery fields are rewritten as pending
+    /// tiers without an estimate, and the state digest is recomputed for the
+    /// new version. Values are otherwise unchanged and views read either
+    /// layout, so migrating is idempotent. Returns the number of entries
+    /// moved.
+    pub fn migrate(env: Env) -> u32 {
+        let mut moved = 0;
+        for key in moved_keys(&env).iter() {
+            let value: Option<Val> = env.storage().instance().get(&key);
+            if let Some(value) = value {
+                if !env.storage().persistent().has(&key) {
+                    set_moved(&env, &key, &value);
+                }
+                env.storage().instance().remove(&key);
+                moved += 1;
+            }
+        }
+        if env.storage().persistent().has(&DataKey::RewardTiers) {
+            set_moved(&env, &DataKey::RewardTiers, &load_reward_tiers(&env));
+        }
+        refresh_state_digest(&env);
+        moved
+    }
+
+    /// Upgrade the contract to a new WASM implementation — admin-only.
+    ///
+    /// This function allows the designated admin to upgrade the contract's WASM code
+    /// without changing the contract's address or storage. The new WASM hash must be
+    /// provided and the caller must be authorized as the admin.
+    ///
+    /// When the `factory` option is set, the factory is notified of the new
+    /// hash.
+    ///
+    /// # Arguments
+    /// * `new_wasm_hash` – The SHA-256 hash of the new WASM binary to deploy.
+    ///
+    /// # Panics
+    /// * If the caller is not the admin.
+    pub fn upgrade(env: Env, new_wasm_hash: soroban_sdk::BytesN<32>) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+
+        if let Some(factory) = load_options(&env).factory {
+            let args: Vec<Val> = (env.current_contract_address(), new_wasm_hash).into_val(&env);
+            env.invoke_contract::<()>(&factory, &Symbol::new(&env, "notify_upgraded"), args);
+        }
+    }
+
+    /// Set or clear the withdrawal co-signer — creator-only.
+    ///
+    /// Only allowed before any funds have been raised, so backers always
+    /// contribute under known signing terms. Replacing an existing co-signer
+    /// also requires that co-signer's authorization.
+    ///
+    /// # Panics
+    /// * If any contribution has already been made.
+    pub fn set_cosigner(env: Env, cosigner: Option<Address>) {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        let total: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap();
+        if total != 0 {
+            panic!("cosigner can only be changed before any contribution");
+        }
+
+        let mut options = load_options(&env);
+        if let Some(ref current) = options.withdraw_cosigner {
+            current.require_auth();
+        }
+        options.withdraw_cosigner = cosigner.clone();
+        env.storage().instance().set(&DataKey::Options, &options);
+
+        events::emit(&env, "cosigner_updated", cosigner);
+    }
+
+    /// Set or clear the KYC attestation contract — creator-only.
+    ///
+    /// Only allowed before any funds have been raised, so every backer is
+    /// checked against the same source.
+    ///
+    /// # Panics
+    /// * If any contribution has already been made.
+    pub fn set_attestation_contract(env: Env, attestation: Option<Address>) {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        let total: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap();
+        if total != 0 {
+            panic!("attestation contract can only be changed before any contribution");
+        }
+
+        let mut options = load_options(&env);
+        options.attestation_contract = attestation.clone();
+        env.storage().instance().set(&DataKey::Options, &options);
+
+        events::emit(&env, "attestation_updated", attestation);
+    }
+
+    /// Set or clear the companion badge contract — creator-only.
+    pub fn set_badge_contract(env: Env, badge: Option<Address>) {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        match badge {
+            Some(ref badge) => env
+                .storage()
+                .instance()
+                .set(&RecordKey::BadgeContract, badge),
+            None => env.storage().instance().remove(&RecordKey::BadgeContract),
+        }
+
+        events::emit(&env, "badge_updated", badge);
+    }
+
+    /// Link the campaign to the factory that deployed it, as the `factory`
+    /// option does — the creator and `factory` must both authorize. Lets a
+    /// factory set itself on campaigns it deploys without building their
+    /// options.
+    ///
+    /// # Errors
+    /// * `CampaignNotActive` if the campaign is no longer Active.
+    /// * `CampaignHasFunds` if any contribution or pledge exists.
+    /// * `InvalidInput` if a factory is already set.
+    pub fn set_factory(env: Env, factory: Address) -> Result<(), ContractError> {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+        factory.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            return Err(ContractError::CampaignNotActive);
+        }
+        let total: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap();
+        let pledged: i128 = get_moved(&env, &DataKey::TotalPledged).unwrap_or(0);
+        if total != 0 || pledged != 0 {
+            return Err(ContractError::CampaignHasFunds);
+        }
+        let mut options = load_options(&env);
+        if options.factory.is_some() {
+            return Err(ContractError::InvalidInput);
+        }
+
+        options.factory = Some(factory);
+        env.storage().instance().set(&DataKey::Options, &options);
+        Ok(())
+    }
+
+    /// Replace the contribution token — creator-only, and only while
+    /// nothing has been contributed, pledged or escrowed for vouchers. The
+    /// new token gets the same sanity checks as in `initialize`.
+    ///
+    /// # Errors
+    /// * `CampaignNotActive` if the campaign is no longer Active.
+    /// * `CampaignHasFunds` if any contribution, pledge or unspent voucher
+    ///   exists.
+    /// * `InvalidToken` if `new_token` does not respond to `decimals()`.
+    /// * `SuspiciouslySmallGoal` if the goal is below one whole new token
+    ///   and `allow_dust_goal` is not set.
+    pub fn set_token(env: Env, new_token: Address) -> Result<(), ContractError> {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            return Err(ContractError::CampaignNotActive);
+        }
+        let total: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap();
+        let pledged: i128 = get_moved(&env, &DataKey::TotalPledged).unwrap_or(0);
+        let vouchers: i128 = env
+            .storage()
+            .instance()
+            .get(&ExtraKey::VoucherEscrow)
+            .unwrap_or(0);
+        if total != 0 || pledged != 0 || vouchers != 0 {
+            return Err(ContractError::CampaignHasFunds);
+        }
+
+        let decimals = match token::Client::new(&env, &new_token).try_decimals() {
+            Ok(Ok(decimals)) => decimals,
+            _ => return Err(ContractError::InvalidToken),
+        };
+        let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
+        let one_token = 10i128
+            .checked_pow(decimals)
+            .ok_or(ContractError::InvalidToken)?;
+        if goal < one_token && !load_options(&env).allow_dust_goal {
+            return Err(ContractError::SuspiciouslySmallGoal);
+        }
+
+        let old_token: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        env.storage().instance().set(&DataKey::Token, &new_token);
+        env.storage()
+            .instance()
+            .set(&DataKey::TokenDecimals, &decimals);
+
+        events::emit(
+            &env,
+            "token_changed",
+            TokenChangedEvent {
+                old_token,
+                new_token,
+            },
+        );
+        Ok(())
+    }
+
+    /// Cap the number of active contributors, or lift the cap with 0 —
+    /// creator-only, while Active. Once the cap is reached new
+    /// contributors are rejected; a contributor whose contribution is
+    /// reversed or refunded frees their slot.
+    ///
+    /// # Errors
+    /// * `CampaignNotActive` if the campaign is no longer Active.
+    /// * `InvalidInput` if `max` is below the current contributor count.
+    pub fn set_max_contributors(env: Env, max: u32) -> Result<(), ContractError> {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            return Err(ContractError::CampaignNotActive);
+        }
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ActiveContributorCount)
+            .unwrap_or(0);
+        if max != 0 && max < count {
+            return Err(ContractError::InvalidInput);
+        }
+
+        env.storage()
+            .instance()
+            .set(&RecordKey::MaxContributors, &max);
+        Ok(())
+    }
+
+    /// Choose the order `refund_batch` refunds contributors in —
+    /// creator-only, before anything is contributed or pledged.
+    ///
+    /// # Errors
+    /// * `CampaignNotActive` if the campaign is no longer Active.
+    /// * `CampaignHasFunds` once a contribution or pledge exists.
+    pub fn set_refund_order(env: Env, order: RefundOrder) -> Result<(), ContractError> {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            return Err(ContractError::CampaignNotActive);
+        }
+        let total_raised: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap_or(0);
+        let total_pledged: i128 = get_moved(&env, &DataKey::TotalPledged).unwrap_or(0);
+        if total_raised != 0 || total_pledged != 0 {
+            return Err(ContractError::CampaignHasFunds);
+        }
+
+        env.storage()
+            .instance()
+            .set(&RecordKey::RefundOrder, &order);
+        Ok(())
+    }
+
+    /// Allow or disallow `keeper` to `collect_pledges` — creator-only.
+    pub fn set_pledge_keeper(env: Env, keeper: Address, allowed: bool) {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        let key = ExtraKey::PledgeKeeper(keeper);
+        if allowed {
+            env.storage().instance().set(&key, &true);
+        } else {
+            env.storage().instance().remove(&key);
+        }
+    }
+
+    /// Set how many seconds after the deadline pledgers may still
+    /// `cancel_pledge` before collection opens — creator-only, before the
+    /// deadline.
+    ///
+    /// # Errors
+    /// * `CampaignNotActive` if the campaign is no longer Active.
+    /// * `CampaignEnded` once the deadline has passed.
+    pub fn set_pledge_grace_period(env: Env, grace_secs: u64) -> Result<(), ContractError> {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            return Err(ContractError::CampaignNotActive);
+        }
+        if is_after_deadline(&env) {
+            return Err(ContractError::CampaignEnded);
+        }
+
+        env.storage()
+            .instance()
+            .set(&ExtraKey::PledgeGraceSecs, &grace_secs);
+        Ok(())
+    }
+
+    /// Seconds after the deadline before pledges can be collected.
+    pub fn pledge_grace_period(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&ExtraKey::PledgeGraceSecs)
+            .unwrap_or(0)
+    }
+
+    /// Move the announced start time earlier — creator-only.
+    ///
+    /// Only allowed while nothing has been contributed or pledged and the
+    /// campaign has not opened yet; a start time can never be pushed later.
+    ///
+    /// # Panics
+    /// * If any contribution or pledge exists.
+    /// * If the campaign has already opened.
+    /// * If `new_start` is not earlier than the current start time.
+    pub fn update_start_time(env: Env, new_start: u64) {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        let total: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap();
+        let pledged: i128 = get_moved(&env, &DataKey::TotalPledged).unwrap_or(0);
+        if total != 0 || pledged != 0 {
+            panic!("start time can only be changed before any contribution");
+        }
+
+        let mut options = load_options(&env);
+        if env.ledger().timestamp() >= options.start_time {
+            panic!("campaign has already started");
+        }
+        if new_start >= options.start_time {
+            panic!("start time can only be moved earlier");
+        }
+
+        options.start_time = new_start;
+        env.storage().instance().set(&DataKey::Options, &options);
+
+        events::emit(&env, "start_time_updated", new_start);
+    }
+
+    /// Exempt an address from (or re-subject it to) the contribution
+    /// cooldown — creator-only.
+    ///
+    /// Intended for service accounts that route many user payments through
+    /// a single address, such as custodial wallets and fiat on-ramps.
+    pub fn set_rate_limit_exempt(env: Env, addr: Address, exempt: bool) {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        let key = DataKey::RateLimitExempt(addr.clone());
+        if exempt {
+            env.storage().persistent().set(&key, &true);
+            extend_active_record(&env, &key);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        events::emit(
+            &env,
+            "rate_limit_exempt_updated",
+            RateLimitExemptEvent { addr, exempt },
+        );
+    }
+
+    /// Let addresses holding no contribution skip the contribution
+    /// cooldown — creator-only.
+    pub fn set_skip_cooldown_for_new(env: Env, enabled: bool) {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&ExtraKey::SkipCooldownForNew, &enabled);
+    }
+
+    /// Pause or unpause the contract — creator-only.
+    ///
+    /// When paused, all contributions, withdrawals, and refunds are blocked.
+    /// This is a security mechanism to halt operations in case of detected
+    /// vulnerabilities or external threats.
+    ///
+    /// # Arguments
+    /// * `paused` – True to pause, false to unpause.
+    pub fn set_paused(env: Env, paused: bool) {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        set_state(&env, &DataKey::Paused, &paused);
+        if !paused {
+            env.storage().instance().remove(&RecordKey::InflowWindow);
+        }
+
+        let event_name = if paused { "paused" } else { "unpaused" };
+        events::emit(&env, event_name, ());
+    }
+
+    /// Set or clear the inflow circuit breaker — creator-only.
+    ///
+    /// A contribution that would push the amount contributed within
+    /// `window_secs` past `max_inflow` pauses the campaign and is refused:
+    /// it returns 0 and transfers nothing, and later contributions fail
+    /// with `ContractPaused`. The window opens with the first contribution
+    /// after it lapses.
+    ///
+    /// # Errors
+    /// * `InvalidInput` if `max_inflow` or `window_secs` is not positive.
+    pub fn set_circuit_breaker(
+        env: Env,
+        breaker: Option<CircuitBreaker>,
+    ) -> Result<(), ContractError> {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        match breaker {
+            Some(ref breaker) => {
+                if breaker.max_inflow <= 0 || breaker.window_secs == 0 {
+                    return Err(ContractError::InvalidInput);
+                }
+                env.storage()
+                    .instance()
+                    .set(&RecordKey::CircuitBreaker, breaker);
+            }
+            None => env.storage().instance().remove(&RecordKey::CircuitBreaker),
+        }
+        env.storage().instance().remove(&RecordKey::InflowWindow);
+        Ok(())
+    }
+
+    /// Unpause after the circuit breaker tripped and reset its inflow
+    /// window — callable by the creator or the admin.
+    ///
+    /// # Panics
+    /// * If `caller` is neither the creator nor the admin.
+    pub fn reset_circuit_breaker(env: Env, caller: Address) {
+        caller.require_auth();
+
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        if caller != creator && Some(caller) != admin {
+            panic!("not authorized");
+        }
+
+        set_state(&env, &DataKey::Paused, &false);
+        env.storage().instance().remove(&RecordKey::InflowWindow);
+        events::emit(&env, "unpaused", ());
+    }
+
+    /// Returns the inflow circuit breaker, if one is set.
+    pub fn circuit_breaker(env: Env) -> Option<CircuitBreaker> {
+        env.storage().instance().get(&RecordKey::CircuitBreaker)
+    }
+
+    /// Amount contributed in the circuit breaker's current inflow window
+    /// and when the window ends, or `(0, 0)` without a breaker.
+    pub fn inflow_window(env: Env) -> (i128, u64) {
+        let Some(breaker) = Self::circuit_breaker(env.clone()) else {
+            return (0, 0);
+        };
+        let (start, total) = inflow_window(&env, &breaker);
+        (total, start.saturating_add(breaker.window_secs))
+    }
+
+    /// Update campaign metadata — only callable by the creator while the
+    /// campaign is still Active.
+    ///
+    /// # Arguments
+    /// * `creator`     – The campaign creator's address (for authentication).
+    /// * `title`       – Optional new title (None to keep existing).
+    /// * `description` – Optional new description (None to keep existing).
+    /// * `socials`     – Optional new social links (None to keep existing).
+    ///
+    /// # Errors
+    /// * `MetadataTooLarge` if the title exceeds 100 bytes, the description
+    ///   2000 bytes, any URL 200 bytes, all URLs together 500 bytes, or more
+    ///   than 10 social links are given.
+    pub fn update_metadata(
+        env: Env,
+        creator: Address,
+        title: Option<String>,
+        description: Option<String>,
+        socials: Option<Vec<SocialLink>>,
+    ) -> Result<(), ContractError> {
+        // Check campaign is active.
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        // Require creator authentication and verify caller is the creator.
+        let stored_creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        if creator != stored_creator {
+            panic!("not authorized");
+        }
+        creator.require_auth();
+
+        // Bound every field so instance storage cannot be bloated.
+        if title.as_ref().is_some_and(|t| t.len() > MAX_TITLE_LEN)
+            || description
+                .as_ref()
+                .is_some_and(|d| d.len() > MAX_DESCRIPTION_LEN)
+        {
+            return Err(ContractError::MetadataTooLarge);
+        }
+        if let Some(ref links) = socials {
+            if links.len() > MAX_SOCIAL_LINKS {
+                return Err(ContractError::MetadataTooLarge);
+            }
+            let mut combined: u32 = 0;
+            for link in links.iter() {
+                if link.url.len() > MAX_SOCIAL_URL_LEN {
+                    return Err(ContractError::MetadataTooLarge);
+                }
+                combined += link.url.len();
+            }
+            if combined > MAX_SOCIALS_LEN {
+                return Err(ContractError::MetadataTooLarge);
+            }
+        }
+
+        // Track which fields were updated for the event.
+        let mut updated_fields: Vec<Symbol> = Vec::new(&env);
+
+        // Update title if provided.
+        if let Some(new_title) = title {
+            env.storage().instance().set(&DataKey::Title, &new_title);
+            updated_fields.push_back(Symbol::new(&env, "title"));
+        }
+
+        // Update description if provided.
+        if let Some(new_description) = description {
+            env.storage()
+                .instance()
+                .set(&DataKey::Description, &new_description);
+            updated_fields.push_back(Symbol::new(&env, "description"));
+        }
+
+        // Update social links if provided.
+        if let Some(new_socials) = socials {
+            env.storage()
+                .instance()
+                .set(&DataKey::SocialLinks, &new_socials);
+            updated_fields.push_back(Symbol::new(&env, "socials"));
+        }
+
+        // Emit metadata_updated event with the list of updated field names.
+        events::emit(&env, "metadata_updated", updated_fields);
+
+        Ok(())
+    }
+
+    /// Set the campaign category and tags — creator-only while Active.
+    ///
+    /// Tags are stored lowercased (ASCII only), so `has_tag` matches them
+    /// case-insensitively.
+    ///
+    /// # Errors
+    /// * `MetadataTooLarge` if the category exceeds 50 bytes or more than 10
+    ///   tags are given.
+    /// * `InvalidInput` if a tag is empty, exceeds 32 bytes, contains
+    ///   whitespace or repeats another tag once lowercased.
+    pub fn update_category(
+        env: Env,
+        category: String,
+        tags: Vec<String>,
+    ) -> Result<(), ContractError> {
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        if category.len() > MAX_CATEGORY_LEN || tags.len() > MAX_TAGS {
+            return Err(ContractError::MetadataTooLarge);
+        }
+        let mut normalized = Vec::new(&env);
+        for tag in tags.iter() {
+            let tag = normalize_tag(&env, &tag)?;
+            if normalized.contains(&tag) {
+                return Err(ContractError::InvalidInput);
+            }
+            normalized.push_back(tag);
+        }
+
+        env.storage().instance().set(&DataKey::Category, &category);
+        env.storage().instance().set(&DataKey::Tags, &normalized);
+
+        events::emit(&env, "category_updated", category);
+
+        Ok(())
+    }
+
+    /// Set the campaign's cover image and gallery by content hash —
+    /// creator-only while Active.
+    ///
+    /// # Errors
+    /// * `CampaignNotActive` once the campaign is no longer Active.
+    /// * `MetadataTooLarge` if the gallery has more than 12 images.
+    /// * `InvalidInput` if a hash appears twice across cover and gallery.
+    pub fn set_media(
+        env: Env,
+        cover: BytesN<32>,
+        gallery: Vec<BytesN<32>>,
+    ) -> Result<(), ContractError> {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            return Err(ContractError::CampaignNotActive);
+        }
+        if gallery.len() > MAX_GALLERY_ITEMS {
+            return Err(ContractError::MetadataTooLarge);
+        }
+        let mut seen = Vec::from_array(&env, [cover.clone()]);
+        for hash in gallery.iter() {
+            if seen.contains(&hash) {
+                return Err(ContractError::InvalidInput);
+            }
+            seen.push_back(hash);
+        }
+
+        let media = Media { cover, gallery };
+        env.storage().instance().set(&ExtraKey::Media, &media);
+        events::emit(&env, "media_updated", media);
+        Ok(())
+    }
+
+    /// Update the campaign deadline — only callable by the creator while the
+    /// campaign is still Active.
+    ///
+    /// # Arguments
+    /// * `new_deadline` – The new deadline as a ledger timestamp (must be greater than current deadline).
+    ///
+    /// # Errors
+    /// * `CampaignEnded` if the current deadline has already passed.
+    /// * `ExtensionLimitExceeded` if the cumulative extension would exceed
+    ///   `max_total_extension_secs` (30 days by default).
+    ///
+    /// # Panics
+    /// * If the campaign is not Active.
+    /// * If new_deadline is less than or equal to the current deadline.
+    pub fn update_deadline(env: Env, new_deadline: u64) -> Result<(), ContractError> {
+        // Check campaign is active.
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        // Require creator authentication.
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        // Get the current deadline.
+        let current_deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+
+        // An expired campaign cannot be resurrected to dodge refunds.
+        if is_after_deadline(&env) {
+            return Err(ContractError::CampaignEnded);
+        }
+
+        // Ensure new_deadline is greater than current_deadline (only extensions allowed).
+        if new_deadline <= current_deadline {
+            panic!("new deadline must be after current deadline");
+        }
+
+        // Enforce the cap on cumulative extensions.
+        let options = load_options(&env);
+        let max_extension = if options.max_total_extension_secs == 0 {
+            DEFAULT_MAX_EXTENSION_SECS
+        } else {
+            options.max_total_extension_secs
+        };
+        let used: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ExtensionUsed)
+            .unwrap_or(0);
+        let used = used
+            .checked_add(new_deadline - current_deadline)
+            .ok_or(ContractError::Overflow)?;
+        if used > max_extension {
+            return Err(ContractError::ExtensionLimitExceeded);
+        }
+
+        // Update the deadline.
+        set_state(&env, &DataKey::Deadline, &new_deadline);
+        env.storage().instance().set(&DataKey::ExtensionUsed, &used);
+
+        // Emit deadline_updated event with old and new deadline values and
+        // the cumulative extension used so far.
+        events::emit(
+            &env,
+            "deadline_updated",
+            DeadlineUpdatedEvent {
+                old_deadline: current_deadline,
+                new_deadline,
+                extension_used: used,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Lower the campaign goal — only callable by the creator.
+    ///
+    /// Backers contributed under the original goal, so the goal may only
+    /// go down, and only while the deadline is more than
+    /// `goal_update_buffer_secs` away. The new goal may not drop below
+    /// `GOAL_FLOOR_BPS` of `TotalRaised` or below the highest reward tier.
+    ///
+    /// # Errors
+    /// * `GoalUpdateTooLate` if the deadline is within the buffer.
+    /// * `GoalBelowFloor` if `new_goal` is below either floor.
+    ///
+    /// # Panics
+    /// * If the campaign is not Active.
+    /// * If `new_goal` is not below the current goal or not positive.
+    pub fn update_goal(env: Env, new_goal: i128) -> Result<(), ContractError> {
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        let old_goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
+        if new_goal >= old_goal {
+            panic!("new goal must be below current goal");
+        }
+        if new_goal <= 0 {
+            panic!("goal must be positive");
+        }
+
+        let options = load_options(&env);
+        let buffer = if options.goal_update_buffer_secs == 0 {
+            DEFAULT_GOAL_UPDATE_BUFFER_SECS
+        } else {
+            options.goal_update_buffer_secs
+        };
+        let deadline: u64 = env.storage().instance().get(&DataKey::Deadline).unwrap();
+        if env.ledger().timestamp().saturating_add(buffer) >= deadline {
+            return Err(ContractError::GoalUpdateTooLate);
+        }
+
+        let total: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap_or(0);
+        let raised_floor = total
+            .checked_mul(GOAL_FLOOR_BPS)
+            .ok_or(ContractError::Overflow)?
+            / 10_000;
+        let tiers = load_reward_tiers(&env);
+        let tier_floor = tiers.iter().map(|t| t.min_amount).max().unwrap_or(0);
+        if new_goal < raised_floor || new_goal < tier_floor {
+            return Err(ContractError::GoalBelowFloor);
+        }
+
+        env.storage().instance().set(&DataKey::Goal, &new_goal);
+
+        events::emit(
+            &env,
+            "goal_updated",
+            GoalUpdatedEvent { old_goal, new_goal },
+        );
+
+        Ok(())
+    }
+
+    // ── View helpers ────────────────────────────────────────────────────
+
+    /// Add a roadmap item to the campaign timeline.
+    ///
+    /// Only the creator can add roadmap items. The date must be in the future
+    /// and the description must not be empty.
+    pub fn add_roadmap_item(env: Env, date: u64, description: String) {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        check_roadmap_item(&env, date, &description);
+
+        let mut roadmap: Vec<RoadmapItem> =
+            get_moved(&env, &DataKey::Roadmap).unwrap_or_else(|| Vec::new(&env));
+        if roadmap.len() >= MAX_ROADMAP_ITEMS {
+            panic!("too many roadmap items");
+        }
+
+        let item = RoadmapItem {
+            date,
+            description: description.clone(),
+        };
+
+        roadmap.push_back(item.clone());
+        set_moved(&env, &DataKey::Roadmap, &roadmap);
+
+        events::emit(
+            &env,
+            "roadmap_item_added",
+            RoadmapItemAddedEvent { date, description },
+        );
+    }
+
+    /// Replace the campaign timeline with `items` — creator only.
+    ///
+    /// Each item is validated like `add_roadmap_item`, so a factory can
+    /// configure a campaign in the transaction that deploys it.
+    ///
+    /// # Panics
+    /// * If there are more than `MAX_ROADMAP_ITEMS` items.
+    /// * If any item has a past date or an empty description.
+    pub fn set_roadmap(env: Env, items: Vec<RoadmapItem>) {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        if items.len() > MAX_ROADMAP_ITEMS {
+            panic!("too many roadmap items");
+        }
+        for item in items.iter() {
+            check_roadmap_item(&env, item.date, &item.description);
+        }
+        set_moved(&env, &DataKey::Roadmap, &items);
+
+        for item in items.iter() {
+            events::emit(
+                &env,
+                "roadmap_item_added",
+                RoadmapItemAddedEvent {
+                    date: item.date,
+                    description: item.description,
+                },
+            );
+        }
+    }
+
+    /// Returns the full ordered list of roadmap items.
+    pub fn roadmap(env: Env) -> Vec<RoadmapItem> {
+        get_moved(&env, &DataKey::Roadmap).unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Post an update to the campaign page — only callable by the creator.
+    ///
+    /// Updates may be posted in any status, so a creator can thank backers
+    /// after withdrawal. Returns the index of the new post.
+    ///
+    /// # Errors
+    /// * `InvalidInput` if `content` is empty.
+    /// * `MetadataTooLarge` if `content` exceeds `MAX_DESCRIPTION_LEN` or
+    ///   `MAX_UPDATE_POSTS` updates were already posted.
+    pub fn post_update(env: Env, content: String) -> Result<u32, ContractError> {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        if content.is_empty() {
+            return Err(ContractError::InvalidInput);
+        }
+        let mut posts = load_update_posts(&env);
+        if content.len() > MAX_DESCRIPTION_LEN || posts.len() >= MAX_UPDATE_POSTS {
+            return Err(ContractError::MetadataTooLarge);
+        }
+
+        let index = posts.len();
+        posts.push_back(UpdatePost {
+            content,
+            timestamp: env.ledger().timestamp(),
+        });
+        env.storage()
+            .persistent()
+            .set(&RecordKey::UpdatePosts, &posts);
+        extend_audit_record(&env, &RecordKey::UpdatePosts);
+
+        events::emit(&env, "update_posted", index);
+        Ok(index)
+    }
+
+    /// Returns a page of update posts, oldest first.
+    pub fn update_posts(env: Env, offset: u32, limit: u32) -> Vec<UpdatePost> {
+        let posts = load_update_posts(&env);
+
+        let mut page = Vec::new(&env);
+        let mut i = offset;
+        while i < posts.len() && page.len() < limit {
+            page.push_back(posts.get(i).unwrap());
+            i += 1;
+        }
+        page
+    }
+
+    /// Pin the update post at `index`, replacing any pinned post — only
+    /// callable by the creator. The pin survives status transitions.
+    ///
+    /// # Errors
+    /// * `InvalidInput` if there is no post at `index`.
+    pub fn pin_update(env: Env, index: u32) -> Result<(), ContractError> {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        if index >= load_update_posts(&env).len() {
+            return Err(ContractError::InvalidInput);
+        }
+        env.storage()
+            .instance()
+            .set(&RecordKey::PinnedUpdate, &index);
+
+        events::emit(&env, "update_pinned", index);
+        Ok(())
+    }
+
+    /// Register `tag` as a marketing source contributions may be attributed
+    /// to — only callable by the creator. Re-registering is a no-op.
+    pub fn register_source_tag(env: Env, tag: Symbol) {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        let key = RecordKey::SourceTagRegistered(tag.clone());
+        if env.storage().persistent().has(&key) {
+            return;
+        }
+        env.storage().persistent().set(&key, &true);
+        extend_active_record(&env, &key);
+
+        events::emit(&env, "source_tag_registered", tag);
+    }
+
+    /// Unpin the pinned update post, if any — only callable by the creator.
+    pub fn unpin_update(env: Env) {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        let pinned: Option<u32> = env.storage().instance().get(&RecordKey::PinnedUpdate);
+        if let Some(index) = pinned {
+            env.storage().instance().remove(&RecordKey::PinnedUpdate);
+            events::emit(&env, "update_unpinned", index);
+        }
+    }
+
+    /// Returns the pinned update post, if any.
+    pub fn pinned_update(env: Env) -> Option<UpdatePost> {
+        let index: u32 = env.storage().instance().get(&RecordKey::PinnedUpdate)?;
+        load_update_posts(&env).get(index)
+    }
+
+    /// Add a stretch goal milestone to the campaign.
+    ///
+    /// Only the creator can add stretch goals. The milestone must be greater
+    /// than the primary goal.
+    pub fn add_stretch_goal(env: Env, milestone: i128) {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
+        if milestone <= goal {
+            panic!("stretch goal must be greater than primary goal");
+        }
+
+        let mut stretch_goals: Vec<i128> =
+            get_moved(&env, &DataKey::StretchGoals).unwrap_or_else(|| Vec::new(&env));
+
+        stretch_goals.push_back(milestone);
+        set_moved(&env, &DataKey::StretchGoals, &stretch_goals);
+    }
+
+    /// Remove an abusive memo — creator-only.
+    ///
+    /// Clears the contributor's stored memo and drops all of their entries
+    /// from the recent memos buffer.
+    pub fn remove_memo(env: Env, contributor: Address) {
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Memo(contributor.clone()));
+
+        let recent: Vec<MemoEntry> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RecentMemos)
+            .unwrap_or_else(|| Vec::new(&env));
+        let mut kept = Vec::new(&env);
+        for entry in recent.iter() {
+            if entry.contributor != Some(contributor.clone()) {
+                kept.push_back(entry);
+            }
+        }
+        env.storage().persistent().set(&DataKey::RecentMemos, &kept);
+
+        events::emit(&env, "memo_removed", contributor);
+    }
+
+    /// Scrub `contributor`'s address from the campaign's public views once
+    /// their part is settled — contributor-only, in a terminal status.
+    ///
+    /// Removes the address from the contributor list, tier rosters, memos
+    /// and referral records. Receipts keep their amounts but no longer
+    /// show the address, and totals and contributor counts are unchanged.
+    /// The `contributor_forgotten` event carries only a SHA-256 hash of the
+    /// address.
+    ///
+    /// # Errors
+    /// * `CampaignStillActive` while the campaign is Active.
+    /// * `SettlementPending` if the contributor still has a refund or a
+    ///   failed payout to claim, or the fulfillment escrow is unsettled.
+    pub fn forget_me(env: Env, contributor: Address) -> Result<(), ContractError> {
+        contributor.require_auth();
+
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status == Status::Active {
+            return Err(ContractError::CampaignStillActive);
+        }
+        let contribution: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contribution(contributor.clone()))
+            .unwrap_or(0);
+        let claimable: Map<Address, i128> = env
+            .storage()
+            .persistent()
+            .get(&RecordKey::ClaimablePayouts(contributor.clone()))
+            .unwrap_or_else(|| Map::new(&env));
+        let refund_owed = status != Status::Successful && contribution > 0;
+        if refund_owed || !claimable.is_empty() || load_escrow(&env).is_some() {
+            return Err(ContractError::SettlementPending);
+        }
+
+        let mut contributors: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contributors)
+            .unwrap_or_else(|| Vec::new(&env));
+        if let Some(index) = contributors.first_index_of(&contributor) {
+            contributors.remove(index);
+            env.storage()
+                .persistent()
+                .set(&DataKey::Contributors, &contributors);
+            // Keep the refund and archive cursors on the same contributors.
+            let refund_cursor: u32 = env
+                .storage()
+                .instance()
+                .get(&DataKey::RefundCursor)
+                .unwrap_or(0);
+            let refunded = match refund_order(&env) {
+                RefundOrder::Lifo => index >= contributors.len() + 1 - refund_cursor,
+                RefundOrder::Fifo | RefundOrder::ProRataOnly => index < refund_cursor,
+            };
+            if refunded {
+                env.storage()
+                    .instance()
+                    .set(&DataKey::RefundCursor, &(refund_cursor - 1));
+            }
+            let archive_cursor: u32 = env
+                .storage()
+                .instance()
+                .get(&RecordKey::ArchiveCursor)
+                .unwrap_or(0);
+            if index < archive_cursor {
+                env.storage()
+                    .instance()
+                    .set(&RecordKey::ArchiveCursor, &(archive_cursor - 1));
+            }
+            let forgotten: u32 = env
+                .storage()
+                .instance()
+                .get(&RecordKey::ForgottenCount)
+                .unwrap_or(0);
+            env.storage()
+                .instance()
+                .set(&RecordKey::ForgottenCount, &(forgotten + 1));
+        }
+
+        let snapshot_key = DataKey::TierSnapshot(contributor.clone());
+        if let Some(tier) = env.storage().persistent().get::<_, String>(&snapshot_key) {
+            let roster_key = DataKey::TierRoster(tier);
+            let mut roster: Vec<Address> = env
+                .storage()
+                .persistent()
+                .get(&roster_key)
+                .unwrap_or_else(|| Vec::new(&env));
+            if let Some(index) = roster.first_index_of(&contributor) {
+                roster.remove(index);
+                env.storage().persistent().set(&roster_key, &roster);
+            }
+            env.storage().persistent().remove(&snapshot_key);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Memo(contributor.clone()));
+        let recent: Vec<MemoEntry> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RecentMemos)
+            .unwrap_or_else(|| Vec::new(&env));
+        let mut kept = Vec::new(&env);
+        for entry in recent.iter() {
+            if entry.contributor != Some(contributor.clone()) {
+                kept.push_back(entry);
+            }
+        }
+        env.storage().persistent().set(&DataKey::RecentMemos, &kept);
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ReferralTally(contributor.clone()));
+        env.storage()
+            .persistent()
+            .remove(&RecordKey::ReferralCredits(contributor.clone()));
+        env.storage()
+            .persistent()
+            .remove(&ExtraKey::ContributionDays(contributor.clone()));
+
+        let forgotten_key = RecordKey::Forgotten(contributor.clone());
+        env.storage().persistent().set(&forgotten_key, &true);
+        extend_audit_record(&env, &forgotten_key);
+
+        let hash: BytesN<32> = env.crypto().sha256(&contributor.to_xdr(&env)).into();
+        events::emit(&env, "contributor_forgotten", hash);
+        Ok(())
+    }
+
+    /// Add a reward tier (creator only). Rejects min_amount <= 0 and an
+    /// `estimated_delivery` on or before the deadline; pass 0 for no
+    /// estimate. The tier starts out pending.
+    pub fn add_reward_tier(
+        env: Env,
+        creator: Address,
+        name: String,
+        min_amount: i128,
+        estimated_delivery: u64,
+    ) {
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        let stored_creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        if creator != stored_creator {
+            panic!("not authorized");
+        }
+        creator.require_auth();
+
+        check_reward_tier(&env, min_amount, estimated_delivery);
+
+        let mut tiers = load_reward_tiers(&env);
+        if tiers.len() >= MAX_REWARD_TIERS {
+            panic!("too many reward tiers");
+        }
+
+        tiers.push_back(RewardTier {
+            name: name.clone(),
+            min_amount,
+            estimated_delivery,
+            delivery_status: DELIVERY_PENDING,
+        });
+        set_moved(&env, &DataKey::RewardTiers, &tiers);
+
+        events::emit(
+            &env,
+            "reward_tier_added",
+            RewardTierAddedEvent { name, min_amount },
+        );
+    }
+
+    /// Replace the reward tiers with `tiers` (creator only). Each tier is
+    /// validated like `add_reward_tier`.
+    ///
+    /// # Panics
+    /// * If the campaign is not Active or `creator` is not the creator.
+    /// * If there are more than `MAX_REWARD_TIERS` tiers.
+    /// * If any tier has `min_amount <= 0`, an `estimated_delivery` on or
+    ///   before the deadline, or a delivery status other than pending.
+    pub fn set_reward_tiers(env: Env, creator: Address, tiers: Vec<RewardTier>) {
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            panic!("campaign is not active");
+        }
+
+        let stored_creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        if creator != stored_creator {
+            panic!("not authorized");
+        }
+        creator.require_auth();
+
+        if tiers.len() > MAX_REWARD_TIERS {
+            panic!("too many reward tiers");
+        }
+        for tier in tiers.iter() {
+            check_reward_tier(&env, tier.min_amount, tier.estimated_delivery);
+            if tier.delivery_status != DELIVERY_PENDING {
+                panic!("new tiers must be pending delivery");
+            }
+        }
+        set_moved(&env, &DataKey::RewardTiers, &tiers);
+
+        for tier in tiers.iter() {
+            events::emit(
+                &env,
+                "reward_tier_added",
+                RewardTierAddedEvent {
+                    name: tier.name,
+                    min_amount: tier.min_amount,
+                },
+            );
+        }
+    }
+
+    /// Returns the full ordered list of reward tiers.
+    pub fn reward_tiers(env: Env) -> Vec<RewardTier> {
+        load_reward_tiers(&env)
+    }
+
+    /// Update the delivery status of the tier at `index` — creator-only,
+    /// once the campaign is Successful. Statuses may be set in any order
+    /// so the creator can correct a mistake.
+    ///
+    /// # Panics
+    /// * If the campaign is not Successful.
+    /// * If `index` is out of range or `status` is not a delivery status.
+    pub fn set_tier_delivery_status(env: Env, index: u32, status: u32) {
+        let campaign_status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if campaign_status != Status::Successful {
+            panic!("campaign is not successful");
+        }
+
+        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
+        creator.require_auth();
+
+        if status > DELIVERY_DELIVERED {
+            panic!("invalid delivery status");
+        }
+        let mut tiers = load_reward_tiers(&env);
+        let mut tier = tiers.get(index).expect("reward tier not found");
+        tier.delivery_status = status;
+        tiers.set(index, tier.clone());
+        set_moved(&env, &DataKey::RewardTiers, &tiers);
+
+        events::emit(
+            &env,
+            "tier_delivery_updated",
+            TierDeliveryUpdatedEvent {
+                index,
+                name: tier.name,
+                status,
+            },
+        );
+    }
+
+    /// Returns the highest tier name the user's contribution qualifies for,
+    /// or None if the user has not contributed or no tiers are defined.
+    /// Tiers are evaluated by min_amount descending (highest qualifying tier wins).
+    pub fn get_user_tier(env: Env, user: Address) -> Option<String> {
+        let contribution: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contribution(user))
+            .unwrap_or(0);
+
+        tier_for(&env, contribution)
+    }
+
+    /// Returns the tier the contributor qualified for when the campaign
+    /// succeeded, or None if it has not succeeded or they did not qualify.
+    pub fn final_tier(env: Env, contributor: Address) -> Option<String> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TierSnapshot(contributor))
+    }
+
+    /// Returns a page of the contributors who qualified for `tier_name`
+    /// when the campaign succeeded, in contribution order.
+    pub fn tier_roster(env: Env, tier_name: String, offset: u32, limit: u32) -> Vec<Address> {
+        let roster: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TierRoster(tier_name))
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let mut page = Vec::new(&env);
+        let mut i = offset;
+        while i < roster.len() && page.len() < limit {
+            page.push_back(roster.get(i).unwrap());
+            i += 1;
+        }
+        page
+    }
+
+    /// Returns the next unmet stretch goal milestone.
+    ///
+    /// Returns 0 if there are no stretch goals or all have been met.
+    pub fn current_milestone(env: Env) -> i128 {
+        let total_raised: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap_or(0);
+
+        let stretch_goals: Vec<i128> =
+            get_moved(&env, &DataKey::StretchGoals).unwrap_or_else(|| Vec::new(&env));
+
+        for milestone in stretch_goals.iter() {
+            if total_raised < milestone {
+                return milestone;
+            }
+        }
+
+        0
+    }
+    pub fn total_raised(env: Env) -> i128 {
+        get_moved(&env, &DataKey::TotalRaised).unwrap_or(0)
+    }
+
+    /// sha256 over the campaign's totals, status, deadline, pause flag,
+    /// contributor count and version; it changes whenever any of them does.
+    pub fn state_digest(env: Env) -> BytesN<32> {
+        env.storage()
+            .instance()
+            .get(&RecordKey::StateDigest)
+            .unwrap()
+    }
+
+    /// Returns `(state_digest, total_raised, status)` in one read.
+    pub fn summary(env: Env) -> (BytesN<32>, i128, Status) {
+        (
+            Self::state_digest(env.clone()),
+            get_moved(&env, &DataKey::TotalRaised).unwrap_or(0),
+            env.storage().instance().get(&DataKey::Status).unwrap(),
+        )
+    }
+
+    /// Returns the funding goal.
+    pub fn goal(env: Env) -> i128 {
+        env.storage().instance().get(&DataKey::Goal).unwrap()
+    }
+
+    /// Returns the USD value of `TotalRaised` and the USD goal, or `None`
+    /// when the campaign has no oracle or its price is missing or stale.
+    pub fn goal_progress_usd(env: Env) -> Option<(i128, i128)> {
+        let options = load_options(&env);
+        let total: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap();
+        usd_value(&env, &options, total).map(|value| (value, options.goal_usd))
+    }
+
+    /// Whether the goal can still be met. False once the deadline passed
+    /// with contributions and pledges short of the goal, when the hard cap
+    /// is below the goal, or when the campaign was refunded or cancelled.
+    pub fn goal_reachable(env: Env) -> bool {
+        goal_reachable(&env)
+    }
+
+    /// Amount still missing from the goal after contributions and
+    /// outstanding pledges (`0` once covered).
+    pub fn shortfall(env: Env) -> i128 {
+        let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
+        let total: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap();
+        let pledged: i128 = get_moved(&env, &DataKey::TotalPledged).unwrap_or(0);
+        (goal - total - pledged).max(0)
+    }
+
+    /// Returns the hard cap (maximum total that can be raised).
+    pub fn hard_cap(env: Env) -> i128 {
+        env.storage().instance().get(&DataKey::HardCap).unwrap()
+    }
+
+    /// Returns the campaign deadline.
+    pub fn deadline(env: Env) -> u64 {
+        env.storage().instance().get(&DataKey::Deadline).unwrap()
+    }
+
+    /// Returns the contribution of a specific address.
+    pub fn contribution(env: Env, contributor: Address) -> i128 {
+        let contribution_key = DataKey::Contribution(contributor);
+        env.storage()
+            .persistent()
+            .get(&contribution_key)
+            .unwrap_or(0)
+    }
+
+    /// Returns the total contribution refunded to `contributor` by refunds,
+    /// claims and cancellation, before any keeper bounty.
+    pub fn refunded_amount(env: Env, contributor: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&RecordKey::Refunded(contributor))
+            .unwrap_or(0)
+    }
+
+    /// Whether `contributor` has been paid a refund.
+    pub fn was_refunded(env: Env, contributor: Address) -> bool {
+        Self::refunded_amount(env, contributor) > 0
+    }
+
+    /// Everything the campaign records about `addr` in one call.
+    pub fn backer_profile(env: Env, addr: Address) -> BackerProfile {
+        let contributed = Self::contribution(env.clone(), addr.clone());
+        BackerProfile {
+            contributed,
+            pledged_outstanding: Self::pledge_amount(env.clone(), addr.clone()),
+            refunds_received: Self::refunded_amount(env.clone(), addr.clone()),
+            first_contribution_ts: env
+                .storage()
+                .persistent()
+                .get(&RecordKey::FirstContribution(addr.clone()))
+                .unwrap_or(0),
+            tier: tier_for(&env, contributed),
+            referral_tally: Self::referral_tally(env, addr),
+        }
+    }
+
+    /// Returns the pledge of a specific address.
+    pub fn pledge_amount(env: Env, pledger: Address) -> i128 {
+        // Pledges are voided when the campaign closes.
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            return 0;
+        }
+        let pledge_key = DataKey::Pledge(pledger);
+        env.storage().persistent().get(&pledge_key).unwrap_or(0)
+    }
+
+    /// Pledgers whose pledge `revalidate_pledges` last found underfunded.
+    pub fn flagged_pledges(env: Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&RecordKey::FlaggedPledges)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// The contributor's installment plan, if they have one.
+    pub fn installment_plan(env: Env, contributor: Address) -> Option<InstallmentPlan> {
+        env.storage()
+            .persistent()
+            .get(&RecordKey::InstallmentPlan(contributor))
+    }
+
+    /// Number of pledgers with an outstanding pledge.
+    pub fn pledger_count(env: Env) -> u32 {
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        if status != Status::Active {
+            return 0;
+        }
+        let pledgers: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Pledgers)
+            .unwrap_or_else(|| Vec::new(&env));
+        let mut count = 0;
+        for pledger in pledgers.iter() {
+            let amount: i128 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Pledge(pledger))
+                .unwrap_or(0);
+            if amount > 0 {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Returns the total amount pledged (not yet transferred).
+    pub fn total_pledged(env: Env) -> i128 {
+        get_moved(&env, &DataKey::TotalPledged).unwrap_or(0)
+    }
+
+    /// Returns the minimum contribution amount.
+    pub fn min_contribution(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MinContribution)
+            .unwrap()
+    }
+
+    /// Returns the per-address contribution cap (`0` if disabled) and
+    /// whether outstanding pledges count toward it.
+    pub fn contribution_cap(env: Env) -> (i128, bool) {
+        let options = load_options(&env);
+        (
+            options.max_contribution_per_address,
+            options.pledges_count_toward_cap,
+        )
+    }
+
+    /// Returns the primary campaign category.
+    pub fn category(env: Env) -> soroban_sdk::String {
+        env.storage().instance().get(&DataKey::Category).unwrap()
+    }
+
+    /// Returns the optional descriptive tags, lowercased.
+    pub fn tags(env: Env) -> Vec<soroban_sdk::String> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Tags)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// The campaign's cover image and gallery, if set.
+    pub fn media(env: Env) -> Option<Media> {
+        env.storage().instance().get(&ExtraKey::Media)
+    }
+
+    /// Whether the campaign carries `tag`, ignoring ASCII case. A tag that
+    /// could never be stored matches nothing.
+    pub fn has_tag(env: Env, tag: String) -> bool {
+        match normalize_tag(&env, &tag) {
+            Ok(tag) => Self::tags(env).contains(&tag),
+            Err(_) => false,
+        }
+    }
+
+    /// Conditions needing the creator's attention: `goal_reached`,
+    /// `deadline_near` (goal unmet within the alert window) and
+    /// `hard_cap_near` (within 5% of the hard cap).
+    pub fn creator_alerts(env: Env) -> Vec<Symbol> {
+        alert_names(&env, current_alerts(&env))
+    }
+
+    /// Check the campaign's invariants and return the `HEALTH_*` code of
+    /// each one violated, emitting `health_violation` per code.
+    ///
+    /// Contributor and pledge records are sampled `HEALTH_SAMPLE_SIZE` at a
+    /// time starting at `offset`; a sample must not exceed the recorded
+    /// aggregate, and a sample covering every record must match it.
+    pub fn health_check(env: Env, offset: u32) -> Vec<u32> {
+        let mut codes = Vec::new(&env);
+        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
+        let active = status == Status::Active;
+        let total: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap();
+        let hard_cap: i128 = env.storage().instance().get(&DataKey::HardCap).unwrap();
+
+        if active {
+            let token: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+            let secondary: i128 = load_token_totals(&env)
+                .values()
+                .iter()
+                .map(|tally| tally.credited)
+                .sum();
+            let balance = campaign_balance(&env, &token::Client::new(&env, &token));
+            if balance < total - secondary {
+                codes.push_back(HEALTH_BALANCE_SHORT);
+            }
+        }
+
+        if total > hard_cap {
+            codes.push_back(HEALTH_OVER_HARD_CAP);
+        }
+
+        if active {
+            let contributors: Vec<Address> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Contributors)
+                .unwrap_or_else(|| Vec::new(&env));
+            let count: u32 = env
+                .storage()
+                .instance()
+                .get(&DataKey::ActiveContributorCount)
+                .unwrap_or(0);
+            let end = contributors
+                .len()
+                .min(offset.saturating_add(HEALTH_SAMPLE_SIZE));
+            let mut sampled = 0u32;
+            for i in offset.min(end)..end {
+                let amount: i128 = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::Contribution(contributors.get(i).unwrap()))
+                    .unwrap_or(0);
+                if amount > 0 {
+                    sampled += 1;
+                }
+            }
+            let covers_all = offset == 0 && end == contributors.len();
+            if sampled > count || (covers_all && sampled != count) {
+                codes.push_back(HEALTH_CONTRIBUTOR_COUNT);
+            }
+
+            let pledgers: Vec<Address> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Pledgers)
+                .unwrap_or_else(|| Vec::new(&env));
+            let pledged: i128 = get_moved(&env, &DataKey::TotalPledged).unwrap_or(0);
+            let end = pledgers
+                .len()
+                .min(offset.saturating_add(HEALTH_SAMPLE_SIZE));
+            let mut sampled: i128 = 0;
+            for i in offset.min(end)..end {
+                let amount: i128 = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::Pledge(pledgers.get(i).unwrap()))
+                    .unwrap_or(0);
+                sampled = sampled.saturating_add(amount);
+            }
+            let covers_all = offset == 0 && end == pledgers.len();
+            if sampled > pledged || (covers_all && sampled != pledged) {
+                codes.push_back(HEALTH_PLEDGE_TOTAL);
+            }
+        }
+
+        if status == Status::Successful && is_before_deadline(&env) {
+            codes.push_back(HEALTH_STATUS_DEADLINE);
+        }
+
+        for code in codes.iter() {
+            events::emit(&env, "health_violation", code);
+        }
+        codes
+    }
+
+    /// Returns comprehensive campaign statistics.
+    ///
+    /// Never traps: ratios saturate rather than overflow, even for totals
+    /// near the i128 limit.
+    pub fn get_stats(env: Env) -> CampaignStats {
+        let total_raised: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap_or(0);
+        let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
+        let contributors: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contributors)
+            .unwrap();
+
+        let options = load_options(&env);
+        let progress_of = |amount: i128| match usd_value(&env, &options, amount) {
+            Some(value) => bps_of(value, options.goal_usd),
+            None => bps_of(amount, goal),
+        };
+        let progress_raw_bps = progress_of(total_raised);
+        let progress_bps = progress_raw_bps.clamp(0, 10_000) as u32;
+        let pledged: i128 = get_moved(&env, &DataKey::TotalPledged).unwrap_or(0);
+        let hard_cap: i128 = env.storage().instance().get(&DataKey::HardCap).unwrap();
+
+        let contributor_count = Self::contributor_count(env.clone());
+        let (average_contribution, largest_contribution) = if contributor_count == 0 {
+            (0, 0)
+        } else {
+            let average = total_raised / contributor_count as i128;
+            let mut largest = 0i128;
+            for contributor in contributors.iter() {
+                let amount: i128 = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::Contribution(contributor))
+                    .unwrap_or(0);
+                if amount > largest {
+                    largest = amount;
+                }
+            }
+            (average, largest)
+        };
+
+        CampaignStats {
+            total_raised,
+            goal,
+            progress_bps,
+            progress_raw_bps,
+            overfunded_bps: progress_raw_bps.saturating_sub(10_000).max(0),
+            cap_utilization_bps: bps_of(total_raised, hard_cap),
+            projected_progress_bps: progress_of(total_raised.saturating_add(pledged)),
+            contributor_count,
+            average_contribution,
+            largest_contribution,
+        }
+    }
+
+    /// Returns the campaign title.
+    pub fn title(env: Env) -> String {
+        let empty = String::from_str(&env, "");
+        env.storage()
+            .instance()
+            .get(&DataKey::Title)
+            .unwrap_or(empty)
+    }
+
+    /// Returns the campaign description.
+    pub fn description(env: Env) -> String {
+        let empty = String::from_str(&env, "");
+        env.storage()
+            .instance()
+            .get(&DataKey::Description)
+            .unwrap_or(empty)
+    }
+
+    /// Returns the campaign's social link URLs joined by single spaces.
+    ///
+    /// Kept for clients that predate `social_links`.
+    pub fn socials(env: Env) -> String {
+        let links = Self::social_links(env.clone());
+        let mut buf = [0u8; (MAX_SOCIALS_LEN + MAX_SOCIAL_LINKS) as usize];
+        let mut len = 0usize;
+        for link in links.iter() {
+            if len > 0 {
+                buf[len] = b' ';
+                len += 1;
+            }
+            let url_len = link.url.len() as usize;
+            link.url.copy_into_slice(&mut buf[len..len + url_len]);
+            len += url_len;
+        }
+        String::from_bytes(&env, &buf[..len])
+    }
+
+    /// Returns the campaign's structured social links.
+    pub fn social_links(env: Env) -> Vec<SocialLink> {
+        env.storage()
+            .instance()
+            .get(&DataKey::SocialLinks)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Returns the contract version.
+    ///
+    /// This view function allows external tools to detect which version of the
+    /// contract logic is currently running at this address. The version must be
+    /// manually incremented with every contract upgrade (see Issue #38).
+    pub fn version(_env: Env) -> u32 {
+        CONTRACT_VERSION
+    }
+
+    /// Returns the total contribution demand the hard cap turned away:
+    /// the truncated part of clipped contributions and batch entries
+    /// skipped once the cap was reached.
+    ///
+    /// Calls rejected outright with `HardCapExceeded` are rolled back and
+    /// are not counted.
+    pub fn excess_demand(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&RecordKey::ExcessDemand)
+            .unwrap_or(0)
+    }
+
+    /// Returns the KYC attestation contract, if the campaign is gated.
+    pub fn attestation_contract(env: Env) -> Option<Address> {
+        load_options(&env).attestation_contract
+    }
+
+    /// Returns the companion badge contract, if one is set.
+    pub fn badge_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&RecordKey::BadgeContract)
+    }
+
+    /// Returns the withdrawal co-signer, if one is configured.
+    pub fn cosigner(env: Env) -> Option<Address> {
+        load_options(&env).withdraw_cosigner
+    }
+
+    /// Total amount vested so far under the campaign's vesting schedule
+    /// (`0` for campaigns without vesting).
+    pub fn vested_amount(env: Env) -> i128 {
+        let total: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap();
+        vested_total(&env, &load_options(&env), total)
+    }
+
+    /// Gross amount already released through `withdraw_vested`.
+    pub fn withdrawn_amount(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::VestedWithdrawn)
+            .unwrap_or(0)
+    }
+
+    /// Whether disputes currently freeze withdrawal.
+    pub fn is_disputed(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::DisputeFrozen)
+            .unwrap_or(false)
+    }
+
+    /// Approved contribution weight as a share of `TotalRaised`, in basis
+    /// points (10000 = 100%).
+    pub fn approval_progress_bps(env: Env) -> u32 {
+        let total: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap();
+        if total <= 0 {
+            return 0;
+        }
+        let approved: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ApprovedWeight)
+            .unwrap_or(0);
+        let bps = (approved * 10_000) / total;
+        bps.min(10_000) as u32
+    }
 
-        roadmap.push_back(item.clone());
-        env.storage().instance().set(&DataKey::Roadmap, &roadmap);
+    /// Ledger sequence at which `addr` first contributed or pledged. Only
+    /// tracked while `min_first_seen_ledgers` is set.
+    pub fn first_seen_ledger(env: Env, addr: Address) -> Option<u32> {
+        env.storage().persistent().get(&RecordKey::FirstSeen(addr))
+    }
 
-        env.events()
-            .publish(("campaign", "roadmap_item_added"), (date, description));
+    /// Total contribution amount credited to `referrer`.
+    pub fn referral_tally(env: Env, referrer: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ReferralTally(referrer))
+            .unwrap_or(0)
     }
 
-    /// Returns the full ordered list of roadmap items.
-    pub fn roadmap(env: Env) -> Vec<RoadmapItem> {
+    /// Total contributed under source tag `tag`.
+    pub fn source_tally(env: Env, tag: Symbol) -> i128 {
         env.storage()
-            .instance()
-            .get(&DataKey::Roadmap)
-            .unwrap_or_else(|| Vec::new(&env))
+            .persistent()
+            .get(&RecordKey::SourceTally(tag))
+            .unwrap_or(0)
     }
 
-    /// Add a stretch goal milestone to the campaign.
-    ///
-    /// Only the creator can add stretch goals. The milestone must be greater
-    /// than the primary goal.
-    pub fn add_stretch_goal(env: Env, milestone: i128) {
-        let creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
-        creator.require_auth();
+    /// Source tags that have been credited, in first-seen order.
+    pub fn source_tags(env: Env) -> Vec<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&RecordKey::SourceTags)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
 
-        let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
-        if milestone <= goal {
-            panic!("stretch goal must be greater than primary goal");
-        }
+    /// Timestamp of `addr`'s most recent contribution (`0` if none).
+    pub fn last_contribution_time(env: Env, addr: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::LastContributionTime(addr))
+            .unwrap_or(0)
+    }
 
-        let mut stretch_goals: Vec<i128> = env
-            .storage()
-            .instance()
-            .get(&DataKey::StretchGoals)
-            .unwrap_or_else(|| Vec::new(&env));
+    /// Seconds until `addr` may contribute again (`0` if it may contribute
+    /// now).
+    pub fn cooldown_remaining(env: Env, addr: Address) -> u64 {
+        cooldown_remaining_for(&env, &addr)
+    }
 
-        stretch_goals.push_back(milestone);
+    /// Contributions bounced by the cooldown, counted at most once per
+    /// address per cooldown.
+    pub fn rate_limit_hits(env: Env) -> u32 {
         env.storage()
             .instance()
-            .set(&DataKey::StretchGoals, &stretch_goals);
+            .get(&ExtraKey::RateLimitHits)
+            .unwrap_or(0)
     }
 
-    /// Add a reward tier (creator only). Rejects min_amount <= 0.
-    pub fn add_reward_tier(env: Env, creator: Address, name: String, min_amount: i128) {
-        let status: Status = env.storage().instance().get(&DataKey::Status).unwrap();
-        if status != Status::Active {
-            panic!("campaign is not active");
-        }
-
-        let stored_creator: Address = env.storage().instance().get(&DataKey::Creator).unwrap();
-        if creator != stored_creator {
-            panic!("not authorized");
-        }
-        creator.require_auth();
+    /// Contributions from `addr` bounced by its cooldown, counted at most
+    /// once per cooldown.
+    pub fn rate_limit_hits_for(env: Env, addr: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get::<_, (u32, u64)>(&ExtraKey::RateLimitHitsFor(addr))
+            .map_or(0, |(hits, _)| hits)
+    }
 
-        if min_amount <= 0 {
-            panic!("min_amount must be greater than 0");
-        }
+    /// How much more `addr` may contribute before its cooldown ends, under
+    /// `cooldown_bypass_amount` (`0` when the bypass is disabled).
+    pub fn cooldown_bypass_remaining(env: Env, addr: Address) -> i128 {
+        cooldown_bypass_remaining_for(&env, &addr)
+    }
 
-        let mut tiers: Vec<RewardTier> = env
-            .storage()
+    /// Total of pledges that expired before they could be collected.
+    pub fn expired_pledges(env: Env) -> i128 {
+        env.storage()
             .instance()
-            .get(&DataKey::RewardTiers)
-            .unwrap_or_else(|| Vec::new(&env));
+            .get(&DataKey::ExpiredPledges)
+            .unwrap_or(0)
+    }
 
-        tiers.push_back(RewardTier {
-            name: name.clone(),
-            min_amount,
-        });
-        env.storage().instance().set(&DataKey::RewardTiers, &tiers);
+    /// Net amount raised per day, as `(timestamp / 86_400, amount)` pairs in
+    /// ascending day order. Only the most recent 90 days are kept; refunds
+    /// are taken back out of the days the refunded contributions were made.
+    pub fn daily_totals(env: Env) -> Vec<(u64, i128)> {
+        let totals: Map<u64, i128> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DailyTotals)
+            .unwrap_or_else(|| Map::new(&env));
+        let mut out = Vec::new(&env);
+        for entry in totals.iter() {
+            out.push_back(entry);
+        }
+        out
+    }
 
-        env.events()
-            .publish(("campaign", "reward_tier_added"), (name, min_amount));
+    /// Tokens set aside at withdrawal and not yet claimed by the creator.
+    pub fn surplus(env: Env) -> i128 {
+        env.storage().instance().get(&DataKey::Surplus).unwrap_or(0)
     }
 
-    /// Returns the full ordered list of reward tiers.
-    pub fn reward_tiers(env: Env) -> Vec<RewardTier> {
+    /// Whether the campaign has been archived.
+    pub fn is_archived(env: Env) -> bool {
         env.storage()
             .instance()
-            .get(&DataKey::RewardTiers)
-            .unwrap_or_else(|| Vec::new(&env))
+            .get(&RecordKey::Archived)
+            .unwrap_or(false)
     }
 
-    /// Returns the highest tier name the user's contribution qualifies for,
-    /// or None if the user has not contributed or no tiers are defined.
-    /// Tiers are evaluated by min_amount descending (highest qualifying tier wins).
-    pub fn get_user_tier(env: Env, user: Address) -> Option<String> {
-        let contribution: i128 = env
-            .storage()
+    /// Whether `addr` is exempt from the contribution cooldown.
+    pub fn is_rate_limit_exempt(env: Env, addr: Address) -> bool {
+        env.storage()
             .persistent()
-            .get(&DataKey::Contribution(user))
-            .unwrap_or(0);
-
-        if contribution <= 0 {
-            return None;
-        }
+            .get(&DataKey::RateLimitExempt(addr))
+            .unwrap_or(false)
+    }
 
-        let tiers: Vec<RewardTier> = env
-            .storage()
+    /// Whether addresses holding no contribution skip the cooldown.
+    pub fn skip_cooldown_for_new(env: Env) -> bool {
+        env.storage()
             .instance()
-            .get(&DataKey::RewardTiers)
-            .unwrap_or_else(|| Vec::new(&env));
+            .get(&ExtraKey::SkipCooldownForNew)
+            .unwrap_or(false)
+    }
+
+    /// Timestamp contributions open at (`0` if open since initialization).
+    pub fn start_time(env: Env) -> u64 {
+        load_options(&env).start_time
+    }
 
-        if tiers.is_empty() {
-            return None;
+    /// Campaign configuration and current state in a single call.
+    pub fn campaign_info(env: Env) -> CampaignInfo {
+        let storage = env.storage().instance();
+        let media: Option<Media> = storage.get(&ExtraKey::Media);
+        CampaignInfo {
+            creator: storage.get(&DataKey::Creator).unwrap(),
+            token: storage.get(&DataKey::Token).unwrap(),
+            goal: storage.get(&DataKey::Goal).unwrap(),
+            hard_cap: storage.get(&DataKey::HardCap).unwrap(),
+            start_time: load_options(&env).start_time,
+            deadline: storage.get(&DataKey::Deadline).unwrap(),
+            min_contribution: storage.get(&DataKey::MinContribution).unwrap(),
+            min_contributors: load_options(&env).min_contributors,
+            total_raised: get_moved(&env, &DataKey::TotalRaised).unwrap_or(0),
+            status: storage.get(&DataKey::Status).unwrap(),
+            refund_order: refund_order(&env),
+            cover: media.clone().map(|media| media.cover),
+            gallery: media.map_or_else(|| Vec::new(&env), |media| media.gallery),
         }
+    }
 
-        let mut best: Option<RewardTier> = None;
-        for tier in tiers.iter() {
-            if contribution >= tier.min_amount {
-                let is_better = match &best {
-                    None => true,
-                    Some(ref b) => tier.min_amount > b.min_amount,
-                };
-                if is_better {
-                    best = Some(tier.clone());
-                }
+    /// Returns the token contract address used for contributions.
+    pub fn token(env: Env) -> Address {
+        env.storage().instance().get(&DataKey::Token).unwrap()
+    }
+
+    /// Returns every accepted token by index, primary token first.
+    pub fn accepted_tokens(env: Env) -> Vec<Address> {
+        let mut tokens = Vec::new(&env);
+        tokens.push_back(env.storage().instance().get(&DataKey::Token).unwrap());
+        if let Some(secondary) = load_options(&env).secondary_tokens {
+            for accepted in secondary.iter() {
+                tokens.push_back(accepted.token);
             }
         }
+        tokens
+    }
 
-        best.map(|t| t.name)
+    /// Returns the amount of the token at `token_index` currently held for
+    /// contributors, in that token's own units.
+    pub fn token_raised(env: Env, token_index: u32) -> i128 {
+        if token_index == 0 {
+            let total: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap();
+            return total - secondary_credited(&env);
+        }
+        load_token_totals(&env)
+            .get(token_index)
+            .map(|tally| tally.raised)
+            .unwrap_or(0)
     }
 
-    /// Returns the next unmet stretch goal milestone.
-    ///
-    /// Returns 0 if there are no stretch goals or all have been met.
-    pub fn current_milestone(env: Env) -> i128 {
-        let total_raised: i128 = env
+    /// Returns a contributor's refundable amount of the token at
+    /// `token_index`, in that token's own units.
+    pub fn token_contribution(env: Env, contributor: Address, token_index: u32) -> i128 {
+        let tallies: Option<Map<u32, TokenTally>> = env
             .storage()
-            .instance()
-            .get(&DataKey::TotalRaised)
+            .persistent()
+            .get(&RecordKey::TokenContribution(contributor.clone()));
+        let credited: i128 = tallies
+            .as_ref()
+            .map(|tallies| tallies.values().iter().map(|tally| tally.credited).sum())
             .unwrap_or(0);
-
-        let stretch_goals: Vec<i128> = env
-            .storage()
-            .instance()
-            .get(&DataKey::StretchGoals)
-            .unwrap_or_else(|| Vec::new(&env));
-
-        for milestone in stretch_goals.iter() {
-            if total_raised < milestone {
-                return milestone;
-            }
+        if token_index == 0 {
+            let contribution: i128 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Contribution(contributor))
+                .unwrap_or(0);
+            return contribution - credited;
         }
-
-        0
-    }
-    pub fn total_raised(env: Env) -> i128 {
-        env.storage()
-            .instance()
-            .get(&DataKey::TotalRaised)
+        tallies
+            .and_then(|tallies| tallies.get(token_index))
+            .map(|tally| tally.raised)
             .unwrap_or(0)
     }
 
-    /// Returns the funding goal.
-    pub fn goal(env: Env) -> i128 {
-        env.storage().instance().get(&DataKey::Goal).unwrap()
+    /// The goal split into whole tokens and the fractional remainder, using
+    /// the token's decimals: `(whole, fraction, decimals)`.
+    pub fn goal_display(env: Env) -> (i128, i128, u32) {
+        let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
+        to_display(&env, goal)
     }
 
-    /// Returns the hard cap (maximum total that can be raised).
-    pub fn hard_cap(env: Env) -> i128 {
-        env.storage().instance().get(&DataKey::HardCap).unwrap()
+    /// `TotalRaised` split into `(whole, fraction, decimals)`, like
+    /// `goal_display`.
+    pub fn total_raised_display(env: Env) -> (i128, i128, u32) {
+        let total: i128 = get_moved(&env, &DataKey::TotalRaised).unwrap_or(0);
+        to_display(&env, total)
     }
 
-    /// Returns the campaign deadline.
-    pub fn deadline(env: Env) -> u64 {
-        env.storage().instance().get(&DataKey::Deadline).unwrap()
+    /// Returns the public list of contributor addresses.
+    ///
+    /// Contributors with an unrevealed anonymous contribution are excluded.
+    pub fn get_contributors(env: Env) -> Vec<Address> {
+        let contributors: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contributors)
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let mut public = Vec::new(&env);
+        for contributor in contributors.iter() {
+            if !env
+                .storage()
+                .persistent()
+                .has(&DataKey::AnonymousAmount(contributor.clone()))
+            {
+                public.push_back(contributor);
+            }
+        }
+        public
     }
 
-    /// Returns the contribution of a specific address.
-    pub fn contribution(env: Env, contributor: Address) -> i128 {
-        let contribution_key = DataKey::Contribution(contributor);
-        env.storage()
-            .persistent()
-            .get(&contribution_key)
-            .unwrap_or(0)
+    /// Returns the most recent memo left by a contributor, if any.
+    pub fn memo(env: Env, contributor: Address) -> Option<String> {
+        env.storage().persistent().get(&DataKey::Memo(contributor))
     }
 
-    /// Returns the pledge of a specific address.
-    pub fn pledge_amount(env: Env, pledger: Address) -> i128 {
-        let pledge_key = DataKey::Pledge(pledger);
-        env.storage().persistent().get(&pledge_key).unwrap_or(0)
+    /// Returns a page of reported expenses, oldest first.
+    pub fn expenses(env: Env, offset: u32, limit: u32) -> Vec<Expense> {
+        let expenses: Vec<Expense> = env
+            .storage()
+            .persistent()
+            .get(&RecordKey::Expenses)
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let mut page = Vec::new(&env);
+        let mut i = offset;
+        while i < expenses.len() && page.len() < limit {
+            page.push_back(expenses.get(i).unwrap());
+            i += 1;
+        }
+        page
     }
 
-    /// Returns the total amount pledged (not yet transferred).
-    pub fn total_pledged(env: Env) -> i128 {
+    /// Returns the total transferred to the creator so far.
+    pub fn paid_to_creator(env: Env) -> i128 {
         env.storage()
             .instance()
-            .get(&DataKey::TotalPledged)
+            .get(&RecordKey::PaidToCreator)
             .unwrap_or(0)
     }
 
-    /// Returns the minimum contribution amount.
-    pub fn min_contribution(env: Env) -> i128 {
-        env.storage()
+    /// Returns how much of the funds paid to the creator has not yet been
+    /// accounted for by reported expenses.
+    pub fn unreported_amount(env: Env) -> i128 {
+        let reported: i128 = env
+            .storage()
             .instance()
-            .get(&DataKey::MinContribution)
-            .unwrap()
+            .get(&RecordKey::ExpensesReported)
+            .unwrap_or(0);
+        Self::paid_to_creator(env) - reported
     }
 
-    /// Returns the primary campaign category.
-    pub fn category(env: Env) -> soroban_sdk::String {
-        env.storage().instance().get(&DataKey::Category).unwrap()
+    /// Returns the receipt for contribution `id`, if it exists.
+    pub fn get_receipt(env: Env, id: u64) -> Option<Receipt> {
+        env.storage()
+            .persistent()
+            .get(&RecordKey::Receipt(id))
+            .map(|receipt| redact_receipt(&env, receipt))
     }
 
-    /// Returns the optional descriptive tags.
-    pub fn tags(env: Env) -> Vec<soroban_sdk::String> {
+    /// Returns the number of contribution receipts issued.
+    pub fn receipt_count(env: Env) -> u64 {
         env.storage()
             .instance()
-            .get(&DataKey::Tags)
-            .unwrap_or(Vec::new(&env))
+            .get(&RecordKey::ReceiptCount)
+            .unwrap_or(0)
     }
 
-    /// Returns comprehensive campaign statistics.
-    pub fn get_stats(env: Env) -> CampaignStats {
-        let total_raised: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TotalRaised)
-            .unwrap_or(0);
-        let goal: i128 = env.storage().instance().get(&DataKey::Goal).unwrap();
-        let contributors: Vec<Address> = env
-            .storage()
-            .instance()
-            .get(&DataKey::Contributors)
-            .unwrap();
-
-        let progress_bps = if goal > 0 {
-            let raw = (total_raised * 10_000) / goal;
-            if raw > 10_000 {
-                10_000
-            } else {
-                raw as u32
+    /// Returns a page of receipts in id order, starting at id `offset`.
+    pub fn receipts_page(env: Env, offset: u64, limit: u32) -> Vec<Receipt> {
+        let count = Self::receipt_count(env.clone());
+        let mut page = Vec::new(&env);
+        let mut id = offset;
+        while id < count && page.len() < limit {
+            if let Some(receipt) = env.storage().persistent().get(&RecordKey::Receipt(id)) {
+                page.push_back(redact_receipt(&env, receipt));
             }
-        } else {
-            0
-        };
+            id += 1;
+        }
+        page
+    }
 
-        let contributor_count = contributors.len();
-        let (average_contribution, largest_contribution) = if contributor_count == 0 {
-            (0, 0)
-        } else {
-            let average = total_raised / contributor_count as i128;
-            let mut largest = 0i128;
-            for contributor in contributors.iter() {
-                let amount: i128 = env
-                    .storage()
-                    .instance()
-                    .get(&DataKey::Contribution(contributor))
-                    .unwrap_or(0);
-                if amount > largest {
-                    largest = amount;
-                }
-            }
-            (average, largest)
-        };
+    /// Returns a page of the most recent memos, newest first.
+    ///
+    /// Only the last 50 memos are retained.
+    pub fn recent_memos(env: Env, offset: u32, limit: u32) -> Vec<MemoEntry> {
+        let recent: Vec<MemoEntry> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RecentMemos)
+            .unwrap_or_else(|| Vec::new(&env));
 
-        CampaignStats {
-            total_raised,
-            goal,
-            progress_bps,
-            contributor_count,
-            average_contribution,
-            largest_contribution,
+        let mut page = Vec::new(&env);
+        let len = recent.len();
+        let mut i = offset;
+        while i < len && page.len() < limit {
+            page.push_back(recent.get(len - 1 - i).unwrap());
+            i += 1;
         }
+        page
     }
 
-    /// Returns the campaign title.
-    pub fn title(env: Env) -> String {
-        let empty = String::from_str(&env, "");
+    /// Returns the aggregate amount contributed anonymously.
+    pub fn anonymous_total(env: Env) -> i128 {
         env.storage()
             .instance()
-            .get(&DataKey::Title)
-            .unwrap_or(empty)
+            .get(&DataKey::AnonymousTotal)
+            .unwrap_or(0)
     }
 
-    /// Returns the campaign description.
-    pub fn description(env: Env) -> String {
-        let empty = String::from_str(&env, "");
+    /// Returns the decimals of the contribution token, recorded at
+    /// initialization so UIs can format amounts.
+    pub fn token_decimals(env: Env) -> u32 {
         env.storage()
             .instance()
-            .get(&DataKey::Description)
-            .unwrap_or(empty)
+            .get(&DataKey::TokenDecimals)
+            .unwrap_or(0)
     }
 
-    /// Returns the campaign social links.
-    pub fn socials(env: Env) -> String {
-        let empty = String::from_str(&env, "");
+    /// Returns the number of contributors who have not been refunded.
+    pub fn contributor_count(env: Env) -> u32 {
         env.storage()
             .instance()
-            .get(&DataKey::SocialLinks)
-            .unwrap_or(empty)
+            .get(&DataKey::ActiveContributorCount)
+            .unwrap_or(0)
     }
 
-    /// Returns the contract version.
-    ///
-    /// This view function allows external tools to detect which version of the
-    /// contract logic is currently running at this address. The version must be
-    /// manually incremented with every contract upgrade (see Issue #38).
-    pub fn version(_env: Env) -> u32 {
-        CONTRACT_VERSION
+    /// Whether enough contributors are still in to meet the
+    /// `min_contributors` quorum (always true without one).
+    pub fn quorum_met(env: Env) -> bool {
+        quorum_met(&env)
     }
 
-    /// Returns the token contract address used for contributions.
-    pub fn token(env: Env) -> Address {
-        env.storage().instance().get(&DataKey::Token).unwrap()
+    /// Contributor slots left under the cap set by `set_max_contributors`,
+    /// or `None` when the number of contributors is not capped.
+    pub fn slots_remaining(env: Env) -> Option<u32> {
+        slots_remaining(&env)
     }
 
-    /// Returns the number of unique contributors.
-    pub fn contributor_count(env: Env) -> u32 {
+    /// Returns the number of unique addresses that ever contributed,
+    /// including those since refunded or forgotten.
+    pub fn lifetime_contributor_count(env: Env) -> u32 {
         let contributors: Vec<Address> = env
             .storage()
             .persistent()
             .get(&DataKey::Contributors)
             .unwrap_or_else(|| Vec::new(&env));
-        contributors.len()
+        let forgotten: u32 = env
+            .storage()
+            .instance()
+            .get(&RecordKey::ForgottenCount)
+            .unwrap_or(0);
+        contributors.len() + forgotten
     }
 }
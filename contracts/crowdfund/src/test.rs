@@ -1,5 +1,6 @@
 #![allow(unused_doc_comments)]
 
+use proptest::prelude::*;
 use soroban_sdk::{
     testutils::{Address as _, Ledger},
     token, Address, Env,
@@ -46,6 +47,21 @@ fn mint_to(env: &Env, token_address: &Address, admin: &Address, to: &Address, am
     let _ = admin;
 }
 
+/// Campaign options for tests whose goals are given in raw base units,
+/// below one whole unit of the 7-decimal test token.
+pub(crate) fn dust_goal_options() -> Option<crate::CampaignOptions> {
+    Some(crate::CampaignOptions {
+        allow_dust_goal: true,
+        ..Default::default()
+    })
+}
+
+/// Topics of a contract event named `name`.
+fn campaign_topics(env: &Env, name: &str) -> soroban_sdk::Vec<soroban_sdk::Val> {
+    use soroban_sdk::{IntoVal, Symbol};
+    (Symbol::new(env, "campaign"), Symbol::new(env, name)).into_val(env)
+}
+
 // ── Tests ───────────────────────────────────────────────────────────────────
 
 #[test]
@@ -64,6 +80,8 @@ fn test_initialize() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     assert_eq!(client.goal(), goal);
@@ -96,6 +114,8 @@ fn test_double_initialize_panics() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
     let result = client.try_initialize(
         &creator,
@@ -105,6 +125,8 @@ fn test_double_initialize_panics() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     assert!(result.is_err());
@@ -129,12 +151,23 @@ fn test_contribute() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 500_000);
 
-    client.contribute(&contributor, &500_000, &None);
+    client.contribute(
+        &contributor,
+        &500_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
 
     assert_eq!(client.total_raised(), 500_000);
     assert_eq!(client.contribution(&contributor), 500_000);
@@ -155,6 +188,8 @@ fn test_multiple_contributions() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     let alice = Address::generate(&env);
@@ -162,8 +197,8 @@ fn test_multiple_contributions() {
     mint_to(&env, &token_address, &admin, &alice, 600_000);
     mint_to(&env, &token_address, &admin, &bob, 400_000);
 
-    client.contribute(&alice, &300_000, None);
-    client.contribute(&bob, &200_000, None);
+    client.contribute(&alice, &300_000, &0, &None, &false, &None, &None, &None);
+    client.contribute(&bob, &200_000, &0, &None, &false, &None, &None, &None);
 
     assert_eq!(client.total_raised(), 500_000);
     assert_eq!(client.contribution(&alice), 300_000);
@@ -185,6 +220,8 @@ fn test_contribute_after_deadline_panics() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     // Fast-forward past the deadline.
@@ -193,7 +230,16 @@ fn test_contribute_after_deadline_panics() {
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 500_000);
 
-    let result = client.try_contribute(&contributor, &500_000);
+    let result = client.try_contribute(
+        &contributor,
+        &500_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
 
     assert!(result.is_err());
     assert_eq!(
@@ -217,11 +263,22 @@ fn test_withdraw_after_goal_met() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 1_000_000);
-    client.contribute(&contributor, &1_000_000, &None);
+    client.contribute(
+        &contributor,
+        &1_000_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
 
     assert_eq!(client.total_raised(), goal);
 
@@ -253,11 +310,22 @@ fn test_withdraw_before_deadline_panics() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 1_000_000);
-    client.contribute(&contributor, &1_000_000, &None);
+    client.contribute(
+        &contributor,
+        &1_000_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
 
     let result = client.try_withdraw();
 
@@ -283,11 +351,22 @@ fn test_withdraw_goal_not_reached_panics() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 500_000);
-    client.contribute(&contributor, &500_000, &None);
+    client.contribute(
+        &contributor,
+        &500_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
 
     // Move past deadline, but goal not met.
     env.ledger().set_timestamp(deadline + 1);
@@ -316,6 +395,8 @@ fn test_refund_when_goal_not_met() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     let alice = Address::generate(&env);
@@ -323,8 +404,8 @@ fn test_refund_when_goal_not_met() {
     mint_to(&env, &token_address, &admin, &alice, 300_000);
     mint_to(&env, &token_address, &admin, &bob, 200_000);
 
-    client.contribute(&alice, &300_000, None);
-    client.contribute(&bob, &200_000, None);
+    client.contribute(&alice, &300_000, &0, &None, &false, &None, &None, &None);
+    client.contribute(&bob, &200_000, &0, &None, &false, &None, &None, &None);
 
     // Move past deadline — goal not met.
     env.ledger().set_timestamp(deadline + 1);
@@ -353,11 +434,22 @@ fn test_refund_when_goal_reached_panics() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 1_000_000);
-    client.contribute(&contributor, &1_000_000, &None);
+    client.contribute(
+        &contributor,
+        &1_000_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
 
     env.ledger().set_timestamp(deadline + 1);
 
@@ -404,6 +496,8 @@ fn test_bug_condition_exploration_all_error_conditions_panic() {
             &deadline,
             &1_000,
             &None,
+            &dust_goal_options(),
+            &None,
         );
         let result = client.try_initialize(
             &creator,
@@ -413,6 +507,8 @@ fn test_bug_condition_exploration_all_error_conditions_panic() {
             &deadline,
             &1_000,
             &None,
+            &dust_goal_options(),
+            &None,
         );
 
         assert!(result.is_err());
@@ -435,13 +531,24 @@ fn test_bug_condition_exploration_all_error_conditions_panic() {
             &deadline,
             &1_000,
             &None,
+            &dust_goal_options(),
+            &None,
         );
 
         env.ledger().set_timestamp(deadline + 1);
 
         let contributor = Address::generate(&env);
         mint_to(&env, &token_address, &admin, &contributor, 500_000);
-        let result = client.try_contribute(&contributor, &500_000);
+        let result = client.try_contribute(
+            &contributor,
+            &500_000,
+            &0,
+            &None,
+            &false,
+            &None,
+            &None,
+            &None,
+        );
 
         assert!(result.is_err());
         assert_eq!(result.unwrap_err().unwrap(), ContractError::CampaignEnded);
@@ -460,11 +567,22 @@ fn test_bug_condition_exploration_all_error_conditions_panic() {
             &deadline,
             &1_000,
             &None,
+            &dust_goal_options(),
+            &None,
         );
 
         let contributor = Address::generate(&env);
         mint_to(&env, &token_address, &admin, &contributor, 1_000_000);
-        client.contribute(&contributor, &1_000_000, &None);
+        client.contribute(
+            &contributor,
+            &1_000_000,
+            &0,
+            &None,
+            &false,
+            &None,
+            &None,
+            &None,
+        );
 
         let result = client.try_withdraw();
 
@@ -488,11 +606,22 @@ fn test_bug_condition_exploration_all_error_conditions_panic() {
             &deadline,
             &1_000,
             &None,
+            &dust_goal_options(),
+            &None,
         );
 
         let contributor = Address::generate(&env);
         mint_to(&env, &token_address, &admin, &contributor, 500_000);
-        client.contribute(&contributor, &500_000, &None);
+        client.contribute(
+            &contributor,
+            &500_000,
+            &0,
+            &None,
+            &false,
+            &None,
+            &None,
+            &None,
+        );
 
         env.ledger().set_timestamp(deadline + 1);
         let result = client.try_withdraw();
@@ -514,11 +643,22 @@ fn test_bug_condition_exploration_all_error_conditions_panic() {
             &deadline,
             &1_000,
             &None,
+            &dust_goal_options(),
+            &None,
         );
 
         let contributor = Address::generate(&env);
         mint_to(&env, &token_address, &admin, &contributor, 500_000);
-        client.contribute(&contributor, &500_000, &None);
+        client.contribute(
+            &contributor,
+            &500_000,
+            &0,
+            &None,
+            &false,
+            &None,
+            &None,
+            &None,
+        );
 
         let result = client.try_refund();
 
@@ -542,11 +682,22 @@ fn test_bug_condition_exploration_all_error_conditions_panic() {
             &deadline,
             &1_000,
             &None,
+            &dust_goal_options(),
+            &None,
         );
 
         let contributor = Address::generate(&env);
         mint_to(&env, &token_address, &admin, &contributor, 1_000_000);
-        client.contribute(&contributor, &1_000_000, &None);
+        client.contribute(
+            &contributor,
+            &1_000_000,
+            &0,
+            &None,
+            &false,
+            &None,
+            &None,
+            &None,
+        );
 
         env.ledger().set_timestamp(deadline + 1);
         let result = client.try_refund();
@@ -573,6 +724,8 @@ fn test_cancel_with_no_contributions() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     client.cancel();
@@ -595,6 +748,8 @@ fn test_cancel_with_contributions() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     let alice = Address::generate(&env);
@@ -602,8 +757,8 @@ fn test_cancel_with_contributions() {
     mint_to(&env, &token_address, &admin, &alice, 300_000);
     mint_to(&env, &token_address, &admin, &bob, 200_000);
 
-    client.contribute(&alice, &300_000, None);
-    client.contribute(&bob, &200_000, None);
+    client.contribute(&alice, &300_000, &0, &None, &false, &None, &None, &None);
+    client.contribute(&bob, &200_000, &0, &None, &false, &None, &None, &None);
 
     client.cancel();
 
@@ -613,6 +768,158 @@ fn test_cancel_with_contributions() {
     assert_eq!(client.total_raised(), 0);
 }
 
+#[test]
+fn test_contribute_after_cancel_rejected() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+    client.cancel();
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 10_000);
+    let result = client.try_contribute(&alice, &10_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::CampaignNotActive
+    );
+    let result = client.try_pledge(&alice, &10_000);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::CampaignNotActive
+    );
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&alice), 10_000);
+    assert_eq!(token_client.balance(&client.address), 0);
+    assert_eq!(client.total_raised(), 0);
+}
+
+#[test]
+fn test_teardown_fresh_campaign() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+    let title = soroban_sdk::String::from_str(&env, "Typo");
+    client.update_metadata(&creator, &Some(title), &None, &None);
+    let bronze = soroban_sdk::String::from_str(&env, "Bronze");
+    client.add_reward_tier(&creator, &bronze, &10_000, &0);
+
+    client.teardown();
+    assert_eq!(last_event::<Address>(&env, "torn_down"), creator);
+    assert!(client.campaign_info().status == crate::Status::Cancelled);
+    assert!(client.is_archived());
+    assert_eq!(client.title(), soroban_sdk::String::from_str(&env, ""));
+    assert_eq!(client.reward_tiers().len(), 0);
+}
+
+#[test]
+fn test_teardown_and_archive_survive_failing_factory() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+    // The token contract has no `notify_archived` or `admin`.
+    let options = crate::CampaignOptions {
+        allow_dust_goal: true,
+        factory: Some(token_address.clone()),
+        ..Default::default()
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options.clone()),
+        &None,
+    );
+    client.teardown();
+    assert!(client.is_archived());
+
+    let other = CrowdfundContractClient::new(&env, &env.register(CrowdfundContract, ()));
+    other.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+    other.cancel();
+    other.archive(&creator);
+    assert!(other.is_archived());
+}
+
+#[test]
+fn test_teardown_rejected_after_contribution_or_window() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 7 * 24 * 60 * 60;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+    let initialized_at = env.ledger().timestamp();
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 10_000);
+    client.contribute(&alice, &10_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(
+        client.try_teardown().unwrap_err().unwrap(),
+        crate::ContractError::CampaignHasFunds
+    );
+
+    // A second, unfunded campaign stays up once the window has passed.
+    let other = CrowdfundContractClient::new(&env, &env.register(CrowdfundContract, ()));
+    other.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+    env.ledger()
+        .set_timestamp(initialized_at + 24 * 60 * 60 + 1);
+    assert_eq!(
+        other.try_teardown().unwrap_err().unwrap(),
+        crate::ContractError::TeardownWindowClosed
+    );
+    assert!(other.campaign_info().status == crate::Status::Active);
+}
+
 // ── Minimum Contribution Tests ─────────────────────────────────────────────
 
 #[test]
@@ -630,12 +937,23 @@ fn test_contribute_exact_minimum() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 10_000);
 
-    client.contribute(&contributor, &10_000, None);
+    client.contribute(
+        &contributor,
+        &10_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
 
     assert_eq!(client.total_raised(), 10_000);
     assert_eq!(client.contribution(&contributor), 10_000);
@@ -656,12 +974,23 @@ fn test_contribute_above_minimum() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 50_000);
 
-    client.contribute(&contributor, &50_000, &None);
+    client.contribute(
+        &contributor,
+        &50_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
 
     assert_eq!(client.total_raised(), 50_000);
     assert_eq!(client.contribution(&contributor), 50_000);
@@ -684,18 +1013,29 @@ fn test_get_user_tier_bronze_level() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     let bronze = soroban_sdk::String::from_str(&env, "Bronze");
     let silver = soroban_sdk::String::from_str(&env, "Silver");
     let gold = soroban_sdk::String::from_str(&env, "Gold");
-    client.add_reward_tier(&creator, &bronze, &10_000);
-    client.add_reward_tier(&creator, &silver, &100_000);
-    client.add_reward_tier(&creator, &gold, &500_000);
+    client.add_reward_tier(&creator, &bronze, &10_000, &0);
+    client.add_reward_tier(&creator, &silver, &100_000, &0);
+    client.add_reward_tier(&creator, &gold, &500_000, &0);
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 50_000);
-    client.contribute(&contributor, &50_000, &None);
+    client.contribute(
+        &contributor,
+        &50_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
 
     let tier = client.get_user_tier(&contributor);
     assert!(tier.is_some());
@@ -717,18 +1057,29 @@ fn test_get_user_tier_gold_level() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     let bronze = soroban_sdk::String::from_str(&env, "Bronze");
     let silver = soroban_sdk::String::from_str(&env, "Silver");
     let gold = soroban_sdk::String::from_str(&env, "Gold");
-    client.add_reward_tier(&creator, &bronze, &10_000);
-    client.add_reward_tier(&creator, &silver, &100_000);
-    client.add_reward_tier(&creator, &gold, &500_000);
+    client.add_reward_tier(&creator, &bronze, &10_000, &0);
+    client.add_reward_tier(&creator, &silver, &100_000, &0);
+    client.add_reward_tier(&creator, &gold, &500_000, &0);
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 600_000);
-    client.contribute(&contributor, &600_000, &None);
+    client.contribute(
+        &contributor,
+        &600_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
 
     let tier = client.get_user_tier(&contributor);
     assert!(tier.is_some());
@@ -750,10 +1101,12 @@ fn test_get_user_tier_non_contributor_returns_none() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     let bronze = soroban_sdk::String::from_str(&env, "Bronze");
-    client.add_reward_tier(&creator, &bronze, &10_000);
+    client.add_reward_tier(&creator, &bronze, &10_000, &0);
 
     let non_contributor = Address::generate(&env);
     let tier = client.get_user_tier(&non_contributor);
@@ -775,11 +1128,22 @@ fn test_get_user_tier_no_tiers_defined_returns_none() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 500_000);
-    client.contribute(&contributor, &500_000, &None);
+    client.contribute(
+        &contributor,
+        &500_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
 
     let tier = client.get_user_tier(&contributor);
     assert!(tier.is_none());
@@ -800,18 +1164,29 @@ fn test_get_user_tier_highest_qualifying_tier_returned() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     let bronze = soroban_sdk::String::from_str(&env, "Bronze");
     let silver = soroban_sdk::String::from_str(&env, "Silver");
     let gold = soroban_sdk::String::from_str(&env, "Gold");
-    client.add_reward_tier(&creator, &bronze, &10_000);
-    client.add_reward_tier(&creator, &silver, &100_000);
-    client.add_reward_tier(&creator, &gold, &500_000);
+    client.add_reward_tier(&creator, &bronze, &10_000, &0);
+    client.add_reward_tier(&creator, &silver, &100_000, &0);
+    client.add_reward_tier(&creator, &gold, &500_000, &0);
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 1_000_000);
-    client.contribute(&contributor, &1_000_000, &None);
+    client.contribute(
+        &contributor,
+        &1_000_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
 
     let tier = client.get_user_tier(&contributor);
     assert!(tier.is_some());
@@ -834,11 +1209,13 @@ fn test_add_reward_tier_non_creator_rejected() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     let non_creator = Address::generate(&env);
     let bronze = soroban_sdk::String::from_str(&env, "Bronze");
-    client.add_reward_tier(&non_creator, &bronze, &10_000);
+    client.add_reward_tier(&non_creator, &bronze, &10_000, &0);
 }
 
 #[test]
@@ -857,10 +1234,12 @@ fn test_add_reward_tier_rejects_zero_min_amount() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     let bronze = soroban_sdk::String::from_str(&env, "Bronze");
-    client.add_reward_tier(&creator, &bronze, &0);
+    client.add_reward_tier(&creator, &bronze, &0, &0);
 }
 
 #[test]
@@ -878,14 +1257,16 @@ fn test_reward_tiers_view() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     assert_eq!(client.reward_tiers().len(), 0);
 
     let bronze = soroban_sdk::String::from_str(&env, "Bronze");
     let silver = soroban_sdk::String::from_str(&env, "Silver");
-    client.add_reward_tier(&creator, &bronze, &10_000);
-    client.add_reward_tier(&creator, &silver, &100_000);
+    client.add_reward_tier(&creator, &bronze, &10_000, &0);
+    client.add_reward_tier(&creator, &silver, &100_000, &0);
 
     let tiers = client.reward_tiers();
     assert_eq!(tiers.len(), 2);
@@ -895,6 +1276,79 @@ fn test_reward_tiers_view() {
     assert_eq!(tiers.get(1).unwrap().min_amount, 100_000);
 }
 
+#[test]
+fn test_tier_delivery_status_after_withdraw() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let bronze = soroban_sdk::String::from_str(&env, "Bronze");
+    let estimate = deadline + 30 * 24 * 60 * 60;
+    client.add_reward_tier(&creator, &bronze, &10_000, &estimate);
+    let tier = client.reward_tiers().get(0).unwrap();
+    assert_eq!(tier.estimated_delivery, estimate);
+    assert_eq!(tier.delivery_status, 0);
+
+    let contributor = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &contributor, 1_000_000);
+    client.contribute(
+        &contributor,
+        &1_000_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
+    env.ledger().set_timestamp(deadline + 1);
+    client.withdraw();
+
+    client.set_tier_delivery_status(&0, &2);
+    assert_eq!(
+        last_event::<crate::events::TierDeliveryUpdatedEvent>(&env, "tier_delivery_updated"),
+        crate::events::TierDeliveryUpdatedEvent {
+            index: 0,
+            name: bronze,
+            status: 2,
+        }
+    );
+    let tier = client.reward_tiers().get(0).unwrap();
+    assert_eq!(tier.delivery_status, 2);
+    assert_eq!(tier.estimated_delivery, estimate);
+}
+
+#[test]
+#[should_panic(expected = "estimated delivery must be after the deadline")]
+fn test_tier_delivery_estimate_before_deadline_panics() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let bronze = soroban_sdk::String::from_str(&env, "Bronze");
+    client.add_reward_tier(&creator, &bronze, &10_000, &deadline);
+}
+
 // ── Roadmap Tests ──────────────────────────────────────────────────────────
 
 #[test]
@@ -912,6 +1366,8 @@ fn test_add_single_roadmap_item() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     let current_time = env.ledger().timestamp();
@@ -941,6 +1397,8 @@ fn test_add_multiple_roadmap_items_in_order() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     let current_time = env.ledger().timestamp();
@@ -982,6 +1440,8 @@ fn test_add_roadmap_item_with_past_date_panics() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     let current_time = env.ledger().timestamp();
@@ -1009,6 +1469,8 @@ fn test_add_roadmap_item_with_current_date_panics() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     let current_time = env.ledger().timestamp();
@@ -1033,6 +1495,8 @@ fn test_add_roadmap_item_with_empty_description_panics() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     let current_time = env.ledger().timestamp();
@@ -1069,6 +1533,8 @@ fn test_add_roadmap_item_by_non_creator_panics() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     env.mock_all_auths_allowing_non_root_auth();
@@ -1106,6 +1572,8 @@ fn test_roadmap_empty_after_initialization() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     let roadmap = client.roadmap();
@@ -1129,6 +1597,8 @@ fn test_update_title() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     // Update title.
@@ -1153,6 +1623,8 @@ fn test_update_description() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     // Update description.
@@ -1175,12 +1647,25 @@ fn test_update_socials() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     // Update social links.
-    let socials = soroban_sdk::String::from_str(&env, "https://twitter.com/campaign");
+    let socials = soroban_sdk::vec![
+        &env,
+        crate::SocialLink {
+            platform: soroban_sdk::Symbol::new(&env, "twitter"),
+            url: soroban_sdk::String::from_str(&env, "https://twitter.com/campaign"),
+        },
+    ];
     client.update_metadata(&creator, &None, &None, &Some(socials));
-}
+
+    assert_eq!(
+        client.socials(),
+        soroban_sdk::String::from_str(&env, "https://twitter.com/campaign")
+    );
+}
 
 #[test]
 fn test_partial_update() {
@@ -1197,6 +1682,8 @@ fn test_partial_update() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     // Update only title (description and socials should remain None).
@@ -1204,8 +1691,19 @@ fn test_partial_update() {
     client.update_metadata(&creator, &Some(title), &None, &None);
 
     // Update only socials (should not affect title).
-    let socials = soroban_sdk::String::from_str(&env, "https://twitter.com/new");
+    let socials = soroban_sdk::vec![
+        &env,
+        crate::SocialLink {
+            platform: soroban_sdk::Symbol::new(&env, "twitter"),
+            url: soroban_sdk::String::from_str(&env, "https://twitter.com/new"),
+        },
+    ];
     client.update_metadata(&creator, &None, &None, &Some(socials));
+
+    assert_eq!(
+        client.title(),
+        soroban_sdk::String::from_str(&env, "Updated Title")
+    );
 }
 
 #[test]
@@ -1224,12 +1722,23 @@ fn test_update_metadata_when_not_active_panics() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     // Contribute to meet the goal.
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 1_000_000);
-    client.contribute(&contributor, &1_000_000, &None);
+    client.contribute(
+        &contributor,
+        &1_000_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
 
     // Move past deadline and withdraw (status becomes Successful).
     env.ledger().set_timestamp(deadline + 1);
@@ -1240,6 +1749,78 @@ fn test_update_metadata_when_not_active_panics() {
     client.update_metadata(&creator, &Some(title), &None, &None);
 }
 
+#[test]
+fn test_set_media_round_trip_and_frozen_after_withdrawal() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+    let hash = |byte: u8| soroban_sdk::BytesN::from_array(&env, &[byte; 32]);
+
+    assert_eq!(client.media(), None);
+    let gallery = soroban_sdk::vec![&env, hash(2), hash(3)];
+    client.set_media(&hash(1), &gallery);
+    let media = crate::Media {
+        cover: hash(1),
+        gallery,
+    };
+    assert_eq!(client.media(), Some(media.clone()));
+    let info = client.campaign_info();
+    assert_eq!(info.cover, Some(hash(1)));
+    assert_eq!(info.gallery, media.gallery);
+
+    let result = client.try_set_media(&hash(1), &soroban_sdk::vec![&env, hash(2), hash(2)]);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::InvalidInput
+    );
+    let result = client.try_set_media(&hash(1), &soroban_sdk::vec![&env, hash(1)]);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::InvalidInput
+    );
+    let mut oversized = soroban_sdk::Vec::new(&env);
+    for byte in 10..23 {
+        oversized.push_back(hash(byte));
+    }
+    let result = client.try_set_media(&hash(1), &oversized);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::MetadataTooLarge
+    );
+
+    let contributor = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &contributor, 1_000_000);
+    client.contribute(
+        &contributor,
+        &1_000_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
+    env.ledger().set_timestamp(deadline + 1);
+    client.withdraw();
+
+    let result = client.try_set_media(&hash(4), &soroban_sdk::vec![&env]);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::CampaignNotActive
+    );
+    assert_eq!(client.media(), Some(media));
+}
+
 #[test]
 #[should_panic(expected = "campaign is not active")]
 fn test_update_metadata_after_cancel_panics() {
@@ -1256,6 +1837,8 @@ fn test_update_metadata_after_cancel_panics() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     // Cancel the campaign.
@@ -1287,6 +1870,8 @@ fn test_update_deadline_extends_campaign() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     // Verify initial deadline
@@ -1316,6 +1901,8 @@ fn test_update_deadline_rejects_shortening() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     // Try to shorten the deadline (should panic)
@@ -1339,6 +1926,8 @@ fn test_update_deadline_rejects_equal_deadline() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     // Try to set deadline to the same value (should panic)
@@ -1361,6 +1950,8 @@ fn test_update_deadline_when_not_active_panics() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     // Move past deadline and refund
@@ -1374,6 +1965,75 @@ fn test_update_deadline_when_not_active_panics() {
     client.update_deadline(&new_deadline);
 }
 
+/// Initialize a campaign whose deadline may be extended by at most
+/// `max_extension` seconds in total.
+fn setup_extension_capped_campaign(
+    max_extension: u64,
+) -> (Env, CrowdfundContractClient<'static>, u64) {
+    let (env, client, creator, token_address, _admin) = setup_env();
+
+    let options = crate::CampaignOptions {
+        allow_dust_goal: true,
+        max_total_extension_secs: max_extension,
+        ..Default::default()
+    };
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &1_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+
+    (env, client, deadline)
+}
+
+#[test]
+fn test_update_deadline_rejects_extension_beyond_cap() {
+    let (_env, client, deadline) = setup_extension_capped_campaign(1_000);
+
+    let result = client.try_update_deadline(&(deadline + 1_001));
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::ExtensionLimitExceeded
+    );
+    assert_eq!(client.deadline(), deadline);
+}
+
+#[test]
+fn test_update_deadline_small_extensions_sum_to_cap() {
+    let (_env, client, deadline) = setup_extension_capped_campaign(1_000);
+
+    client.update_deadline(&(deadline + 400));
+    client.update_deadline(&(deadline + 700));
+    client.update_deadline(&(deadline + 1_000));
+    assert_eq!(client.deadline(), deadline + 1_000);
+
+    let result = client.try_update_deadline(&(deadline + 1_001));
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::ExtensionLimitExceeded
+    );
+}
+
+#[test]
+fn test_update_deadline_rejected_after_expiry() {
+    let (env, client, deadline) = setup_extension_capped_campaign(0);
+
+    env.ledger().set_timestamp(deadline + 1);
+    let result = client.try_update_deadline(&(deadline + 3600));
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::CampaignEnded
+    );
+}
+
 // ── Stretch Goal Tests ─────────────────────────────────────────────────────
 
 #[test]
@@ -1391,6 +2051,8 @@ fn test_add_single_stretch_goal() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     let stretch_milestone: i128 = 1_500_000;
@@ -1421,7 +2083,7 @@ proptest! {
         let deadline = env.ledger().timestamp() + deadline_offset;
         let hard_cap = (amount1 + amount2 + amount3).max(goal * 2);
 
-        client.initialize(&creator, &token_address, &goal, &hard_cap, &deadline, &1_000, &None);
+        client.initialize(&creator, &token_address, &goal, &hard_cap, &deadline, &1_000, &None, &dust_goal_options(), &None);
 
         let alice = Address::generate(&env);
         let bob = Address::generate(&env);
@@ -1431,9 +2093,9 @@ proptest! {
         mint_to(&env, &token_address, &admin, &bob, amount2);
         mint_to(&env, &token_address, &admin, &charlie, amount3);
 
-        client.contribute(&alice, &amount1, None);
-        client.contribute(&bob, &amount2, None);
-        client.contribute(&charlie, &amount3, None);
+        client.contribute(&alice, &amount1, &0, &None, &false, &None, &None, &None);
+        client.contribute(&bob, &amount2, &0, &None, &false, &None, &None, &None);
+        client.contribute(&charlie, &amount3, &0, &None, &false, &None, &None, &None);
 
         let expected_total = amount1 + amount2 + amount3;
         let actual_total = client.total_raised();
@@ -1466,11 +2128,11 @@ proptest! {
         // Ensure contribution is less than goal
         let safe_contribution = contribution.min(goal - 1);
 
-        client.initialize(&creator, &token_address, &goal, &(goal * 2), &deadline, &1_000, &None);
+        client.initialize(&creator, &token_address, &goal, &(goal * 2), &deadline, &1_000, &None, &dust_goal_options(), &None);
 
         let contributor = Address::generate(&env);
         mint_to(&env, &token_address, &admin, &contributor, safe_contribution);
-        client.contribute(&contributor, &safe_contribution, None);
+        client.contribute(&contributor, &safe_contribution, &0, &None, &false, &None, &None, &None);
 
         // Move past deadline (goal not met)
         env.ledger().set_timestamp(deadline + 1);
@@ -1507,7 +2169,7 @@ proptest! {
         let (env, client, creator, token_address, admin) = setup_env();
         let deadline = env.ledger().timestamp() + deadline_offset;
 
-        client.initialize(&creator, &token_address, &goal, &(goal * 2), &deadline, &1_000, &None);
+        client.initialize(&creator, &token_address, &goal, &(goal * 2), &deadline, &1_000, &None, &dust_goal_options(), &None);
 
         let contributor = Address::generate(&env);
         // Mint enough tokens so the failure is due to amount validation, not balance
@@ -1515,7 +2177,7 @@ proptest! {
 
         // Attempt to contribute zero or negative amount
         // This should fail due to minimum contribution check
-        let result = client.try_contribute(&contributor, &negative_amount);
+        let result = client.try_contribute(&contributor, &negative_amount, &0, &None, &false, &None, &None, &None);
 
         // **INVARIANT**: Contribution <= 0 must fail
         prop_assert!(
@@ -1551,6 +2213,8 @@ proptest! {
             &past_deadline,
             &1_000,
             &None,
+            &dust_goal_options(),
+            &None,
         );
 
         // **INVARIANT**: Past deadline should fail or be rejected
@@ -1588,7 +2252,7 @@ proptest! {
         let expected_total = amount1 + amount2 + amount3;
         let hard_cap = expected_total.max(goal);
 
-        client.initialize(&creator, &token_address, &goal, &hard_cap, &deadline, &1_000, &None);
+        client.initialize(&creator, &token_address, &goal, &hard_cap, &deadline, &1_000, &None, &dust_goal_options(), &None);
 
         let contributor1 = Address::generate(&env);
         let contributor2 = Address::generate(&env);
@@ -1598,9 +2262,9 @@ proptest! {
         mint_to(&env, &token_address, &admin, &contributor2, amount2);
         mint_to(&env, &token_address, &admin, &contributor3, amount3);
 
-        client.contribute(&contributor1, &amount1, None);
-        client.contribute(&contributor2, &amount2, None);
-        client.contribute(&contributor3, &amount3, None);
+        client.contribute(&contributor1, &amount1, &0, &None, &false, &None, &None, &None);
+        client.contribute(&contributor2, &amount2, &0, &None, &false, &None, &None, &None);
+        client.contribute(&contributor3, &amount3, &0, &None, &false, &None, &None, &None);
 
         // **INVARIANT**: total_raised must equal sum of all contributions
         prop_assert_eq!(client.total_raised(), expected_total);
@@ -1625,11 +2289,11 @@ proptest! {
         let (env, client, creator, token_address, admin) = setup_env();
         let deadline = env.ledger().timestamp() + deadline_offset;
 
-        client.initialize(&creator, &token_address, &goal, &(goal * 2), &deadline, &1_000, &None);
+        client.initialize(&creator, &token_address, &goal, &(goal * 2), &deadline, &1_000, &None, &dust_goal_options(), &None);
 
         let contributor = Address::generate(&env);
         mint_to(&env, &token_address, &admin, &contributor, goal);
-        client.contribute(&contributor, &goal, None);
+        client.contribute(&contributor, &goal, &0, &None, &false, &None, &None, &None);
 
         // Move past deadline
         env.ledger().set_timestamp(deadline + 1);
@@ -1670,23 +2334,25 @@ proptest! {
         let (env, client, creator, token_address, admin) = setup_env();
         let deadline = env.ledger().timestamp() + deadline_offset;
 
-        client.initialize(&creator, &token_address, &goal, &(goal * 2), &deadline, &1_000, &None);
+        client.initialize(&creator, &token_address, &goal, &(goal * 2), &deadline, &1_000, &None, &dust_goal_options(), &None);
 
         let contributor = Address::generate(&env);
         let total_needed = amount1.saturating_add(amount2).saturating_add(amount3);
         mint_to(&env, &token_address, &admin, &contributor, total_needed);
 
         // First contribution
-        client.contribute(&contributor, &amount1, None);
+        client.contribute(&contributor, &amount1, &0, &None, &false, &None, &None, &None);
         prop_assert_eq!(client.contribution(&contributor), amount1);
 
-        // Second contribution
-        client.contribute(&contributor, &amount2, None);
+        // Second contribution (after the rate-limit cooldown)
+        env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+        client.contribute(&contributor, &amount2, &0, &None, &false, &None, &None, &None);
         let expected_after_2 = amount1.saturating_add(amount2);
         prop_assert_eq!(client.contribution(&contributor), expected_after_2);
 
         // Third contribution
-        client.contribute(&contributor, &amount3, None);
+        env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+        client.contribute(&contributor, &amount3, &0, &None, &false, &None, &None, &None);
         let expected_total = amount1.saturating_add(amount2).saturating_add(amount3);
         prop_assert_eq!(client.contribution(&contributor), expected_total);
 
@@ -1711,11 +2377,11 @@ proptest! {
 
         let safe_contribution = contribution.min(goal - 1);
 
-        client.initialize(&creator, &token_address, &goal, &(goal * 2), &deadline, &1_000, &None);
+        client.initialize(&creator, &token_address, &goal, &(goal * 2), &deadline, &1_000, &None, &dust_goal_options(), &None);
 
         let contributor = Address::generate(&env);
         mint_to(&env, &token_address, &admin, &contributor, safe_contribution);
-        client.contribute(&contributor, &safe_contribution, None);
+        client.contribute(&contributor, &safe_contribution, &0, &None, &false, &None, &None, &None);
 
         // Verify total_raised is set
         prop_assert_eq!(client.total_raised(), safe_contribution);
@@ -1745,14 +2411,14 @@ proptest! {
         let (env, client, creator, token_address, admin) = setup_env();
         let deadline = env.ledger().timestamp() + deadline_offset;
 
-        client.initialize(&creator, &token_address, &goal, &(goal * 2), &deadline, &min_contribution, &None);
+        client.initialize(&creator, &token_address, &goal, &(goal * 2), &deadline, &min_contribution, &None, &dust_goal_options(), &None);
 
         let contributor = Address::generate(&env);
         let amount_to_contribute = below_minimum.min(min_contribution - 1);
         mint_to(&env, &token_address, &admin, &contributor, amount_to_contribute);
 
         // Attempt to contribute below minimum
-        let result = client.try_contribute(&contributor, &amount_to_contribute);
+        let result = client.try_contribute(&contributor, &amount_to_contribute, &0, &None, &false, &None, &None, &None);
 
         // **INVARIANT**: Contribution below minimum must fail
         prop_assert!(
@@ -1778,7 +2444,7 @@ proptest! {
         let (env, client, creator, token_address, admin) = setup_env();
         let deadline = env.ledger().timestamp() + deadline_offset;
 
-        client.initialize(&creator, &token_address, &goal, &(goal * 2), &deadline, &1_000, &None);
+        client.initialize(&creator, &token_address, &goal, &(goal * 2), &deadline, &1_000, &None, &dust_goal_options(), &None);
 
         // Move past deadline
         env.ledger().set_timestamp(deadline + time_after_deadline);
@@ -1787,7 +2453,7 @@ proptest! {
         mint_to(&env, &token_address, &admin, &contributor, contribution);
 
         // Attempt to contribute after deadline
-        let result = client.try_contribute(&contributor, &contribution);
+        let result = client.try_contribute(&contributor, &contribution, &0, &None, &false, &None, &None, &None);
 
         // **INVARIANT**: Contribution after deadline must fail
         prop_assert!(
@@ -1819,6 +2485,8 @@ fn test_contribute_rejected_when_paused() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     // Pause the contract
@@ -1828,7 +2496,8 @@ fn test_contribute_rejected_when_paused() {
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 5_000);
 
-    let result = client.try_contribute(&contributor, &5_000, &None);
+    let result =
+        client.try_contribute(&contributor, &5_000, &0, &None, &false, &None, &None, &None);
 
     assert!(result.is_err());
     assert_eq!(
@@ -1853,12 +2522,14 @@ fn test_withdraw_rejected_when_paused() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     // Contribute to meet goal
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, goal);
-    client.contribute(&contributor, &goal, None);
+    client.contribute(&contributor, &goal, &0, &None, &false, &None, &None, &None);
 
     // Move past deadline
     env.ledger().set_timestamp(deadline + 1);
@@ -1892,12 +2563,23 @@ fn test_refund_rejected_when_paused() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     // Contribute but don't meet goal
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 500_000);
-    client.contribute(&contributor, &500_000, &None);
+    client.contribute(
+        &contributor,
+        &500_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
 
     // Move past deadline
     env.ledger().set_timestamp(deadline + 1);
@@ -1931,6 +2613,8 @@ fn test_all_interactions_succeed_after_unpause() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     // Pause the contract
@@ -1942,7 +2626,7 @@ fn test_all_interactions_succeed_after_unpause() {
     // Contribute should succeed
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 5_000);
-    client.contribute(&contributor, &5_000, &None);
+    client.contribute(&contributor, &5_000, &0, &None, &false, &None, &None, &None);
 
     assert_eq!(client.total_raised(), 5_000);
 }
@@ -1975,6 +2659,8 @@ fn test_set_paused_rejected_from_non_creator() {
         &deadline,
         &min_contribution,
         &None,
+        &dust_goal_options(),
+        &None,
     );
 
     env.mock_all_auths_allowing_non_root_auth();
@@ -2003,7 +2689,17 @@ fn test_contributor_count_zero_before_contributions() {
     let goal: i128 = 1_000_000;
     let min_contribution: i128 = 1_000;
 
-    client.initialize(&creator, &token_address, &goal, &(goal * 2), &deadline, &min_contribution, &None);
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &(goal * 2),
+        &deadline,
+        &min_contribution,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
 
     assert_eq!(client.contributor_count(), 0);
 }
@@ -2016,11 +2712,30 @@ fn test_contributor_count_one_after_single_contribution() {
     let goal: i128 = 1_000_000;
     let min_contribution: i128 = 1_000;
 
-    client.initialize(&creator, &token_address, &goal, &(goal * 2), &deadline, &min_contribution, &None);
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &(goal * 2),
+        &deadline,
+        &min_contribution,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
 
     let contributor = Address::generate(&env);
     mint_to(&env, &token_address, &admin, &contributor, 500_000);
-    client.contribute(&contributor, &500_000);
+    client.contribute(
+        &contributor,
+        &500_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
 
     assert_eq!(client.contributor_count(), 1);
 }
@@ -2033,22 +2748,8275 @@ fn test_contributor_count_multiple_contributors() {
     let goal: i128 = 1_000_000;
     let min_contribution: i128 = 1_000;
 
-    client.initialize(&creator, &token_address, &goal, &(goal * 2), &deadline, &min_contribution, &None);
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &(goal * 2),
+        &deadline,
+        &min_contribution,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
 
     let alice = Address::generate(&env);
     let bob = Address::generate(&env);
     let charlie = Address::generate(&env);
-    
+
     mint_to(&env, &token_address, &admin, &alice, 300_000);
     mint_to(&env, &token_address, &admin, &bob, 200_000);
     mint_to(&env, &token_address, &admin, &charlie, 100_000);
 
-    client.contribute(&alice, &300_000);
+    client.contribute(&alice, &300_000, &0, &None, &false, &None, &None, &None);
     assert_eq!(client.contributor_count(), 1);
 
-    client.contribute(&bob, &200_000);
+    client.contribute(&bob, &200_000, &0, &None, &false, &None, &None, &None);
     assert_eq!(client.contributor_count(), 2);
 
-    client.contribute(&charlie, &100_000);
+    client.contribute(&charlie, &100_000, &0, &None, &false, &None, &None, &None);
     assert_eq!(client.contributor_count(), 3);
 }
+
+// ── Idempotency Key Tests ───────────────────────────────────────────────────
+
+#[test]
+fn test_idempotency_key_credits_retried_contribution_once() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 30 * 24 * 60 * 60;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 500_000);
+    let key = soroban_sdk::BytesN::from_array(&env, &[7; 32]);
+    let some_key = Some(key.clone());
+    client.contribute(&alice, &200_000, &0, &None, &false, &None, &None, &some_key);
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 60);
+    let result =
+        client.try_contribute(&alice, &200_000, &0, &None, &false, &None, &None, &some_key);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::DuplicateIdempotencyKey
+    );
+    let result = client.try_contribute_for(&alice, &alice, &200_000, &None, &some_key);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::DuplicateIdempotencyKey
+    );
+    assert_eq!(client.idempotency_result(&key), Some(200_000));
+    assert_eq!(client.total_raised(), 200_000);
+    assert_eq!(client.contribution(&alice), 200_000);
+
+    // Still remembered inside the window, prunable after it.
+    let keys = soroban_sdk::vec![&env, key.clone()];
+    assert_eq!(client.prune_idempotency_keys(&keys), 0);
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + 7 * 24 * 60 * 60);
+    assert_eq!(client.idempotency_result(&key), None);
+    assert_eq!(client.prune_idempotency_keys(&keys), 1);
+}
+
+// ── Contribute-For (Gift) Tests ────────────────────────────────────────────
+
+#[test]
+fn test_contribute_for_credits_beneficiary() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 1_000_000;
+    let min_contribution: i128 = 1_000;
+
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &(goal * 2),
+        &deadline,
+        &min_contribution,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let payer = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &payer, 200_000);
+
+    client.contribute_for(&payer, &beneficiary, &200_000, &None, &None);
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&payer), 0);
+    assert_eq!(client.total_raised(), 200_000);
+    assert_eq!(client.contribution(&beneficiary), 200_000);
+    assert_eq!(client.contribution(&payer), 0);
+    assert_eq!(client.contributor_count(), 1);
+}
+
+#[test]
+fn test_contribute_for_beneficiary_qualifies_for_tier() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 1_000_000;
+    let min_contribution: i128 = 1_000;
+
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &(goal * 2),
+        &deadline,
+        &min_contribution,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let gold = soroban_sdk::String::from_str(&env, "Gold");
+    client.add_reward_tier(&creator, &gold, &100_000, &0);
+
+    let payer = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &payer, 100_000);
+
+    client.contribute_for(&payer, &beneficiary, &100_000, &None, &None);
+
+    assert_eq!(client.get_user_tier(&beneficiary), Some(gold));
+    assert_eq!(client.get_user_tier(&payer), None);
+}
+
+#[test]
+fn test_contribute_for_refund_goes_to_beneficiary() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 1_000_000;
+    let min_contribution: i128 = 1_000;
+
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &(goal * 2),
+        &deadline,
+        &min_contribution,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let payer = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &payer, 300_000);
+
+    client.contribute_for(&payer, &beneficiary, &300_000, &None, &None);
+
+    // Move past deadline — goal not met.
+    env.ledger().set_timestamp(deadline + 1);
+    client.refund();
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&beneficiary), 300_000);
+    assert_eq!(token_client.balance(&payer), 0);
+}
+
+#[test]
+fn test_contribute_for_rate_limit_keyed_on_payer() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 1_000_000;
+    let min_contribution: i128 = 1_000;
+
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &(goal * 2),
+        &deadline,
+        &min_contribution,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let payer = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &payer, 20_000);
+
+    client.contribute_for(&payer, &alice, &10_000, &None, &None);
+
+    // A second gift from the same payer within the cooldown is refused,
+    // even though the beneficiary differs.
+    assert_eq!(
+        client.contribute_for(&payer, &bob, &10_000, &None, &None),
+        0
+    );
+    assert_eq!(client.contribution(&bob), 0);
+    assert_eq!(client.rate_limit_hits_for(&payer), 1);
+}
+
+// ── Anonymous Contribution Tests ───────────────────────────────────────────
+
+#[test]
+fn test_anonymous_contribution_hidden_from_views() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 1_000_000;
+    let min_contribution: i128 = 1_000;
+
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &(goal * 2),
+        &deadline,
+        &min_contribution,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    mint_to(&env, &token_address, &admin, &bob, 50_000);
+
+    client.contribute(&alice, &100_000, &0, &None, &true, &None, &None, &None);
+    client.contribute(&bob, &50_000, &0, &None, &false, &None, &None, &None);
+
+    assert_eq!(
+        client.get_contributors(),
+        soroban_sdk::vec![&env, bob.clone()]
+    );
+    assert_eq!(client.contribution(&alice), 100_000);
+    assert_eq!(client.anonymous_total(), 100_000);
+    assert_eq!(client.total_raised(), 150_000);
+    assert_eq!(client.contributor_count(), 2);
+}
+
+#[test]
+fn test_anonymous_contribution_event_is_redacted() {
+    use soroban_sdk::{testutils::Events, IntoVal};
+
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 1_000_000;
+    let min_contribution: i128 = 1_000;
+
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &(goal * 2),
+        &deadline,
+        &min_contribution,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    client.contribute(&alice, &100_000, &0, &None, &true, &None, &None, &None);
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, campaign_topics(&env, "anon_contributed"));
+    let amount: i128 = data.into_val(&env);
+    assert_eq!(amount, 100_000);
+}
+
+#[test]
+fn test_reveal_contribution_restores_visibility() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 1_000_000;
+    let min_contribution: i128 = 1_000;
+
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &(goal * 2),
+        &deadline,
+        &min_contribution,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    client.contribute(&alice, &100_000, &0, &None, &true, &None, &None, &None);
+    assert_eq!(client.get_contributors().len(), 0);
+
+    client.reveal_contribution(&alice);
+
+    assert_eq!(
+        client.get_contributors(),
+        soroban_sdk::vec![&env, alice.clone()]
+    );
+    assert_eq!(client.anonymous_total(), 0);
+}
+
+#[test]
+fn test_anonymous_contribution_is_refunded() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 1_000_000;
+    let min_contribution: i128 = 1_000;
+
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &(goal * 2),
+        &deadline,
+        &min_contribution,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    client.contribute(&alice, &100_000, &0, &None, &true, &None, &None, &None);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.refund();
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&alice), 100_000);
+    assert_eq!(client.anonymous_total(), 0);
+}
+
+// ── Memo Tests ─────────────────────────────────────────────────────────────
+
+#[test]
+fn test_contribute_with_memo() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 1_000_000;
+    let min_contribution: i128 = 1_000;
+
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &(goal * 2),
+        &deadline,
+        &min_contribution,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 10_000);
+    let memo = soroban_sdk::String::from_str(&env, "good luck!");
+    client.contribute(
+        &alice,
+        &10_000,
+        &0,
+        &None,
+        &false,
+        &Some(memo.clone()),
+        &None,
+        &None,
+    );
+
+    assert_eq!(client.memo(&alice), Some(memo.clone()));
+    let recent = client.recent_memos(&0, &10);
+    assert_eq!(recent.len(), 1);
+    assert_eq!(recent.get(0).unwrap().contributor, Some(alice));
+    assert_eq!(recent.get(0).unwrap().memo, memo);
+}
+
+#[test]
+fn test_contribute_rejects_oversized_memo() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 1_000_000;
+    let min_contribution: i128 = 1_000;
+
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &(goal * 2),
+        &deadline,
+        &min_contribution,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 10_000);
+    let memo = soroban_sdk::String::from_str(&env, &"x".repeat(141));
+
+    let result = client.try_contribute(
+        &alice,
+        &10_000,
+        &0,
+        &None,
+        &false,
+        &Some(memo),
+        &None,
+        &None,
+    );
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::InvalidInput
+    );
+    assert_eq!(client.total_raised(), 0);
+}
+
+#[test]
+fn test_recent_memos_ring_buffer_evicts_oldest() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 1_000_000;
+    let min_contribution: i128 = 1_000;
+
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &(goal * 10),
+        &deadline,
+        &min_contribution,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let first = soroban_sdk::String::from_str(&env, "first");
+    let later = soroban_sdk::String::from_str(&env, "later");
+    for i in 0..51 {
+        let backer = Address::generate(&env);
+        mint_to(&env, &token_address, &admin, &backer, 1_000);
+        let memo = if i == 0 { first.clone() } else { later.clone() };
+        client.contribute(
+            &backer,
+            &1_000,
+            &0,
+            &None,
+            &false,
+            &Some(memo),
+            &None,
+            &None,
+        );
+    }
+
+    let all = client.recent_memos(&0, &100);
+    assert_eq!(all.len(), 50);
+    for entry in all.iter() {
+        assert_eq!(entry.memo, later);
+    }
+
+    // Pagination skips the newest entries.
+    assert_eq!(client.recent_memos(&45, &10).len(), 5);
+}
+
+#[test]
+fn test_creator_can_remove_memo() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 1_000_000;
+    let min_contribution: i128 = 1_000;
+
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &(goal * 2),
+        &deadline,
+        &min_contribution,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 10_000);
+    mint_to(&env, &token_address, &admin, &bob, 10_000);
+    let rude = soroban_sdk::String::from_str(&env, "rude");
+    let nice = soroban_sdk::String::from_str(&env, "nice");
+    client.contribute(
+        &alice,
+        &10_000,
+        &0,
+        &None,
+        &false,
+        &Some(rude),
+        &None,
+        &None,
+    );
+    client.contribute(
+        &bob,
+        &10_000,
+        &0,
+        &None,
+        &false,
+        &Some(nice.clone()),
+        &None,
+        &None,
+    );
+
+    client.remove_memo(&alice);
+
+    assert_eq!(client.memo(&alice), None);
+    let recent = client.recent_memos(&0, &10);
+    assert_eq!(recent.len(), 1);
+    assert_eq!(recent.get(0).unwrap().memo, nice);
+    // The contribution itself is untouched.
+    assert_eq!(client.contribution(&alice), 10_000);
+}
+
+#[test]
+fn test_forget_me_after_refund_scrubs_address() {
+    use soroban_sdk::xdr::ToXdr;
+
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 10_000);
+    mint_to(&env, &token_address, &admin, &bob, 20_000);
+    let memo = soroban_sdk::String::from_str(&env, "hello");
+    client.contribute(
+        &alice,
+        &10_000,
+        &0,
+        &None,
+        &false,
+        &Some(memo),
+        &None,
+        &None,
+    );
+    client.contribute(&bob, &20_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(
+        client.try_forget_me(&alice).unwrap_err().unwrap(),
+        crate::ContractError::CampaignStillActive
+    );
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.refund();
+    let total_raised = client.total_raised();
+    let stats = client.get_stats();
+
+    client.forget_me(&alice);
+    let hash: soroban_sdk::BytesN<32> = env.crypto().sha256(&alice.clone().to_xdr(&env)).into();
+    assert_eq!(
+        last_event::<soroban_sdk::BytesN<32>>(&env, "contributor_forgotten"),
+        hash
+    );
+
+    assert_eq!(
+        client.get_contributors(),
+        soroban_sdk::vec![&env, bob.clone()]
+    );
+    assert_eq!(client.memo(&alice), None);
+    assert_eq!(client.recent_memos(&0, &10).len(), 0);
+    let receipt = client.get_receipt(&0).unwrap();
+    assert_eq!(receipt.contributor, None);
+    assert_eq!(receipt.amount, 10_000);
+    assert_eq!(client.get_receipt(&1).unwrap().contributor, Some(bob));
+
+    // Aggregates are unchanged.
+    assert_eq!(client.total_raised(), total_raised);
+    assert_eq!(client.lifetime_contributor_count(), 2);
+    assert_eq!(
+        client.get_stats().contributor_count,
+        stats.contributor_count
+    );
+}
+
+// ── Token Sanity Check Tests ───────────────────────────────────────────────
+
+#[test]
+fn test_initialize_records_token_decimals() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 1_000_000;
+
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &(goal * 2),
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    // Stellar asset contracts always report 7 decimals.
+    assert_eq!(client.token_decimals(), 7);
+}
+
+#[test]
+fn test_initialize_rejects_non_token_address() {
+    let (env, client, creator, _token_address, _admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 1_000_000;
+    let bogus_token = Address::generate(&env);
+
+    let result = client.try_initialize(
+        &creator,
+        &bogus_token,
+        &goal,
+        &(goal * 2),
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::InvalidToken
+    );
+}
+
+#[test]
+fn test_set_token_before_first_contribution() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let new_token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    assert_eq!(
+        client
+            .try_set_token(&Address::generate(&env))
+            .unwrap_err()
+            .unwrap(),
+        crate::ContractError::InvalidToken
+    );
+    client.set_token(&new_token);
+    assert_eq!(
+        last_event::<crate::events::TokenChangedEvent>(&env, "token_changed"),
+        crate::events::TokenChangedEvent {
+            old_token: token_address.clone(),
+            new_token: new_token.clone(),
+        }
+    );
+    assert_eq!(client.token(), new_token);
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    mint_to(&env, &new_token, &admin, &alice, 100_000);
+    client.contribute(&alice, &100_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(
+        token::Client::new(&env, &new_token).balance(&client.address),
+        100_000
+    );
+    assert_eq!(
+        token::Client::new(&env, &token_address).balance(&alice),
+        100_000
+    );
+
+    // Once funds arrived the token is locked.
+    assert_eq!(
+        client.try_set_token(&token_address).unwrap_err().unwrap(),
+        crate::ContractError::CampaignHasFunds
+    );
+    assert_eq!(client.token(), new_token);
+}
+
+#[test]
+fn test_set_factory_only_once_before_funds() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    client.set_factory(&Address::generate(&env));
+    assert_eq!(
+        client
+            .try_set_factory(&Address::generate(&env))
+            .unwrap_err()
+            .unwrap(),
+        crate::ContractError::InvalidInput
+    );
+
+    // A campaign that already took money cannot be linked afterwards.
+    let (env, client, creator, token_address, admin) = setup_env();
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    client.contribute(&alice, &100_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(
+        client
+            .try_set_factory(&Address::generate(&env))
+            .unwrap_err()
+            .unwrap(),
+        crate::ContractError::CampaignHasFunds
+    );
+}
+
+// ── Withdrawal Co-signer Tests ─────────────────────────────────────────────
+
+/// Initialize a campaign (optionally with a withdrawal co-signer), fund it to
+/// the goal, and move past the deadline so it is ready for withdrawal.
+fn setup_funded_campaign(
+    with_cosigner: bool,
+) -> (Env, CrowdfundContractClient<'static>, Address, Address) {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let options = if with_cosigner {
+        Some(crate::CampaignOptions {
+            withdraw_cosigner: Some(Address::generate(&env)),
+            allow_dust_goal: true,
+            ..Default::default()
+        })
+    } else {
+        dust_goal_options()
+    };
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 1_000_000;
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &(goal * 2),
+        &deadline,
+        &1_000,
+        &None,
+        &options,
+        &None,
+    );
+
+    let contributor = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &contributor, goal);
+    client.contribute(&contributor, &goal, &0, &None, &false, &None, &None, &None);
+
+    env.ledger().set_timestamp(deadline + 1);
+    (env, client, creator, token_address)
+}
+
+#[test]
+fn test_withdraw_with_cosigner_rejects_creator_only() {
+    use soroban_sdk::testutils::{MockAuth, MockAuthInvoke};
+    use soroban_sdk::IntoVal;
+
+    let (env, client, creator, _token_address) = setup_funded_campaign(true);
+    let invoke = MockAuthInvoke {
+        contract: &client.address,
+        fn_name: "withdraw",
+        args: ().into_val(&env),
+        sub_invokes: &[],
+    };
+
+    env.set_auths(&[]);
+    let result = client
+        .mock_auths(&[MockAuth {
+            address: &creator,
+            invoke: &invoke,
+        }])
+        .try_withdraw();
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_withdraw_with_cosigner_succeeds_with_both_signatures() {
+    use soroban_sdk::testutils::{MockAuth, MockAuthInvoke};
+    use soroban_sdk::IntoVal;
+
+    let (env, client, creator, token_address) = setup_funded_campaign(true);
+    let cosigner = client.cosigner().unwrap();
+    let invoke = MockAuthInvoke {
+        contract: &client.address,
+        fn_name: "withdraw",
+        args: ().into_val(&env),
+        sub_invokes: &[],
+    };
+
+    env.set_auths(&[]);
+    client
+        .mock_auths(&[
+            MockAuth {
+                address: &creator,
+                invoke: &invoke,
+            },
+            MockAuth {
+                address: &cosigner,
+                invoke: &invoke,
+            },
+        ])
+        .withdraw();
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&creator), 10_000_000 + 1_000_000);
+}
+
+#[test]
+fn test_withdraw_without_cosigner_needs_only_creator() {
+    use soroban_sdk::testutils::{MockAuth, MockAuthInvoke};
+    use soroban_sdk::IntoVal;
+
+    let (env, client, creator, token_address) = setup_funded_campaign(false);
+    let invoke = MockAuthInvoke {
+        contract: &client.address,
+        fn_name: "withdraw",
+        args: ().into_val(&env),
+        sub_invokes: &[],
+    };
+
+    env.set_auths(&[]);
+    client
+        .mock_auths(&[MockAuth {
+            address: &creator,
+            invoke: &invoke,
+        }])
+        .withdraw();
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&creator), 10_000_000 + 1_000_000);
+}
+
+#[test]
+fn test_set_cosigner_before_contributions() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 1_000_000;
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &(goal * 2),
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+    assert_eq!(client.cosigner(), None);
+
+    let cosigner = Address::generate(&env);
+    client.set_cosigner(&Some(cosigner.clone()));
+
+    assert_eq!(client.cosigner(), Some(cosigner));
+}
+
+#[test]
+#[should_panic(expected = "cosigner can only be changed before any contribution")]
+fn test_set_cosigner_rejected_after_contribution() {
+    let (env, client, _creator, _token_address) = setup_funded_campaign(false);
+    client.set_cosigner(&Some(Address::generate(&env)));
+}
+
+// ── Withdrawal Approval Vote Tests ─────────────────────────────────────────
+
+/// Initialize a campaign gated by a 60% approval vote with a one-day voting
+/// window, fund it with three contributors (40/30/30 of the goal), and move
+/// past the deadline.
+fn setup_approval_campaign() -> (
+    Env,
+    CrowdfundContractClient<'static>,
+    Address,
+    Address,
+    [Address; 3],
+) {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let options = crate::CampaignOptions {
+        approval_required_bps: 6_000,
+        allow_dust_goal: true,
+        approval_window_secs: 86_400,
+        ..Default::default()
+    };
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 1_000_000;
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &(goal * 2),
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    for (backer, amount) in [(&alice, 400_000), (&bob, 300_000), (&carol, 300_000)] {
+        mint_to(&env, &token_address, &admin, backer, amount);
+        client.contribute(backer, &amount, &0, &None, &false, &None, &None, &None);
+    }
+
+    env.ledger().set_timestamp(deadline + 1);
+    (env, client, creator, token_address, [alice, bob, carol])
+}
+
+#[test]
+fn test_withdraw_enabled_once_approval_threshold_reached() {
+    let (env, client, creator, token_address, [alice, bob, _carol]) = setup_approval_campaign();
+
+    client.approve_withdrawal(&alice);
+    assert_eq!(client.approval_progress_bps(), 4_000);
+    client.approve_withdrawal(&bob);
+    assert_eq!(client.approval_progress_bps(), 7_000);
+
+    client.withdraw();
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&creator), 10_000_000 + 1_000_000);
+}
+
+#[test]
+fn test_withdraw_blocked_below_approval_threshold() {
+    let (_env, client, _creator, _token_address, [alice, _bob, _carol]) = setup_approval_campaign();
+
+    client.approve_withdrawal(&alice);
+
+    let result = client.try_withdraw();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::WithdrawalNotApproved
+    );
+}
+
+#[test]
+fn test_revoke_approval_drops_below_threshold() {
+    let (_env, client, _creator, _token_address, [alice, bob, _carol]) = setup_approval_campaign();
+
+    client.approve_withdrawal(&alice);
+    client.approve_withdrawal(&bob);
+    client.revoke_approval(&bob);
+
+    assert_eq!(client.approval_progress_bps(), 4_000);
+    let result = client.try_withdraw();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::WithdrawalNotApproved
+    );
+}
+
+#[test]
+fn test_claim_refund_after_failed_approval_vote() {
+    let (env, client, _creator, token_address, [alice, bob, _carol]) = setup_approval_campaign();
+
+    client.approve_withdrawal(&alice);
+
+    // Refunds stay closed while the vote can still pass.
+    let result = client.try_claim_refund(&bob);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::GoalReached
+    );
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + 86_400);
+    client.claim_refund(&bob);
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&bob), 300_000);
+    assert_eq!(client.contribution(&bob), 0);
+    assert_eq!(client.total_raised(), 700_000);
+
+    // The campaign is now in refund mode, so withdrawal stays blocked.
+    let result = client.try_withdraw();
+    assert!(result.is_err());
+}
+
+#[test]
+#[should_panic(expected = "approval window has closed")]
+fn test_approve_withdrawal_after_window_panics() {
+    let (env, client, _creator, _token_address, [alice, _bob, _carol]) = setup_approval_campaign();
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + 86_400);
+    client.approve_withdrawal(&alice);
+}
+
+// ── Dispute Window Tests ───────────────────────────────────────────────────
+
+/// Initialize a campaign with a one-day withdrawal timelock, a 50% dispute
+/// threshold and an admin, fund it with two contributors (40/60 of the goal),
+/// and move just past the deadline into the dispute window.
+fn setup_dispute_campaign() -> (
+    Env,
+    CrowdfundContractClient<'static>,
+    Address,
+    Address,
+    [Address; 2],
+) {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let options = crate::CampaignOptions {
+        admin: Some(Address::generate(&env)),
+        allow_dust_goal: true,
+        withdraw_delay_secs: 86_400,
+        dispute_threshold_bps: 5_000,
+        ..Default::default()
+    };
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 1_000_000;
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &(goal * 2),
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    for (backer, amount) in [(&alice, 400_000), (&bob, 600_000)] {
+        mint_to(&env, &token_address, &admin, backer, amount);
+        client.contribute(backer, &amount, &0, &None, &false, &None, &None, &None);
+    }
+
+    env.ledger().set_timestamp(deadline + 1);
+    (env, client, creator, token_address, [alice, bob])
+}
+
+#[test]
+fn test_withdraw_rejected_during_timelock() {
+    let (_env, client, _creator, _token_address, _backers) = setup_dispute_campaign();
+
+    let result = client.try_withdraw();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::WithdrawTimelocked
+    );
+}
+
+#[test]
+fn test_dispute_below_threshold_does_not_block_withdraw() {
+    let (env, client, creator, token_address, [alice, _bob]) = setup_dispute_campaign();
+
+    client.raise_dispute(&alice, &soroban_sdk::BytesN::from_array(&env, &[1; 32]));
+    assert!(!client.is_disputed());
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + 86_400);
+    client.withdraw();
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&creator), 10_000_000 + 1_000_000);
+}
+
+#[test]
+fn test_dispute_above_threshold_freezes_withdraw() {
+    let (env, client, _creator, _token_address, [_alice, bob]) = setup_dispute_campaign();
+
+    client.raise_dispute(&bob, &soroban_sdk::BytesN::from_array(&env, &[2; 32]));
+    assert!(client.is_disputed());
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + 86_400);
+    let result = client.try_withdraw();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::WithdrawalDisputed
+    );
+
+    client.resolve_dispute(&true);
+    client.withdraw();
+}
+
+#[test]
+fn test_resolve_dispute_against_creator_enables_refunds() {
+    let (env, client, _creator, token_address, [alice, bob]) = setup_dispute_campaign();
+
+    client.raise_dispute(&bob, &soroban_sdk::BytesN::from_array(&env, &[3; 32]));
+    client.resolve_dispute(&false);
+
+    client.claim_refund(&alice);
+    client.claim_refund(&bob);
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&alice), 400_000);
+    assert_eq!(token_client.balance(&bob), 600_000);
+    assert_eq!(client.total_raised(), 0);
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + 86_400);
+    assert!(client.try_withdraw().is_err());
+}
+
+// ── Vesting Tests ───────────────────────────────────────────────────────────
+
+/// Initialize a campaign vesting linearly over 1000 seconds with a
+/// 200-second cliff, fully fund it, and return the deadline.
+fn setup_vesting_campaign() -> (Env, CrowdfundContractClient<'static>, Address, Address, u64) {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let options = crate::CampaignOptions {
+        vest_duration_secs: 1_000,
+        allow_dust_goal: true,
+        cliff_secs: 200,
+        ..Default::default()
+    };
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 1_000_000;
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &goal,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+
+    let contributor = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &contributor, goal);
+    client.contribute(&contributor, &goal, &0, &None, &false, &None, &None, &None);
+
+    (env, client, creator, token_address, deadline)
+}
+
+#[test]
+fn test_withdraw_vested_before_cliff_returns_nothing_vested() {
+    let (env, client, _creator, _token_address, deadline) = setup_vesting_campaign();
+
+    env.ledger().set_timestamp(deadline + 100);
+    let result = client.try_withdraw_vested();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::NothingVested
+    );
+    assert_eq!(client.vested_amount(), 0);
+}
+
+#[test]
+fn test_withdraw_vested_cumulative_payouts() {
+    let (env, client, creator, token_address, deadline) = setup_vesting_campaign();
+    let token_client = token::Client::new(&env, &token_address);
+    let initial = token_client.balance(&creator);
+
+    env.ledger().set_timestamp(deadline + 200);
+    client.withdraw_vested();
+    assert_eq!(client.withdrawn_amount(), 200_000);
+    assert_eq!(token_client.balance(&creator), initial + 200_000);
+
+    // Nothing new has vested within the same ledger timestamp.
+    let result = client.try_withdraw_vested();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::NothingVested
+    );
+
+    env.ledger().set_timestamp(deadline + 500);
+    assert_eq!(client.vested_amount(), 500_000);
+    client.withdraw_vested();
+    assert_eq!(token_client.balance(&creator), initial + 500_000);
+
+    env.ledger().set_timestamp(deadline + 5_000);
+    client.withdraw_vested();
+    assert_eq!(client.withdrawn_amount(), 1_000_000);
+    assert_eq!(token_client.balance(&creator), initial + 1_000_000);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+#[test]
+fn test_withdraw_vested_deducts_proportional_fee() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let platform = Address::generate(&env);
+    let config = crate::PlatformConfig {
+        address: platform.clone(),
+        fee_bps: 500,
+        fee_mode: crate::FeeMode::OnWithdraw,
+    };
+    let options = crate::CampaignOptions {
+        vest_duration_secs: 1_000,
+        allow_dust_goal: true,
+        ..Default::default()
+    };
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 1_000_000;
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &goal,
+        &deadline,
+        &1_000,
+        &Some(config),
+        &Some(options),
+        &None,
+    );
+
+    let contributor = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &contributor, goal);
+    client.contribute(&contributor, &goal, &0, &None, &false, &None, &None, &None);
+
+    let token_client = token::Client::new(&env, &token_address);
+    let initial = token_client.balance(&creator);
+
+    env.ledger().set_timestamp(deadline + 500);
+    client.withdraw_vested();
+
+    assert_eq!(token_client.balance(&platform), 25_000);
+    assert_eq!(token_client.balance(&creator), initial + 475_000);
+
+    let receipts = client.vested_receipts();
+    assert_eq!(receipts.len(), 1);
+    let receipt = receipts.get(0).unwrap();
+    assert_eq!(receipt.gross, 500_000);
+    assert_eq!(receipt.platform_fee, 25_000);
+    assert_eq!(receipt.net_to_creator, 475_000);
+    assert_eq!(client.withdrawal_receipt(), None);
+}
+
+#[test]
+fn test_withdraw_stores_receipt_matching_payouts() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let platform = Address::generate(&env);
+    let config = crate::PlatformConfig {
+        address: platform.clone(),
+        fee_bps: 500,
+        fee_mode: crate::FeeMode::OnWithdraw,
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &Some(config),
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 1_200_000);
+    client.contribute(&alice, &1_200_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(client.withdrawal_receipt(), None);
+
+    let token_client = token::Client::new(&env, &token_address);
+    let creator_before = token_client.balance(&creator);
+    env.ledger().set_timestamp(deadline + 1);
+    client.withdraw();
+
+    let receipt = client.withdrawal_receipt().unwrap();
+    assert_eq!(
+        receipt,
+        crate::WithdrawalReceipt {
+            timestamp: deadline + 1,
+            gross: 1_200_000,
+            platform_fee: token_client.balance(&platform),
+            referral_rewards: 0,
+            escrow_retained: 0,
+            net_to_creator: token_client.balance(&creator) - creator_before,
+            payout_address: creator.clone(),
+        }
+    );
+    assert_eq!(receipt.platform_fee, 60_000);
+    assert_eq!(receipt.net_to_creator, 1_140_000);
+}
+
+#[test]
+#[should_panic(expected = "campaign pays out via withdraw_vested")]
+fn test_instant_withdraw_rejected_for_vesting_campaign() {
+    let (env, client, _creator, _token_address, deadline) = setup_vesting_campaign();
+
+    env.ledger().set_timestamp(deadline + 5_000);
+    client.withdraw();
+}
+
+// ── Goal Unit Validation Tests ─────────────────────────────────────────────
+
+#[test]
+fn test_initialize_rejects_goal_below_one_token() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 100;
+
+    let result = client.try_initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &goal,
+        &deadline,
+        &1,
+        &None,
+        &None,
+        &None,
+    );
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::SuspiciouslySmallGoal
+    );
+}
+
+#[test]
+fn test_initialize_accepts_goal_of_one_token() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 10_000_000;
+
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &goal,
+        &deadline,
+        &1,
+        &None,
+        &None,
+        &None,
+    );
+
+    assert_eq!(client.goal(), goal);
+}
+
+#[test]
+fn test_initialize_accepts_dust_goal_when_allowed() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 100;
+
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &goal,
+        &deadline,
+        &1,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    assert_eq!(client.goal(), goal);
+}
+
+#[test]
+fn test_display_views_split_whole_and_fraction() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 25_000_000;
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &goal,
+        &deadline,
+        &1,
+        &None,
+        &None,
+        &None,
+    );
+
+    let contributor = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &contributor, 12_345_678);
+    client.contribute(
+        &contributor,
+        &12_345_678,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
+
+    assert_eq!(client.goal_display(), (2, 5_000_000, 7));
+    assert_eq!(client.total_raised_display(), (1, 2_345_678, 7));
+}
+
+// ── Scheduled Start Tests ───────────────────────────────────────────────────
+
+/// Initialize a campaign that opens 1000 seconds from now.
+fn setup_scheduled_campaign() -> (Env, CrowdfundContractClient<'static>, Address, Address, u64) {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let start_time = env.ledger().timestamp() + 1_000;
+    let options = crate::CampaignOptions {
+        allow_dust_goal: true,
+        start_time,
+        ..Default::default()
+    };
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let goal: i128 = 1_000_000;
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &goal,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+
+    let contributor = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &contributor, goal);
+    (env, client, creator, contributor, start_time)
+}
+
+#[test]
+fn test_contribute_before_start_time_rejected() {
+    let (env, client, _creator, contributor, start_time) = setup_scheduled_campaign();
+
+    env.ledger().set_timestamp(start_time - 1);
+    let result = client.try_contribute(
+        &contributor,
+        &10_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::CampaignNotStarted
+    );
+
+    let result = client.try_pledge(&contributor, &10_000);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::CampaignNotStarted
+    );
+}
+
+#[test]
+fn test_contribute_at_start_time_accepted() {
+    let (env, client, _creator, contributor, start_time) = setup_scheduled_campaign();
+
+    env.ledger().set_timestamp(start_time);
+    client.contribute(
+        &contributor,
+        &10_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
+
+    assert_eq!(client.total_raised(), 10_000);
+    assert_eq!(client.campaign_info().start_time, start_time);
+}
+
+#[test]
+#[should_panic(expected = "start time must be before the deadline")]
+fn test_initialize_rejects_start_time_at_deadline() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    let options = crate::CampaignOptions {
+        allow_dust_goal: true,
+        start_time: deadline,
+        ..Default::default()
+    };
+
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &1_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+}
+
+#[test]
+fn test_update_start_time_moves_earlier() {
+    let (env, client, _creator, contributor, start_time) = setup_scheduled_campaign();
+
+    client.update_start_time(&(start_time - 500));
+    assert_eq!(client.start_time(), start_time - 500);
+
+    env.ledger().set_timestamp(start_time - 500);
+    client.contribute(
+        &contributor,
+        &10_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "start time can only be moved earlier")]
+fn test_update_start_time_rejects_later() {
+    let (_env, client, _creator, _contributor, start_time) = setup_scheduled_campaign();
+
+    client.update_start_time(&(start_time + 1));
+}
+
+// ── Rate Limit Exemption Tests ─────────────────────────────────────────────
+
+#[test]
+fn test_rate_limit_exempt_address_contributes_twice_in_same_second() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let onramp = Address::generate(&env);
+    let user = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &onramp, 20_000);
+    mint_to(&env, &token_address, &admin, &user, 20_000);
+
+    client.set_rate_limit_exempt(&onramp, &true);
+    assert!(client.is_rate_limit_exempt(&onramp));
+
+    client.contribute(&onramp, &10_000, &0, &None, &false, &None, &None, &None);
+    client.contribute(&onramp, &10_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(client.contribution(&onramp), 20_000);
+
+    client.contribute(&user, &10_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(
+        client.contribute(&user, &10_000, &0, &None, &false, &None, &None, &None),
+        0
+    );
+    assert_eq!(client.contribution(&user), 10_000);
+    assert_eq!(client.rate_limit_hits_for(&onramp), 0);
+}
+
+#[test]
+fn test_rate_limit_exemption_can_be_revoked() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let onramp = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &onramp, 20_000);
+
+    client.set_rate_limit_exempt(&onramp, &true);
+    client.set_rate_limit_exempt(&onramp, &false);
+    assert!(!client.is_rate_limit_exempt(&onramp));
+
+    client.contribute(&onramp, &10_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(
+        client.contribute(&onramp, &10_000, &0, &None, &false, &None, &None, &None),
+        0
+    );
+    assert_eq!(client.contribution(&onramp), 10_000);
+}
+
+#[test]
+fn test_creator_gifting_via_contribute_for_is_exempt() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    client.contribute_for(&creator, &alice, &10_000, &None, &None);
+    client.contribute_for(&creator, &bob, &10_000, &None, &None);
+
+    assert_eq!(client.contribution(&alice), 10_000);
+    assert_eq!(client.contribution(&bob), 10_000);
+}
+
+// ── Hard Cap Top-Up Tests ───────────────────────────────────────────────────
+
+/// Initialize a campaign with a 1_000_000 hard cap and a 10_000 minimum,
+/// and fill it to 995_000.
+fn setup_nearly_capped_campaign() -> (Env, CrowdfundContractClient<'static>, Address, Address) {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &500_000,
+        &1_000_000,
+        &deadline,
+        &10_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let whale = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &whale, 995_000);
+    client.contribute(&whale, &995_000, &0, &None, &false, &None, &None, &None);
+
+    let contributor = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &contributor, 20_000);
+    (env, client, token_address, contributor)
+}
+
+#[test]
+fn test_dust_top_up_closes_hard_cap() {
+    let (_env, client, _token_address, contributor) = setup_nearly_capped_campaign();
+
+    let accepted = client.contribute(&contributor, &5_000, &0, &None, &false, &None, &None, &None);
+
+    assert_eq!(accepted, 5_000);
+    assert_eq!(client.total_raised(), 1_000_000);
+}
+
+#[test]
+fn test_oversized_top_up_is_truncated_to_headroom() {
+    let (env, client, token_address, contributor) = setup_nearly_capped_campaign();
+
+    let accepted = client.contribute(
+        &contributor,
+        &20_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
+
+    assert_eq!(accepted, 5_000);
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&contributor), 15_000);
+}
+
+#[test]
+#[should_panic(expected = "amount below minimum")]
+fn test_top_up_below_headroom_still_rejected() {
+    let (_env, client, _token_address, contributor) = setup_nearly_capped_campaign();
+
+    client.contribute(&contributor, &4_999, &0, &None, &false, &None, &None, &None);
+}
+
+// ── Referral Protection Tests ───────────────────────────────────────────────
+
+/// Initialize a campaign with the given referral options and a platform fee
+/// address, returning the platform address alongside the usual handles.
+fn setup_referral_campaign(
+    options: crate::CampaignOptions,
+) -> (
+    Env,
+    CrowdfundContractClient<'static>,
+    Address,
+    Address,
+    Address,
+    Address,
+) {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let platform = Address::generate(&env);
+    let config = crate::PlatformConfig {
+        address: platform.clone(),
+        fee_bps: 100,
+        fee_mode: crate::FeeMode::OnWithdraw,
+    };
+    let options = crate::CampaignOptions {
+        allow_dust_goal: true,
+        ..options
+    };
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &Some(config),
+        &Some(options),
+        &None,
+    );
+
+    (env, client, creator, token_address, admin, platform)
+}
+
+/// Assert that the most recent event is `referral_ignored` with `code`.
+fn assert_referral_ignored(env: &Env, referrer: &Address, code: u32) {
+    use soroban_sdk::{testutils::Events, FromVal};
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, campaign_topics(env, "referral_ignored"));
+    let event = crate::events::ReferralIgnoredEvent::from_val(env, &data);
+    assert_eq!(&event.referrer, referrer);
+    assert_eq!(event.reason, code);
+}
+
+#[test]
+fn test_referral_below_min_amount_ignored() {
+    let (env, client, _creator, token_address, admin, _platform) =
+        setup_referral_campaign(crate::CampaignOptions {
+            min_referral_amount: 50_000,
+            ..Default::default()
+        });
+
+    let alice = Address::generate(&env);
+    let referrer = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 10_000);
+    client.contribute(
+        &alice,
+        &10_000,
+        &0,
+        &Some(referrer.clone()),
+        &false,
+        &None,
+        &None,
+        &None,
+    );
+
+    assert_referral_ignored(&env, &referrer, 3);
+    assert_eq!(client.referral_tally(&referrer), 0);
+}
+
+#[test]
+fn test_referral_by_creator_or_platform_ignored() {
+    let (env, client, creator, token_address, admin, platform) =
+        setup_referral_campaign(crate::CampaignOptions::default());
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 20_000);
+
+    client.contribute(
+        &alice,
+        &10_000,
+        &0,
+        &Some(creator.clone()),
+        &false,
+        &None,
+        &None,
+        &None,
+    );
+    assert_referral_ignored(&env, &creator, 2);
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+    client.contribute(
+        &alice,
+        &10_000,
+        &0,
+        &Some(platform.clone()),
+        &false,
+        &None,
+        &None,
+        &None,
+    );
+    assert_referral_ignored(&env, &platform, 2);
+
+    assert_eq!(client.referral_tally(&creator), 0);
+    assert_eq!(client.referral_tally(&platform), 0);
+}
+
+#[test]
+fn test_referral_requires_referrer_contribution_when_configured() {
+    let (env, client, _creator, token_address, admin, _platform) =
+        setup_referral_campaign(crate::CampaignOptions {
+            require_referrer_contributed: true,
+            ..Default::default()
+        });
+
+    let alice = Address::generate(&env);
+    let referrer = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 20_000);
+    mint_to(&env, &token_address, &admin, &referrer, 1_000);
+
+    client.contribute(
+        &alice,
+        &10_000,
+        &0,
+        &Some(referrer.clone()),
+        &false,
+        &None,
+        &None,
+        &None,
+    );
+    assert_referral_ignored(&env, &referrer, 4);
+    assert_eq!(client.referral_tally(&referrer), 0);
+
+    client.contribute(&referrer, &1_000, &0, &None, &false, &None, &None, &None);
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+    client.contribute(
+        &alice,
+        &10_000,
+        &0,
+        &Some(referrer.clone()),
+        &false,
+        &None,
+        &None,
+        &None,
+    );
+    assert_eq!(client.referral_tally(&referrer), 10_000);
+}
+
+#[test]
+fn test_self_referral_ignored() {
+    let (env, client, _creator, token_address, admin, _platform) =
+        setup_referral_campaign(crate::CampaignOptions::default());
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 10_000);
+    client.contribute(
+        &alice,
+        &10_000,
+        &0,
+        &Some(alice.clone()),
+        &false,
+        &None,
+        &None,
+        &None,
+    );
+
+    assert_referral_ignored(&env, &alice, 1);
+    assert_eq!(client.referral_tally(&alice), 0);
+}
+
+#[test]
+fn test_referral_tally_capped_at_share_of_total_raised() {
+    let (env, client, _creator, token_address, admin, _platform) =
+        setup_referral_campaign(crate::CampaignOptions {
+            max_referral_share_bps: 2_500,
+            ..Default::default()
+        });
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let referrer = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    mint_to(&env, &token_address, &admin, &bob, 300_000);
+
+    client.contribute(&bob, &300_000, &0, &None, &false, &None, &None, &None);
+    // Total becomes 400_000, so the referrer may hold at most 100_000.
+    client.contribute(
+        &alice,
+        &100_000,
+        &0,
+        &Some(referrer.clone()),
+        &false,
+        &None,
+        &None,
+        &None,
+    );
+    assert_eq!(client.referral_tally(&referrer), 100_000);
+
+    let carol = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &carol, 100_000);
+    // Total becomes 500_000 (cap 125_000), so only 25_000 more is credited.
+    client.contribute(
+        &carol,
+        &100_000,
+        &0,
+        &Some(referrer.clone()),
+        &false,
+        &None,
+        &None,
+        &None,
+    );
+    assert_eq!(client.referral_tally(&referrer), 125_000);
+}
+
+// ── Metadata Size Limit Tests ──────────────────────────────────────────────
+
+fn setup_metadata_campaign() -> (Env, CrowdfundContractClient<'static>, Address) {
+    let (env, client, creator, token_address, _admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    (env, client, creator)
+}
+
+/// A String of `len` ASCII bytes.
+fn string_of_len(env: &Env, len: usize) -> soroban_sdk::String {
+    let buf = [b'a'; 2_048];
+    soroban_sdk::String::from_bytes(env, &buf[..len])
+}
+
+fn social_link(env: &Env, platform: &str, url: soroban_sdk::String) -> crate::SocialLink {
+    crate::SocialLink {
+        platform: soroban_sdk::Symbol::new(env, platform),
+        url,
+    }
+}
+
+#[test]
+fn test_update_metadata_rejects_oversized_title_and_description() {
+    let (env, client, creator) = setup_metadata_campaign();
+
+    let result =
+        client.try_update_metadata(&creator, &Some(string_of_len(&env, 101)), &None, &None);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::MetadataTooLarge
+    );
+
+    let result =
+        client.try_update_metadata(&creator, &None, &Some(string_of_len(&env, 2_001)), &None);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::MetadataTooLarge
+    );
+
+    client.update_metadata(
+        &creator,
+        &Some(string_of_len(&env, 100)),
+        &Some(string_of_len(&env, 2_000)),
+        &None,
+    );
+    assert_eq!(client.title().len(), 100);
+}
+
+#[test]
+fn test_update_metadata_rejects_oversized_socials() {
+    let (env, client, creator) = setup_metadata_campaign();
+
+    // A single URL over the per-link limit.
+    let links = soroban_sdk::vec![&env, social_link(&env, "web", string_of_len(&env, 201))];
+    let result = client.try_update_metadata(&creator, &None, &None, &Some(links));
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::MetadataTooLarge
+    );
+
+    // Three URLs within the per-link limit but over the combined limit.
+    let mut links = soroban_sdk::Vec::new(&env);
+    for _ in 0..3 {
+        links.push_back(social_link(&env, "web", string_of_len(&env, 200)));
+    }
+    let result = client.try_update_metadata(&creator, &None, &None, &Some(links));
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::MetadataTooLarge
+    );
+
+    // More than ten links.
+    let mut links = soroban_sdk::Vec::new(&env);
+    for _ in 0..11 {
+        links.push_back(social_link(&env, "web", string_of_len(&env, 10)));
+    }
+    let result = client.try_update_metadata(&creator, &None, &None, &Some(links));
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::MetadataTooLarge
+    );
+}
+
+#[test]
+fn test_social_links_round_trip() {
+    let (env, client, creator) = setup_metadata_campaign();
+
+    let links = soroban_sdk::vec![
+        &env,
+        social_link(
+            &env,
+            "twitter",
+            soroban_sdk::String::from_str(&env, "https://x.com/raise")
+        ),
+        social_link(
+            &env,
+            "github",
+            soroban_sdk::String::from_str(&env, "https://github.com/raise")
+        ),
+    ];
+    client.update_metadata(&creator, &None, &None, &Some(links.clone()));
+
+    let stored = client.social_links();
+    assert_eq!(stored.len(), 2);
+    assert_eq!(
+        stored.get(1).unwrap().platform,
+        soroban_sdk::Symbol::new(&env, "github")
+    );
+    assert_eq!(stored.get(1).unwrap().url, links.get(1).unwrap().url);
+    assert_eq!(
+        client.socials(),
+        soroban_sdk::String::from_str(&env, "https://x.com/raise https://github.com/raise")
+    );
+}
+
+#[test]
+fn test_tags_are_normalized_and_matched_case_insensitively() {
+    let (env, client, _creator) = setup_metadata_campaign();
+    let s = |text: &str| soroban_sdk::String::from_str(&env, text);
+
+    client.update_category(
+        &s("finance"),
+        &soroban_sdk::vec![&env, s("DeFi"), s("Stellar")],
+    );
+    assert_eq!(
+        client.tags(),
+        soroban_sdk::vec![&env, s("defi"), s("stellar")]
+    );
+    assert!(client.has_tag(&s("defi")));
+    assert!(client.has_tag(&s("DEFI")));
+    assert!(!client.has_tag(&s("nft")));
+}
+
+#[test]
+fn test_update_category_rejects_invalid_tags() {
+    let (env, client, _creator) = setup_metadata_campaign();
+    let s = |text: &str| soroban_sdk::String::from_str(&env, text);
+    let category = s("finance");
+
+    for tags in [
+        soroban_sdk::vec![&env, s("DeFi"), s("defi")],
+        soroban_sdk::vec![&env, string_of_len(&env, 33)],
+        soroban_sdk::vec![&env, s("de fi")],
+    ] {
+        let result = client.try_update_category(&category, &tags);
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            crate::ContractError::InvalidInput
+        );
+    }
+    client.update_category(&category, &soroban_sdk::vec![&env, string_of_len(&env, 32)]);
+
+    let mut too_many = soroban_sdk::Vec::new(&env);
+    for i in 0..11u8 {
+        too_many.push_back(soroban_sdk::String::from_bytes(&env, &[b'a' + i]));
+    }
+    let result = client.try_update_category(&category, &too_many);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::MetadataTooLarge
+    );
+}
+
+// ── Cooldown View Tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_cooldown_views_track_enforcement() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 20_000);
+    assert_eq!(client.last_contribution_time(&alice), 0);
+    assert_eq!(client.cooldown_remaining(&alice), 0);
+
+    env.ledger().set_timestamp(1_000);
+    client.contribute(&alice, &10_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(client.last_contribution_time(&alice), 1_000);
+    assert_eq!(client.cooldown_remaining(&alice), 5);
+
+    env.ledger().set_timestamp(1_003);
+    assert_eq!(client.cooldown_remaining(&alice), 2);
+    assert_eq!(
+        client.contribute(&alice, &10_000, &0, &None, &false, &None, &None, &None),
+        0
+    );
+    assert_eq!(client.contribution(&alice), 10_000);
+
+    env.ledger().set_timestamp(1_005);
+    assert_eq!(client.cooldown_remaining(&alice), 0);
+    client.contribute(&alice, &10_000, &0, &None, &false, &None, &None, &None);
+}
+
+#[test]
+fn test_rate_limit_hits_counted_once_per_cooldown() {
+    use soroban_sdk::testutils::Events;
+
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    env.ledger().set_timestamp(1_000);
+    client.contribute(&alice, &10_000, &0, &None, &false, &None, &None, &None);
+
+    env.ledger().set_timestamp(1_001);
+    client.contribute(&alice, &10_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(
+        last_event::<crate::events::RateLimitedEvent>(&env, "rate_limited"),
+        crate::events::RateLimitedEvent {
+            addr: alice.clone(),
+            remaining_secs: 4,
+        }
+    );
+
+    // A second bounce within the same cooldown is neither counted nor
+    // announced.
+    env.ledger().set_timestamp(1_002);
+    client.contribute(&alice, &10_000, &0, &None, &false, &None, &None, &None);
+    let rate_limited = campaign_topics(&env, "rate_limited");
+    assert!(!env
+        .events()
+        .all()
+        .iter()
+        .any(|(_, topics, _)| topics == rate_limited));
+    assert_eq!(client.rate_limit_hits_for(&alice), 1);
+    assert_eq!(client.rate_limit_hits(), 1);
+    assert_eq!(client.contribution(&alice), 10_000);
+
+    // The next cooldown counts again.
+    env.ledger().set_timestamp(1_005);
+    client.contribute(&alice, &10_000, &0, &None, &false, &None, &None, &None);
+    env.ledger().set_timestamp(1_006);
+    client.contribute(&alice, &10_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(client.rate_limit_hits_for(&alice), 2);
+    assert_eq!(client.rate_limit_hits(), 2);
+    assert_eq!(client.contribution(&alice), 20_000);
+}
+
+#[test]
+fn test_cooldown_remaining_is_zero_for_exempt_address() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let onramp = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &onramp, 10_000);
+    client.set_rate_limit_exempt(&onramp, &true);
+    client.contribute(&onramp, &10_000, &0, &None, &false, &None, &None, &None);
+
+    assert_eq!(client.cooldown_remaining(&onramp), 0);
+}
+
+// ── Batch Refund & Keeper Bounty Tests ─────────────────────────────────────
+
+/// Initialize a failed campaign with a 50 bps keeper bounty and three
+/// contributors of 100_000, 200_000 and 300_000, past its deadline.
+fn setup_failed_campaign_with_bounty() -> (
+    Env,
+    CrowdfundContractClient<'static>,
+    Address,
+    Address,
+    [Address; 3],
+) {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let options = crate::CampaignOptions {
+        allow_dust_goal: true,
+        keeper_bounty_bps: 50,
+        ..Default::default()
+    };
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    for (backer, amount) in [(&alice, 100_000), (&bob, 200_000), (&carol, 300_000)] {
+        mint_to(&env, &token_address, &admin, backer, amount);
+        client.contribute(backer, &amount, &0, &None, &false, &None, &None, &None);
+    }
+
+    env.ledger().set_timestamp(deadline + 1);
+    (env, client, creator, token_address, [alice, bob, carol])
+}
+
+#[test]
+fn test_refund_batch_pays_keeper_bounty_pro_rata() {
+    let (env, client, _creator, token_address, [alice, bob, carol]) =
+        setup_failed_campaign_with_bounty();
+    let token_client = token::Client::new(&env, &token_address);
+    let keeper = Address::generate(&env);
+
+    assert_eq!(client.refund_batch(&keeper, &2), 2);
+    assert_eq!(token_client.balance(&alice), 99_500);
+    assert_eq!(token_client.balance(&bob), 199_000);
+    assert_eq!(token_client.balance(&keeper), 1_500);
+    assert_eq!(client.total_raised(), 300_000);
+
+    assert_eq!(client.refund_batch(&keeper, &2), 1);
+    assert_eq!(token_client.balance(&carol), 298_500);
+    assert_eq!(token_client.balance(&keeper), 3_000);
+    assert_eq!(client.total_raised(), 0);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+#[test]
+fn test_refund_batch_by_creator_pays_no_bounty() {
+    let (env, client, creator, token_address, [alice, _bob, _carol]) =
+        setup_failed_campaign_with_bounty();
+    let token_client = token::Client::new(&env, &token_address);
+    let creator_balance = token_client.balance(&creator);
+
+    client.refund_batch(&creator, &3);
+
+    assert_eq!(token_client.balance(&alice), 100_000);
+    assert_eq!(token_client.balance(&creator), creator_balance);
+}
+
+#[test]
+fn test_claim_refund_pays_no_bounty() {
+    let (env, client, _creator, token_address, [alice, bob, _carol]) =
+        setup_failed_campaign_with_bounty();
+    let token_client = token::Client::new(&env, &token_address);
+    let keeper = Address::generate(&env);
+
+    client.claim_refund(&alice);
+    assert_eq!(token_client.balance(&alice), 100_000);
+
+    // The batch skips the already-claimed contributor and charges the rest.
+    client.refund_batch(&keeper, &3);
+    assert_eq!(token_client.balance(&bob), 199_000);
+    assert_eq!(token_client.balance(&keeper), 2_500);
+}
+
+#[test]
+fn test_refund_batch_rejects_zero_limit() {
+    let (env, client, _creator, _token_address, _backers) = setup_failed_campaign_with_bounty();
+
+    let result = client.try_refund_batch(&Address::generate(&env), &0);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::InvalidLimit
+    );
+}
+
+#[test]
+fn test_refund_batch_lifo_refunds_latest_first() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+    client.set_refund_order(&crate::RefundOrder::Lifo);
+    assert_eq!(
+        client.campaign_info().refund_order,
+        crate::RefundOrder::Lifo
+    );
+
+    let backers = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    for backer in backers.iter() {
+        mint_to(&env, &token_address, &admin, backer, 10_000);
+        client.contribute(backer, &10_000, &0, &None, &false, &None, &None, &None);
+    }
+    let result = client.try_set_refund_order(&crate::RefundOrder::Fifo);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::CampaignHasFunds
+    );
+
+    env.ledger().set_timestamp(deadline + 1);
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(client.refund_batch(&creator, &2), 2);
+    assert_eq!(token_client.balance(&backers[0]), 0);
+    assert_eq!(token_client.balance(&backers[1]), 10_000);
+    assert_eq!(token_client.balance(&backers[2]), 10_000);
+
+    assert_eq!(client.refund_batch(&creator, &2), 1);
+    assert_eq!(token_client.balance(&backers[0]), 10_000);
+    assert_eq!(client.total_raised(), 0);
+}
+
+// ── Pledge Expiry Tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_collect_pledges_skips_expired_pledges() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let options = crate::CampaignOptions {
+        allow_dust_goal: true,
+        pledge_valid_secs: 1_000,
+        ..Default::default()
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+
+    let stale = Address::generate(&env);
+    let fresh = Address::generate(&env);
+    let backer = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &stale, 400_000);
+    mint_to(&env, &token_address, &admin, &fresh, 700_000);
+    mint_to(&env, &token_address, &admin, &backer, 600_000);
+
+    env.ledger().set_timestamp(100);
+    client.pledge(&stale, &400_000);
+    env.ledger().set_timestamp(3_000);
+    client.pledge(&fresh, &700_000);
+    client.contribute(&backer, &600_000, &0, &None, &false, &None, &None, &None);
+
+    // Collection pulls from pledgers inside the contract call, so their
+    // authorization is not tied to the root invocation.
+    env.mock_all_auths_allowing_non_root_auth();
+    env.ledger().set_timestamp(deadline + 1);
+    client.collect_pledges(&creator);
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&stale), 400_000);
+    assert_eq!(token_client.balance(&fresh), 0);
+    assert_eq!(client.pledge_amount(&stale), 0);
+    assert_eq!(client.total_raised(), 1_300_000);
+    assert_eq!(client.total_pledged(), 0);
+    assert_eq!(client.expired_pledges(), 400_000);
+}
+
+#[test]
+fn test_pledge_after_pledge_deadline_rejected() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let options = crate::CampaignOptions {
+        allow_dust_goal: true,
+        pledge_deadline: 1_800,
+        ..Default::default()
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+
+    env.ledger().set_timestamp(2_000);
+    let result = client.try_pledge(&alice, &50_000);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::CampaignEnded
+    );
+
+    client.contribute(&alice, &50_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(client.total_raised(), 50_000);
+}
+
+#[test]
+fn test_pledge_with_check_rejects_pledge_beyond_balance() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    client.pledge_with_check(&alice, &60_000);
+
+    let result = client.try_pledge_with_check(&alice, &50_000);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::InsufficientBalanceForPledge
+    );
+    assert_eq!(client.pledge_amount(&alice), 60_000);
+}
+
+#[test]
+fn test_revalidation_flags_then_voids_underfunded_pledge() {
+    use crate::events::PledgeFlaggedEvent;
+
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let options = crate::CampaignOptions {
+        allow_dust_goal: true,
+        void_underfunded_pledges: true,
+        ..Default::default()
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    mint_to(&env, &token_address, &admin, &bob, 100_000);
+    client.pledge_with_check(&alice, &80_000);
+    client.pledge_with_check(&bob, &80_000);
+    assert_eq!(client.revalidate_pledges(&0, &10), 0);
+
+    // Alice spends most of her balance after pledging.
+    token::Client::new(&env, &token_address).transfer(&alice, &admin, &50_000);
+    assert_eq!(client.revalidate_pledges(&0, &10), 1);
+    assert_eq!(
+        last_event::<PledgeFlaggedEvent>(&env, "pledge_flagged"),
+        PledgeFlaggedEvent {
+            pledger: alice.clone(),
+            pledged: 80_000,
+            balance: 50_000,
+        }
+    );
+    assert_eq!(
+        client.flagged_pledges(),
+        soroban_sdk::vec![&env, alice.clone()]
+    );
+
+    // Still underfunded on the next check, so the pledge is voided.
+    env.ledger().set_timestamp(env.ledger().timestamp() + 60);
+    assert_eq!(client.revalidate_pledges(&0, &10), 1);
+    let voided: PledgeFlaggedEvent = last_event(&env, "pledge_voided");
+    assert_eq!(voided.pledger, alice);
+    assert_eq!(client.pledge_amount(&alice), 0);
+    assert_eq!(client.total_pledged(), 80_000);
+    assert_eq!(client.flagged_pledges().len(), 0);
+}
+
+// ── Pledge Voiding Tests ────────────────────────────────────────────────────
+
+#[test]
+fn test_cancel_voids_outstanding_pledges() {
+    use soroban_sdk::{testutils::Events, IntoVal};
+
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let pledger = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &pledger, 500_000);
+    client.pledge(&pledger, &500_000);
+    assert_eq!(client.pledger_count(), 1);
+
+    client.cancel();
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, campaign_topics(&env, "pledges_voided"));
+    let voided: i128 = data.into_val(&env);
+    assert_eq!(voided, 500_000);
+    assert_eq!(client.total_pledged(), 0);
+    assert_eq!(client.pledge_amount(&pledger), 0);
+    assert_eq!(client.pledger_count(), 0);
+}
+
+#[test]
+fn test_refund_voids_outstanding_pledges() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let backer = Address::generate(&env);
+    let pledger = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &backer, 100_000);
+    mint_to(&env, &token_address, &admin, &pledger, 200_000);
+    client.contribute(&backer, &100_000, &0, &None, &false, &None, &None, &None);
+    client.pledge(&pledger, &200_000);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.refund();
+
+    assert_eq!(client.total_pledged(), 0);
+    assert_eq!(client.pledge_amount(&pledger), 0);
+    assert_eq!(client.pledger_count(), 0);
+}
+
+#[test]
+fn test_collect_pledges_after_cancel_returns_error() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let pledger = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &pledger, 500_000);
+    client.pledge(&pledger, &500_000);
+    client.cancel();
+
+    env.ledger().set_timestamp(deadline + 1);
+    let result = client.try_collect_pledges(&creator);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::CampaignNotActive
+    );
+
+    let result = client.try_pledge(&pledger, &1_000);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::CampaignNotActive
+    );
+}
+
+// ── Balance Reconciliation Tests ────────────────────────────────────────────
+
+#[test]
+fn test_withdraw_sets_aside_surplus_for_creator() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let backer = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &backer, 1_000_000);
+    client.contribute(&backer, &1_000_000, &0, &None, &false, &None, &None, &None);
+
+    // Tokens sent straight to the contract are not part of total_raised.
+    mint_to(&env, &token_address, &admin, &client.address, 25_000);
+
+    let token_client = token::Client::new(&env, &token_address);
+    let creator_before = token_client.balance(&creator);
+    env.ledger().set_timestamp(deadline + 1);
+    client.withdraw();
+
+    assert_eq!(token_client.balance(&creator), creator_before + 1_000_000);
+    assert_eq!(client.surplus(), 25_000);
+
+    assert_eq!(client.claim_surplus(), 25_000);
+    assert_eq!(token_client.balance(&creator), creator_before + 1_025_000);
+    assert_eq!(token_client.balance(&client.address), 0);
+    assert_eq!(client.surplus(), 0);
+
+    let result = client.try_claim_surplus();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::NoSurplus
+    );
+}
+
+#[test]
+fn test_withdraw_emits_reconciliation_delta() {
+    use soroban_sdk::{testutils::Events, FromVal};
+
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let backer = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &backer, 1_000_000);
+    client.contribute(&backer, &1_000_000, &0, &None, &false, &None, &None, &None);
+    mint_to(&env, &token_address, &admin, &client.address, 7);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.withdraw();
+
+    let reconciled = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| *topics == campaign_topics(&env, "balance_reconciled"))
+        .expect("reconciliation event");
+    assert_eq!(
+        crate::events::BalanceReconciledEvent::from_val(&env, &reconciled.2),
+        crate::events::BalanceReconciledEvent {
+            total_raised: 1_000_000,
+            balance: 1_000_007,
+            delta: 7,
+        }
+    );
+}
+
+#[test]
+#[should_panic(expected = "campaign is not settled")]
+fn test_claim_surplus_before_withdraw_panics() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    client.claim_surplus();
+}
+
+// ── Batch Contribution Tests ────────────────────────────────────────────────
+
+#[test]
+fn test_contribute_batch_credits_each_beneficiary() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let exchange = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &exchange, 500_000);
+
+    let entries = soroban_sdk::vec![
+        &env,
+        (alice.clone(), 200_000i128),
+        (bob.clone(), 300_000i128)
+    ];
+    let accepted = client.contribute_batch(&exchange, &entries);
+
+    assert_eq!(accepted, soroban_sdk::vec![&env, 200_000i128, 300_000i128]);
+    assert_eq!(client.contribution(&alice), 200_000);
+    assert_eq!(client.contribution(&bob), 300_000);
+    assert_eq!(client.contribution(&exchange), 0);
+    assert_eq!(client.total_raised(), 500_000);
+    assert_eq!(
+        client.get_contributors(),
+        soroban_sdk::vec![&env, alice, bob]
+    );
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&exchange), 0);
+}
+
+#[test]
+fn test_contribute_batch_respects_contributor_cap() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+    client.set_max_contributors(&2);
+
+    let exchange = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &exchange, 500_000);
+
+    let entries = soroban_sdk::vec![
+        &env,
+        (alice.clone(), 100_000i128),
+        (bob.clone(), 100_000i128)
+    ];
+    client.contribute_batch(&exchange, &entries);
+    assert_eq!(last_event::<u32>(&env, "contributor_slots_exhausted"), 2);
+    assert_eq!(client.slots_remaining(), Some(0));
+
+    // A newcomer fails the whole batch; existing contributors may top up.
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+    let entries = soroban_sdk::vec![
+        &env,
+        (alice.clone(), 100_000i128),
+        (carol.clone(), 100_000i128)
+    ];
+    assert_eq!(
+        client
+            .try_contribute_batch(&exchange, &entries)
+            .unwrap_err()
+            .unwrap(),
+        crate::ContractError::ContributorLimitReached
+    );
+    let entries = soroban_sdk::vec![&env, (bob.clone(), 100_000i128)];
+    client.contribute_batch(&exchange, &entries);
+    assert_eq!(client.contribution(&bob), 200_000);
+    assert_eq!(client.contribution(&carol), 0);
+}
+
+#[test]
+fn test_contribute_batch_straddling_hard_cap() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &500_000,
+        &1_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let exchange = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &exchange, 1_500_000);
+
+    let entries = soroban_sdk::vec![
+        &env,
+        (alice.clone(), 600_000i128),
+        (bob.clone(), 600_000i128),
+        (carol.clone(), 300_000i128)
+    ];
+    let accepted = client.contribute_batch(&exchange, &entries);
+
+    assert_eq!(
+        accepted,
+        soroban_sdk::vec![&env, 600_000i128, 400_000i128, 0i128]
+    );
+    assert_eq!(client.contribution(&bob), 400_000);
+    assert_eq!(client.contribution(&carol), 0);
+    assert_eq!(client.total_raised(), 1_000_000);
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&exchange), 500_000);
+}
+
+#[test]
+fn test_contribute_batch_rejects_oversized_batch() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let exchange = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &exchange, 1_000_000);
+
+    let mut entries = soroban_sdk::Vec::new(&env);
+    for _ in 0..21 {
+        entries.push_back((Address::generate(&env), 1_000i128));
+    }
+    let result = client.try_contribute_batch(&exchange, &entries);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::InvalidLimit
+    );
+
+    let empty = soroban_sdk::Vec::new(&env);
+    let result = client.try_contribute_batch(&exchange, &empty);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::InvalidLimit
+    );
+}
+
+#[test]
+#[should_panic(expected = "amount below minimum")]
+fn test_contribute_batch_applies_minimum_per_entry() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let exchange = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &exchange, 1_000_000);
+
+    let entries = soroban_sdk::vec![
+        &env,
+        (Address::generate(&env), 5_000i128),
+        (Address::generate(&env), 999i128)
+    ];
+    client.contribute_batch(&exchange, &entries);
+}
+
+#[test]
+fn test_contribute_batch_rate_limits_payer_once() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let exchange = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &exchange, 1_000_000);
+
+    let entries = soroban_sdk::vec![&env, (Address::generate(&env), 5_000i128)];
+    client.contribute_batch(&exchange, &entries);
+    assert!(client.cooldown_remaining(&exchange) > 0);
+
+    let balance = token::Client::new(&env, &token_address).balance(&exchange);
+    let accepted = client.contribute_batch(&exchange, &entries);
+    assert_eq!(accepted, soroban_sdk::vec![&env, 0i128]);
+    assert_eq!(
+        token::Client::new(&env, &token_address).balance(&exchange),
+        balance
+    );
+    assert_eq!(client.rate_limit_hits_for(&exchange), 1);
+}
+
+// ── Daily Totals Tests ──────────────────────────────────────────────────────
+
+#[test]
+fn test_daily_totals_bucket_contributions_by_day() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = 10 * 86_400;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 1_000_000);
+    mint_to(&env, &token_address, &admin, &bob, 1_000_000);
+
+    env.ledger().set_timestamp(86_400 + 10);
+    client.contribute(&alice, &100_000, &0, &None, &false, &None, &None, &None);
+    client.contribute(&bob, &50_000, &0, &None, &false, &None, &None, &None);
+
+    env.ledger().set_timestamp(2 * 86_400 + 10);
+    client.contribute(&alice, &200_000, &0, &None, &false, &None, &None, &None);
+
+    env.ledger().set_timestamp(3 * 86_400 + 10);
+    client.contribute(&bob, &300_000, &0, &None, &false, &None, &None, &None);
+
+    assert_eq!(
+        client.daily_totals(),
+        soroban_sdk::vec![
+            &env,
+            (1u64, 150_000i128),
+            (2u64, 200_000i128),
+            (3u64, 300_000i128)
+        ]
+    );
+
+    // Refunds come out of the days the refunded backer contributed on.
+    env.ledger().set_timestamp(deadline + 1);
+    client.claim_refund(&alice);
+    assert_eq!(
+        client.daily_totals(),
+        soroban_sdk::vec![&env, (1u64, 50_000i128), (2u64, 0i128), (3u64, 300_000i128)]
+    );
+}
+
+#[test]
+fn test_daily_totals_book_reversal_against_latest_contribution_day() {
+    let (env, client, _platform, _token_address, alice) = setup_reversible_campaign();
+    let first_day = env.ledger().timestamp() / 86_400;
+
+    // Alice's 600_000 was all contributed on the first day; a reversal on a
+    // later day takes it back from there and adds no bucket of its own.
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + 86_400);
+    client.reverse_contribution(&alice, &200_000, &7);
+    assert_eq!(
+        client.daily_totals(),
+        soroban_sdk::vec![&env, (first_day, 400_000i128)]
+    );
+}
+
+#[test]
+fn test_daily_totals_evict_oldest_bucket() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = 200 * 86_400;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &i128::MAX,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 1_000_000);
+
+    for day in 0..91u64 {
+        env.ledger().set_timestamp(day * 86_400);
+        client.contribute(&alice, &1_000, &0, &None, &false, &None, &None, &None);
+    }
+
+    let totals = client.daily_totals();
+    assert_eq!(totals.len(), 90);
+    assert_eq!(totals.first().unwrap(), (1u64, 1_000i128));
+    assert_eq!(totals.last().unwrap(), (90u64, 1_000i128));
+}
+
+// ── Tier Snapshot Tests ─────────────────────────────────────────────────────
+
+fn setup_tiered_campaign(
+    env: &Env,
+    client: &CrowdfundContractClient,
+    creator: &Address,
+    token_address: &Address,
+) -> u64 {
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        creator,
+        token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    client.add_reward_tier(
+        creator,
+        &soroban_sdk::String::from_str(env, "Bronze"),
+        &10_000,
+        &0,
+    );
+    client.add_reward_tier(
+        creator,
+        &soroban_sdk::String::from_str(env, "Gold"),
+        &500_000,
+        &0,
+    );
+    deadline
+}
+
+#[test]
+fn test_final_tiers_snapshotted_at_withdraw() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = setup_tiered_campaign(&env, &client, &creator, &token_address);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 600_000);
+    mint_to(&env, &token_address, &admin, &bob, 50_000);
+    mint_to(&env, &token_address, &admin, &carol, 700_000);
+    client.contribute(&alice, &600_000, &0, &None, &false, &None, &None, &None);
+    client.contribute(&bob, &50_000, &0, &None, &false, &None, &None, &None);
+    client.contribute(&carol, &700_000, &0, &None, &false, &None, &None, &None);
+
+    assert_eq!(client.final_tier(&alice), None);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.withdraw();
+
+    let gold = soroban_sdk::String::from_str(&env, "Gold");
+    assert_eq!(client.final_tier(&alice), Some(gold.clone()));
+    assert_eq!(
+        client.final_tier(&bob),
+        Some(soroban_sdk::String::from_str(&env, "Bronze"))
+    );
+    assert_eq!(
+        client.tier_roster(&gold, &0, &10),
+        soroban_sdk::vec![&env, alice.clone(), carol.clone()]
+    );
+    assert_eq!(
+        client.tier_roster(&gold, &1, &10),
+        soroban_sdk::vec![&env, carol]
+    );
+    assert_eq!(
+        client.tier_roster(&gold, &0, &1),
+        soroban_sdk::vec![&env, alice]
+    );
+}
+
+#[test]
+fn test_cancel_produces_no_tier_snapshot() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    setup_tiered_campaign(&env, &client, &creator, &token_address);
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 600_000);
+    client.contribute(&alice, &600_000, &0, &None, &false, &None, &None, &None);
+
+    client.cancel();
+
+    let gold = soroban_sdk::String::from_str(&env, "Gold");
+    assert_eq!(client.final_tier(&alice), None);
+    assert_eq!(client.tier_roster(&gold, &0, &10).len(), 0);
+}
+
+// ── Goal Reached & Finalize Tests ───────────────────────────────────────────
+
+#[test]
+fn test_goal_reached_event_fires_once() {
+    use soroban_sdk::testutils::Events;
+
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 1_000_000);
+    mint_to(&env, &token_address, &admin, &bob, 1_000_000);
+
+    let goal_reached = |env: &Env| {
+        env.events()
+            .all()
+            .iter()
+            .filter(|(_, topics, _)| *topics == campaign_topics(env, "goal_reached"))
+            .count()
+    };
+
+    client.contribute(&alice, &600_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(goal_reached(&env), 0);
+
+    client.contribute(&bob, &500_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(goal_reached(&env), 1);
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 120);
+    client.contribute(&alice, &400_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(goal_reached(&env), 0);
+    assert_eq!(client.total_raised(), 1_500_000);
+}
+
+#[test]
+fn test_finalize_marks_successful_and_withdraw_pays_out() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 1_000_000);
+    client.contribute(&alice, &1_000_000, &0, &None, &false, &None, &None, &None);
+
+    let result = client.try_finalize();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::CampaignStillActive
+    );
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.finalize();
+    assert!(client.campaign_info().status == crate::Status::Successful);
+
+    let token_client = token::Client::new(&env, &token_address);
+    let creator_before = token_client.balance(&creator);
+    client.withdraw();
+    assert_eq!(token_client.balance(&creator), creator_before + 1_000_000);
+    assert_eq!(client.total_raised(), 0);
+
+    let result = client.try_finalize();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::CampaignNotActive
+    );
+}
+
+#[test]
+fn test_finalize_rejects_unmet_goal() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 500_000);
+    client.contribute(&alice, &500_000, &0, &None, &false, &None, &None, &None);
+
+    env.ledger().set_timestamp(deadline + 1);
+    let result = client.try_finalize();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::GoalNotReached
+    );
+}
+
+#[test]
+fn test_goal_reachability_and_shortfall() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 1_000_000);
+    client.contribute(&alice, &400_000, &0, &None, &false, &None, &None, &None);
+
+    // Short of the goal but still running.
+    assert!(client.goal_reachable());
+    assert_eq!(client.shortfall(), 600_000);
+
+    // Expired short of the goal: finalize fails fast.
+    env.ledger().set_timestamp(deadline + 1);
+    assert!(!client.goal_reachable());
+    assert_eq!(client.shortfall(), 600_000);
+    assert_eq!(
+        client.try_finalize().unwrap_err().unwrap(),
+        crate::ContractError::GoalNotReached
+    );
+}
+
+#[test]
+fn test_goal_reachable_once_met() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 1_200_000);
+    client.contribute(&alice, &1_200_000, &0, &None, &false, &None, &None, &None);
+    assert!(client.goal_reachable());
+    assert_eq!(client.shortfall(), 0);
+
+    env.ledger().set_timestamp(deadline + 1);
+    assert!(client.goal_reachable());
+    client.finalize();
+    assert!(client.goal_reachable());
+}
+
+#[test]
+fn test_goal_met_without_quorum_refunds() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let options = crate::CampaignOptions {
+        allow_dust_goal: true,
+        min_contributors: 3,
+        ..Default::default()
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+    assert_eq!(client.campaign_info().min_contributors, 3);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 600_000);
+    mint_to(&env, &token_address, &admin, &bob, 600_000);
+    client.contribute(&alice, &600_000, &0, &None, &false, &None, &None, &None);
+    client.contribute(&bob, &600_000, &0, &None, &false, &None, &None, &None);
+    assert!(!client.quorum_met());
+
+    env.ledger().set_timestamp(deadline + 1);
+    assert!(!client.goal_reachable());
+    assert_eq!(
+        client.try_withdraw().unwrap_err().unwrap(),
+        crate::ContractError::QuorumNotMet
+    );
+    client.refund();
+    assert!(client.campaign_info().status == crate::Status::Refunded);
+    assert_eq!(
+        token::Client::new(&env, &token_address).balance(&alice),
+        600_000
+    );
+}
+
+#[test]
+#[should_panic(expected = "funds already withdrawn")]
+fn test_withdraw_twice_after_finalize_panics() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 1_000_000);
+    client.contribute(&alice, &1_000_000, &0, &None, &false, &None, &None, &None);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.finalize();
+    client.withdraw();
+    client.withdraw();
+}
+
+// ── Contribution Receipt Tests ──────────────────────────────────────────────
+
+#[test]
+fn test_contributions_issue_sequential_receipts() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    mint_to(&env, &token_address, &admin, &bob, 200_000);
+    mint_to(&env, &token_address, &admin, &carol, 300_000);
+
+    env.ledger().set_timestamp(100);
+    client.contribute(&alice, &100_000, &0, &None, &false, &None, &None, &None);
+    env.ledger().set_timestamp(200);
+    client.contribute(
+        &bob,
+        &200_000,
+        &0,
+        &Some(alice.clone()),
+        &false,
+        &None,
+        &None,
+        &None,
+    );
+    env.ledger().set_timestamp(300);
+    client.contribute(&carol, &300_000, &0, &None, &true, &None, &None, &None);
+
+    assert_eq!(client.receipt_count(), 3);
+
+    let first = client.get_receipt(&0).unwrap();
+    assert_eq!(first.id, 0);
+    assert_eq!(first.contributor, Some(alice.clone()));
+    assert_eq!(first.amount, 100_000);
+    assert_eq!(first.timestamp, 100);
+    assert_eq!(first.referral, None);
+
+    let second = client.get_receipt(&1).unwrap();
+    assert_eq!(second.contributor, Some(bob));
+    assert_eq!(second.amount, 200_000);
+    assert_eq!(second.timestamp, 200);
+    assert_eq!(second.referral, Some(alice));
+
+    // Anonymous contributions keep the contributor off the receipt.
+    let third = client.get_receipt(&2).unwrap();
+    assert_eq!(third.contributor, None);
+    assert_eq!(third.amount, 300_000);
+
+    assert!(client.get_receipt(&3).is_none());
+
+    let page = client.receipts_page(&1, &10);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().id, 1);
+    assert_eq!(client.extend_receipts_ttl(&0, &10), 3);
+}
+
+#[test]
+fn test_contributed_event_carries_receipt_id() {
+    use soroban_sdk::{testutils::Events, FromVal};
+
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    client.contribute(&alice, &40_000, &0, &None, &false, &None, &None, &None);
+    env.ledger().set_timestamp(env.ledger().timestamp() + 120);
+    client.contribute(&alice, &60_000, &0, &None, &false, &None, &None, &None);
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, campaign_topics(&env, "contributed"));
+    let event = crate::events::ContributedEvent::from_val(&env, &data);
+    assert_eq!(event.contributor, alice);
+    assert_eq!(event.accepted, 60_000);
+    assert_eq!(event.receipt_id, 1);
+}
+
+// ── Collect Pledges To Goal Tests ───────────────────────────────────────────
+
+#[test]
+fn test_collect_pledges_to_goal_stops_at_goal() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let options = crate::CampaignOptions {
+        allow_dust_goal: true,
+        collect_pledges_to_goal: true,
+        ..Default::default()
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000,
+        &10_000,
+        &deadline,
+        &1,
+        &None,
+        &Some(options),
+        &None,
+    );
+
+    let backer = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &backer, 400);
+    mint_to(&env, &token_address, &admin, &first, 400);
+    mint_to(&env, &token_address, &admin, &second, 500);
+    mint_to(&env, &token_address, &admin, &third, 300);
+
+    client.contribute(&backer, &400, &0, &None, &false, &None, &None, &None);
+    client.pledge(&first, &400);
+    client.pledge(&second, &500);
+    client.pledge(&third, &300);
+
+    env.mock_all_auths_allowing_non_root_auth();
+    env.ledger().set_timestamp(deadline + 1);
+    client.collect_pledges(&creator);
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&first), 0);
+    assert_eq!(token_client.balance(&second), 300);
+    assert_eq!(token_client.balance(&third), 300);
+    assert_eq!(client.total_raised(), 1_000);
+    assert_eq!(client.total_pledged(), 0);
+    assert_eq!(client.pledge_amount(&second), 0);
+    assert_eq!(client.pledge_amount(&third), 0);
+}
+
+#[test]
+fn test_collect_pledges_collects_everything_by_default() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000,
+        &10_000,
+        &deadline,
+        &1,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let backer = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &backer, 400);
+    mint_to(&env, &token_address, &admin, &first, 400);
+    mint_to(&env, &token_address, &admin, &second, 500);
+
+    client.contribute(&backer, &400, &0, &None, &false, &None, &None, &None);
+    client.pledge(&first, &400);
+    client.pledge(&second, &500);
+
+    env.mock_all_auths_allowing_non_root_auth();
+    env.ledger().set_timestamp(deadline + 1);
+    client.collect_pledges(&creator);
+
+    assert_eq!(client.total_raised(), 1_300);
+}
+
+#[test]
+fn test_collect_pledges_restricted_to_creator_and_keepers() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000,
+        &10_000,
+        &deadline,
+        &1,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let pledger = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &pledger, 1_000);
+    client.pledge(&pledger, &1_000);
+
+    env.mock_all_auths_allowing_non_root_auth();
+    env.ledger().set_timestamp(deadline + 1);
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_collect_pledges(&stranger).unwrap_err().unwrap(),
+        crate::ContractError::NotAuthorized
+    );
+
+    let keeper = Address::generate(&env);
+    client.set_pledge_keeper(&keeper, &true);
+    client.collect_pledges(&keeper);
+    let event: crate::events::PledgesCollectedEvent = last_event(&env, "pledges_collected");
+    assert_eq!(event.caller, keeper);
+    assert_eq!(event.collected, 1_000);
+    assert_eq!(client.total_raised(), 1_000);
+}
+
+#[test]
+fn test_pledge_cancelled_during_grace_period_is_not_collected() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000,
+        &10_000,
+        &deadline,
+        &1,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+    client.set_pledge_grace_period(&600);
+
+    let staying = Address::generate(&env);
+    let leaving = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &staying, 1_000);
+    mint_to(&env, &token_address, &admin, &leaving, 500);
+    client.pledge(&staying, &1_000);
+    client.pledge(&leaving, &500);
+
+    env.mock_all_auths_allowing_non_root_auth();
+    env.ledger().set_timestamp(deadline + 600);
+    assert_eq!(
+        client.try_collect_pledges(&creator).unwrap_err().unwrap(),
+        crate::ContractError::CampaignStillActive
+    );
+    client.cancel_pledge(&leaving);
+    assert_eq!(client.total_pledged(), 1_000);
+
+    env.ledger().set_timestamp(deadline + 601);
+    client.collect_pledges(&creator);
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&leaving), 500);
+    assert_eq!(client.total_raised(), 1_000);
+}
+
+// ── Active Contributor Count Tests ──────────────────────────────────────────
+
+#[test]
+fn test_refund_clears_active_contributor_count() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    mint_to(&env, &token_address, &admin, &bob, 200_000);
+    client.contribute(&alice, &100_000, &0, &None, &false, &None, &None, &None);
+    client.contribute(&bob, &200_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(client.contributor_count(), 2);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.refund();
+
+    assert_eq!(client.contributor_count(), 0);
+    assert_eq!(client.lifetime_contributor_count(), 2);
+
+    let stats = client.get_stats();
+    assert_eq!(stats.contributor_count, 0);
+    assert_eq!(stats.average_contribution, 0);
+    assert_eq!(stats.largest_contribution, 0);
+}
+
+#[test]
+fn test_claim_refund_decrements_active_contributor_count() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 150_000);
+    mint_to(&env, &token_address, &admin, &bob, 200_000);
+    client.contribute(&alice, &100_000, &0, &None, &false, &None, &None, &None);
+    env.ledger().set_timestamp(env.ledger().timestamp() + 120);
+    client.contribute(&alice, &50_000, &0, &None, &false, &None, &None, &None);
+    client.contribute(&bob, &200_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(client.contributor_count(), 2);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.claim_refund(&alice);
+
+    assert_eq!(client.contributor_count(), 1);
+    assert_eq!(client.lifetime_contributor_count(), 2);
+    assert_eq!(client.get_stats().average_contribution, 200_000);
+}
+
+#[test]
+fn test_stats_rank_overfunded_campaigns() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 1_500_000);
+    mint_to(&env, &token_address, &admin, &bob, 100_000);
+    client.contribute(&alice, &1_500_000, &0, &None, &false, &None, &None, &None);
+    client.pledge(&bob, &100_000);
+
+    let stats = client.get_stats();
+    assert_eq!(stats.progress_bps, 10_000);
+    assert_eq!(stats.progress_raw_bps, 15_000);
+    assert_eq!(stats.overfunded_bps, 5_000);
+    assert_eq!(stats.cap_utilization_bps, 7_500);
+    assert_eq!(stats.projected_progress_bps, 16_000);
+}
+
+#[test]
+fn test_stats_do_not_overflow_near_i128_max() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let goal = i128::MAX / 4;
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &goal,
+        &i128::MAX,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let whale = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &whale, goal * 3);
+    client.contribute(&whale, &(goal * 3), &0, &None, &false, &None, &None, &None);
+
+    let stats = client.get_stats();
+    assert_eq!(stats.total_raised, goal * 3);
+    assert_eq!(stats.progress_bps, 10_000);
+    assert_eq!(stats.progress_raw_bps, 30_000);
+    assert_eq!(stats.overfunded_bps, 20_000);
+    assert_eq!(stats.cap_utilization_bps, 7_499);
+    assert_eq!(stats.average_contribution, goal * 3);
+}
+
+// ── Event Payload Tests ─────────────────────────────────────────────────────
+
+/// Decode the data of the most recent event named `name`.
+fn last_event<T: soroban_sdk::TryFromVal<Env, soroban_sdk::Val>>(env: &Env, name: &str) -> T {
+    use soroban_sdk::{testutils::Events, FromVal};
+
+    let (_, _, data) = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| *topics == campaign_topics(env, name))
+        .last()
+        .expect("event not published");
+    T::from_val(env, &data)
+}
+
+#[test]
+fn test_contributed_event_reports_requested_and_accepted() {
+    use crate::events::ContributedEvent;
+
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &1_500_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 2_000_000);
+    client.contribute(&alice, &2_000_000, &0, &None, &false, &None, &None, &None);
+
+    assert_eq!(
+        last_event::<ContributedEvent>(&env, "contributed"),
+        ContributedEvent {
+            contributor: alice.clone(),
+            payer: alice,
+            requested: 2_000_000,
+            accepted: 1_500_000,
+            total_raised: 1_500_000,
+            receipt_id: 0,
+            memo: None,
+            source_tag: None,
+        }
+    );
+}
+
+#[test]
+fn test_pledge_withdraw_and_refund_events_decode() {
+    use crate::events::{PledgedEvent, RefundedEvent, WithdrawnEvent};
+
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 1_000_000);
+    client.pledge(&alice, &300_000);
+    assert_eq!(
+        last_event::<PledgedEvent>(&env, "pledged"),
+        PledgedEvent {
+            pledger: alice.clone(),
+            amount: 300_000,
+            total_pledged: 300_000,
+            total_raised: 0,
+        }
+    );
+
+    client.contribute(&alice, &1_000_000, &0, &None, &false, &None, &None, &None);
+    env.ledger().set_timestamp(deadline + 1);
+    client.withdraw();
+    assert_eq!(
+        last_event::<WithdrawnEvent>(&env, "withdrawn"),
+        WithdrawnEvent {
+            creator: creator.clone(),
+            amount: 1_000_000,
+            total_raised: 0,
+        }
+    );
+
+    // A second campaign that misses its goal refunds through claim_refund.
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &bob, 100_000);
+    client.contribute(&bob, &100_000, &0, &None, &false, &None, &None, &None);
+    env.ledger().set_timestamp(deadline + 1);
+    client.claim_refund(&bob);
+    assert_eq!(
+        last_event::<RefundedEvent>(&env, "refund_claimed"),
+        RefundedEvent {
+            contributor: bob,
+            amount: 100_000,
+            total_raised: 0,
+        }
+    );
+}
+
+#[test]
+fn test_event_running_totals_match_views() {
+    use crate::events::{
+        CheckpointEvent, ContributedEvent, PledgedEvent, PledgesCollectedEvent, RefundBatchEvent,
+        RefundedEvent,
+    };
+
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 600_000);
+    mint_to(&env, &token_address, &admin, &bob, 400_000);
+
+    client.contribute(&alice, &600_000, &0, &None, &false, &None, &None, &None);
+    let event: ContributedEvent = last_event(&env, "contributed");
+    assert_eq!(event.total_raised, client.total_raised());
+
+    client.pledge(&bob, &400_000);
+    let event: PledgedEvent = last_event(&env, "pledged");
+    assert_eq!(event.total_raised, client.total_raised());
+    assert_eq!(event.total_pledged, client.total_pledged());
+
+    env.mock_all_auths_allowing_non_root_auth();
+    env.ledger().set_timestamp(deadline + 1);
+    client.collect_pledges(&creator);
+    let event: PledgesCollectedEvent = last_event(&env, "pledges_collected");
+    assert_eq!(event.total_raised, client.total_raised());
+    assert_eq!(event.total_raised, 1_000_000);
+
+    client.finalize();
+    assert_eq!(
+        last_event::<CheckpointEvent>(&env, "checkpoint"),
+        CheckpointEvent {
+            total_raised: client.total_raised(),
+            total_pledged: 0,
+            paid_to_creator: client.paid_to_creator(),
+            active_contributors: client.contributor_count(),
+        }
+    );
+
+    // A campaign that misses its goal refunds part by claim, part by batch.
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+    let carol = Address::generate(&env);
+    let dave = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &carol, 100_000);
+    mint_to(&env, &token_address, &admin, &dave, 200_000);
+    client.contribute(&carol, &100_000, &0, &None, &false, &None, &None, &None);
+    client.contribute(&dave, &200_000, &0, &None, &false, &None, &None, &None);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.claim_refund(&carol);
+    let event: RefundedEvent = last_event(&env, "refund_claimed");
+    assert_eq!(event.total_raised, client.total_raised());
+    assert_eq!(event.total_raised, 200_000);
+
+    client.refund_batch(&creator, &10);
+    let event: RefundBatchEvent = last_event(&env, "refund_batch");
+    assert_eq!(event.total_raised, client.total_raised());
+    assert_eq!(event.total_raised, 0);
+
+    client.extend_storage(&0, &10);
+    let event: CheckpointEvent = last_event(&env, "checkpoint");
+    assert_eq!(event.total_raised, 0);
+    assert_eq!(event.active_contributors, 0);
+}
+
+// ── Expense Reporting Tests ─────────────────────────────────────────────────
+
+#[test]
+fn test_report_expenses_up_to_withdrawn_amount() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 1_000_000);
+    client.contribute(&alice, &1_000_000, &0, &None, &false, &None, &None, &None);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.withdraw();
+    assert_eq!(client.unreported_amount(), 1_000_000);
+
+    let hosting = soroban_sdk::String::from_str(&env, "Hosting");
+    let hardware = soroban_sdk::String::from_str(&env, "Hardware");
+    client.report_expense(
+        &400_000,
+        &hosting,
+        &soroban_sdk::BytesN::from_array(&env, &[1u8; 32]),
+    );
+    client.report_expense(
+        &500_000,
+        &hardware,
+        &soroban_sdk::BytesN::from_array(&env, &[2u8; 32]),
+    );
+    assert_eq!(client.unreported_amount(), 100_000);
+
+    let result = client.try_report_expense(
+        &200_000,
+        &soroban_sdk::String::from_str(&env, "Travel"),
+        &soroban_sdk::BytesN::from_array(&env, &[3u8; 32]),
+    );
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::ExpenseExceedsWithdrawn
+    );
+
+    let expenses = client.expenses(&0, &10);
+    assert_eq!(expenses.len(), 2);
+    assert_eq!(expenses.get(0).unwrap().amount, 400_000);
+    assert_eq!(expenses.get(1).unwrap().description, hardware);
+    assert_eq!(client.expenses(&1, &10).len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "campaign is not settled")]
+fn test_report_expense_before_success_panics() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    client.report_expense(
+        &1,
+        &soroban_sdk::String::from_str(&env, "Hosting"),
+        &soroban_sdk::BytesN::from_array(&env, &[1u8; 32]),
+    );
+}
+
+// ── Archive Tests ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_archive_cancelled_campaign_hides_it_in_factory() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+
+    let factory_id = env.register(factory::FactoryContract, ());
+    let factory_client = factory::FactoryContractClient::new(&env, &factory_id);
+    let factory_admin = Address::generate(&env);
+    factory_client.initialize(
+        &factory_admin,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+    );
+
+    // Register this campaign and a live sibling in the factory directory.
+    let sibling = Address::generate(&env);
+    env.as_contract(&factory_id, || {
+        env.storage().persistent().set(
+            &factory::DataKey::Campaigns,
+            &soroban_sdk::vec![&env, client.address.clone(), sibling.clone()],
+        );
+        env.storage().persistent().set(
+            &factory::DataKey::CampaignWasm(client.address.clone()),
+            &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        );
+    });
+
+    let options = crate::CampaignOptions {
+        allow_dust_goal: true,
+        factory: Some(factory_id.clone()),
+        ..Default::default()
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+
+    let result = client.try_archive(&creator);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::CampaignStillActive
+    );
+
+    client.cancel();
+    client.archive(&creator);
+
+    assert!(client.is_archived());
+    assert!(factory_client.is_campaign_archived(&client.address));
+    assert_eq!(
+        factory_client.campaigns_page_filtered(&false, &0, &10),
+        soroban_sdk::vec![&env, sibling]
+    );
+    assert_eq!(
+        factory_client.campaigns_page_filtered(&true, &0, &10).len(),
+        2
+    );
+}
+
+#[test]
+fn test_factory_admin_can_archive() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+
+    let factory_id = env.register(factory::FactoryContract, ());
+    let factory_client = factory::FactoryContractClient::new(&env, &factory_id);
+    let factory_admin = Address::generate(&env);
+    factory_client.initialize(
+        &factory_admin,
+        &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+    );
+    env.as_contract(&factory_id, || {
+        env.storage().persistent().set(
+            &factory::DataKey::Campaigns,
+            &soroban_sdk::vec![&env, client.address.clone()],
+        );
+        env.storage().persistent().set(
+            &factory::DataKey::CampaignWasm(client.address.clone()),
+            &soroban_sdk::BytesN::from_array(&env, &[0u8; 32]),
+        );
+    });
+
+    let options = crate::CampaignOptions {
+        allow_dust_goal: true,
+        factory: Some(factory_id),
+        ..Default::default()
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+    client.cancel();
+
+    client.archive(&factory_admin);
+    assert!(client.is_archived());
+}
+
+#[test]
+#[should_panic(expected = "not authorized to archive")]
+fn test_archive_by_stranger_panics() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+    client.cancel();
+
+    client.archive(&Address::generate(&env));
+}
+
+/// Contributors named by the `archive_entry` events of the last call.
+fn archived_entries(env: &Env) -> soroban_sdk::Vec<Address> {
+    use soroban_sdk::{testutils::Events, FromVal};
+
+    let mut entries = soroban_sdk::Vec::new(env);
+    for (_, topics, data) in env.events().all().iter() {
+        if topics == campaign_topics(env, "archive_entry") {
+            entries.push_back(crate::events::ArchiveEntryEvent::from_val(env, &data).contributor);
+        }
+    }
+    entries
+}
+
+#[test]
+fn test_archive_snapshot_pages_cover_everyone_once() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+    let mut backers = soroban_sdk::Vec::new(&env);
+    for _ in 0..6 {
+        let backer = Address::generate(&env);
+        mint_to(&env, &token_address, &admin, &backer, 200_000);
+        client.contribute(&backer, &200_000, &0, &None, &false, &None, &None, &None);
+        backers.push_back(backer);
+    }
+
+    assert_eq!(
+        client
+            .try_emit_archive_snapshot(&0, &3)
+            .unwrap_err()
+            .unwrap(),
+        crate::ContractError::CampaignStillActive
+    );
+    env.ledger().set_timestamp(deadline + 1);
+    client.finalize();
+
+    assert_eq!(client.emit_archive_snapshot(&0, &3), 3);
+    assert_eq!(archived_entries(&env), backers.slice(0..3));
+    let totals: crate::events::ArchiveTotalsEvent = last_event(&env, "archive_totals");
+    assert_eq!(totals.total_raised, 1_200_000);
+    assert_eq!(totals.contributors, 6);
+    let config: crate::CampaignInfo = last_event(&env, "archive_config");
+    assert_eq!(config.goal, 1_000_000);
+
+    // A repeated or overlapping page only exports what is left.
+    assert_eq!(client.emit_archive_snapshot(&0, &3), 3);
+    assert_eq!(archived_entries(&env).len(), 0);
+    assert_eq!(
+        client
+            .try_emit_archive_snapshot(&4, &3)
+            .unwrap_err()
+            .unwrap(),
+        crate::ContractError::ArchiveCursorGap
+    );
+    assert_eq!(client.emit_archive_snapshot(&3, &3), 6);
+    assert_eq!(archived_entries(&env), backers.slice(3..6));
+    assert_eq!(client.emit_archive_snapshot(&6, &3), 6);
+    assert_eq!(archived_entries(&env).len(), 0);
+}
+
+// ── Refund Claim Window Tests ───────────────────────────────────────────────
+
+fn refund_window_options(env: &Env, recipient: &Address) -> Option<crate::CampaignOptions> {
+    Some(crate::CampaignOptions {
+        admin: Some(Address::generate(env)),
+        sweep_recipient: Some(recipient.clone()),
+        refund_claim_window_secs: 1_000,
+        allow_dust_goal: true,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_unclaimed_refunds_sweep_after_window() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let recipient = Address::generate(&env);
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &refund_window_options(&env, &recipient),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    mint_to(&env, &token_address, &admin, &bob, 200_000);
+    client.contribute(&alice, &100_000, &0, &None, &false, &None, &None, &None);
+    client.contribute(&bob, &200_000, &0, &None, &false, &None, &None, &None);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.claim_refund(&alice);
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&alice), 100_000);
+
+    let result = client.try_sweep_unclaimed();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::RefundWindowOpen
+    );
+
+    env.ledger().set_timestamp(deadline + 1 + 1_001);
+    assert_eq!(client.sweep_unclaimed(), 200_000);
+    assert_eq!(
+        last_event::<crate::events::UnclaimedSweptEvent>(&env, "unclaimed_swept"),
+        crate::events::UnclaimedSweptEvent {
+            recipient: recipient.clone(),
+            amount: 200_000,
+        }
+    );
+    assert_eq!(token_client.balance(&recipient), 200_000);
+    assert_eq!(token_client.balance(&client.address), 0);
+
+    let late = client.try_claim_refund(&bob);
+    assert_eq!(
+        late.unwrap_err().unwrap(),
+        crate::ContractError::RefundWindowClosed
+    );
+    assert_eq!(token_client.balance(&bob), 0);
+}
+
+#[test]
+#[should_panic(expected = "refund claim window requires an admin and a sweep recipient")]
+fn test_refund_window_without_recipient_panics() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+
+    let options = crate::CampaignOptions {
+        admin: Some(Address::generate(&env)),
+        refund_claim_window_secs: 1_000,
+        allow_dust_goal: true,
+        ..Default::default()
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+}
+
+// ── Multi-Token Tests ───────────────────────────────────────────────────────
+
+/// Register a second token and return options accepting it at twice the
+/// primary token's value.
+fn multi_token_options(env: &Env) -> (Address, Address, Option<crate::CampaignOptions>) {
+    let secondary_admin = Address::generate(env);
+    let secondary = env
+        .register_stellar_asset_contract_v2(secondary_admin.clone())
+        .address();
+    let options = crate::CampaignOptions {
+        secondary_tokens: Some(soroban_sdk::vec![
+            env,
+            crate::AcceptedToken {
+                token: secondary.clone(),
+                rate: 2 * crate::RATE_SCALE,
+            },
+        ]),
+        allow_dust_goal: true,
+        ..Default::default()
+    };
+    (secondary, secondary_admin, Some(options))
+}
+
+#[test]
+fn test_mixed_token_contributions_meet_goal() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let (secondary, secondary_admin, options) = multi_token_options(&env);
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &options,
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 600_000);
+    mint_to(&env, &secondary, &secondary_admin, &bob, 200_000);
+
+    client.contribute(&alice, &600_000, &0, &None, &false, &None, &None, &None);
+    let credited = client.contribute(&bob, &200_000, &1, &None, &false, &None, &None, &None);
+
+    assert_eq!(credited, 400_000);
+    assert_eq!(client.total_raised(), 1_000_000);
+    assert_eq!(client.contribution(&bob), 400_000);
+    assert_eq!(client.token_contribution(&bob, &1), 200_000);
+    assert_eq!(client.token_raised(&0), 600_000);
+    assert_eq!(client.token_raised(&1), 200_000);
+    assert_eq!(
+        client.accepted_tokens(),
+        soroban_sdk::vec![&env, token_address.clone(), secondary.clone()]
+    );
+
+    let primary_client = token::Client::new(&env, &token_address);
+    let secondary_client = token::Client::new(&env, &secondary);
+    let creator_before = primary_client.balance(&creator);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.withdraw();
+
+    assert_eq!(primary_client.balance(&creator) - creator_before, 600_000);
+    assert_eq!(secondary_client.balance(&creator), 200_000);
+    assert_eq!(secondary_client.balance(&client.address), 0);
+    assert_eq!(client.token_raised(&1), 0);
+}
+
+#[test]
+fn test_mixed_token_refund_returns_each_token() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let (secondary, secondary_admin, options) = multi_token_options(&env);
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &options,
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    mint_to(&env, &secondary, &secondary_admin, &alice, 50_000);
+    client.contribute(&alice, &100_000, &0, &None, &false, &None, &None, &None);
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+    client.contribute(&alice, &50_000, &1, &None, &false, &None, &None, &None);
+    assert_eq!(client.contribution(&alice), 200_000);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.claim_refund(&alice);
+
+    assert_eq!(
+        token::Client::new(&env, &token_address).balance(&alice),
+        100_000
+    );
+    assert_eq!(token::Client::new(&env, &secondary).balance(&alice), 50_000);
+    assert_eq!(client.total_raised(), 0);
+    assert_eq!(client.token_contribution(&alice, &1), 0);
+    assert_eq!(client.token_raised(&1), 0);
+}
+
+#[test]
+fn test_contribute_unknown_token_index_rejected() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    let result = client.try_contribute(&alice, &100_000, &1, &None, &false, &None, &None, &None);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::UnsupportedToken
+    );
+}
+
+// ── USD Goal Tests ──────────────────────────────────────────────────────────
+
+/// SEP-40-style price feed returning whatever price was last set.
+#[soroban_sdk::contract]
+pub struct MockOracle;
+
+#[soroban_sdk::contractimpl]
+impl MockOracle {
+    pub fn set_price(env: Env, price: i128, timestamp: u64) {
+        let quote = crate::PriceData { price, timestamp };
+        env.storage()
+            .instance()
+            .set(&soroban_sdk::symbol_short!("price"), &quote);
+    }
+
+    pub fn lastprice(env: Env, _asset: crate::OracleAsset) -> Option<crate::PriceData> {
+        env.storage()
+            .instance()
+            .get(&soroban_sdk::symbol_short!("price"))
+    }
+
+    pub fn decimals(_env: Env) -> u32 {
+        7
+    }
+}
+
+/// Initialize a campaign with a 1_000_000 token goal and a 1_000_000 USD
+/// goal priced by a fresh mock oracle, and contribute 600_000 tokens.
+fn setup_usd_campaign() -> (
+    Env,
+    CrowdfundContractClient<'static>,
+    MockOracleClient<'static>,
+    Address,
+    Address,
+    u64,
+) {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let oracle_id = env.register(MockOracle, ());
+    let oracle = MockOracleClient::new(&env, &oracle_id);
+
+    let options = crate::CampaignOptions {
+        oracle: Some(oracle_id),
+        goal_usd: 1_000_000,
+        allow_dust_goal: true,
+        ..Default::default()
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 600_000);
+    client.contribute(&alice, &600_000, &0, &None, &false, &None, &None, &None);
+
+    (env, client, oracle, creator, alice, deadline)
+}
+
+#[test]
+fn test_usd_goal_follows_oracle_price() {
+    let (env, client, oracle, _creator, _alice, deadline) = setup_usd_campaign();
+    env.ledger().set_timestamp(deadline + 1);
+
+    // At $1 the 600_000 tokens raised fall short of the USD goal.
+    oracle.set_price(&10_000_000, &env.ledger().timestamp());
+    assert_eq!(client.goal_progress_usd(), Some((600_000, 1_000_000)));
+    assert_eq!(client.get_stats().progress_bps, 6_000);
+    let result = client.try_withdraw();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::GoalNotReached
+    );
+
+    // At $2 they meet it, although the token goal is still unmet.
+    oracle.set_price(&20_000_000, &env.ledger().timestamp());
+    assert_eq!(client.goal_progress_usd(), Some((1_200_000, 1_000_000)));
+    assert_eq!(client.get_stats().progress_bps, 10_000);
+    let result = client.try_refund();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::GoalReached
+    );
+    client.withdraw();
+    assert_eq!(client.total_raised(), 0);
+}
+
+#[test]
+fn test_stale_price_falls_back_to_token_goal() {
+    let (env, client, oracle, _creator, alice, deadline) = setup_usd_campaign();
+    oracle.set_price(&20_000_000, &env.ledger().timestamp());
+
+    // Well past the default one-hour staleness threshold.
+    env.ledger().set_timestamp(deadline + 7_200);
+    assert_eq!(client.goal_progress_usd(), None);
+
+    let result = client.try_withdraw();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::GoalNotReached
+    );
+    client.claim_refund(&alice);
+    assert_eq!(client.total_raised(), 0);
+}
+
+#[test]
+#[should_panic(expected = "USD goal requires an oracle and a positive goal")]
+fn test_usd_goal_without_oracle_panics() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+
+    let options = crate::CampaignOptions {
+        goal_usd: 1_000_000,
+        allow_dust_goal: true,
+        ..Default::default()
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+}
+
+// ── Refund History Tests ────────────────────────────────────────────────────
+
+#[test]
+fn test_refund_history_recorded_and_reclaim_rejected() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 300_000);
+    client.contribute(&alice, &300_000, &0, &None, &false, &None, &None, &None);
+
+    assert!(!client.was_refunded(&alice));
+    assert_eq!(client.refunded_amount(&alice), 0);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.claim_refund(&alice);
+
+    assert!(client.was_refunded(&alice));
+    assert_eq!(client.refunded_amount(&alice), 300_000);
+    assert!(!client.was_refunded(&bob));
+
+    let result = client.try_claim_refund(&alice);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::AlreadyRefunded
+    );
+}
+
+#[test]
+fn test_cancel_records_refund_history() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 200_000);
+    client.contribute(&alice, &200_000, &0, &None, &false, &None, &None, &None);
+
+    client.cancel();
+    assert_eq!(client.refunded_amount(&alice), 200_000);
+}
+
+// ── KYC Attestation Tests ───────────────────────────────────────────────────
+
+/// Attestation contract verifying whichever addresses were approved, and
+/// counting how often it is asked.
+#[soroban_sdk::contract]
+pub struct MockAttestation;
+
+#[soroban_sdk::contractimpl]
+impl MockAttestation {
+    pub fn set_verified(env: Env, addr: Address, verified: bool) {
+        env.storage().persistent().set(&addr, &verified);
+    }
+
+    pub fn is_verified(env: Env, addr: Address) -> bool {
+        let calls: u32 = env
+            .storage()
+            .instance()
+            .get(&soroban_sdk::symbol_short!("calls"))
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&soroban_sdk::symbol_short!("calls"), &(calls + 1));
+        env.storage().persistent().get(&addr).unwrap_or(false)
+    }
+
+    pub fn calls(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&soroban_sdk::symbol_short!("calls"))
+            .unwrap_or(0)
+    }
+}
+
+#[test]
+fn test_kyc_gate_rejects_unverified_contributors() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let attestation_id = env.register(MockAttestation, ());
+    let attestation = MockAttestationClient::new(&env, &attestation_id);
+
+    let options = crate::CampaignOptions {
+        attestation_contract: Some(attestation_id.clone()),
+        attestation_cache_secs: 600,
+        allow_dust_goal: true,
+        ..Default::default()
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+    assert_eq!(client.attestation_contract(), Some(attestation_id));
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 300_000);
+
+    let result = client.try_contribute(&alice, &100_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::KycRequired
+    );
+    let result = client.try_pledge(&alice, &100_000);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::KycRequired
+    );
+
+    attestation.set_verified(&alice, &true);
+    client.contribute(&alice, &100_000, &0, &None, &false, &None, &None, &None);
+    let calls = attestation.calls();
+
+    // A top-up within the cache window skips the attestation call.
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+    client.contribute(&alice, &100_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(attestation.calls(), calls);
+
+    // Once the cache expires, a revoked attestation blocks contributions.
+    attestation.set_verified(&alice, &false);
+    env.ledger().set_timestamp(env.ledger().timestamp() + 601);
+    let result = client.try_contribute(&alice, &100_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::KycRequired
+    );
+    assert_eq!(client.contribution(&alice), 200_000);
+}
+
+#[test]
+fn test_kyc_gate_rejects_when_attestation_call_fails() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    // The token contract has no `is_verified`, so every check fails.
+    let options = crate::CampaignOptions {
+        attestation_contract: Some(token_address.clone()),
+        attestation_cache_secs: 600,
+        allow_dust_goal: true,
+        ..Default::default()
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+
+    let check = client.can_contribute(&alice, &100_000);
+    assert!(!check.ok);
+    assert_eq!(check.reason, crate::ContractError::KycRequired as u32);
+    let result = client.try_contribute(&alice, &100_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::KycRequired
+    );
+}
+
+#[test]
+#[should_panic(expected = "attestation contract can only be changed before any contribution")]
+fn test_set_attestation_contract_after_contribution_panics() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let attestation_id = env.register(MockAttestation, ());
+    client.set_attestation_contract(&Some(attestation_id.clone()));
+    assert_eq!(client.attestation_contract(), Some(attestation_id));
+    client.set_attestation_contract(&None);
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    client.contribute(&alice, &100_000, &0, &None, &false, &None, &None, &None);
+
+    client.set_attestation_contract(&Some(Address::generate(&env)));
+}
+
+// ── Excess Demand Tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_clipped_contribution_records_excess_demand() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &1_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 800_000);
+    mint_to(&env, &token_address, &admin, &bob, 250_000);
+
+    client.contribute(&alice, &800_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(client.excess_demand(), 0);
+
+    // Bob is clipped to the remaining 200_000.
+    let accepted = client.contribute(&bob, &250_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(accepted, 200_000);
+    let event = last_event::<crate::events::ContributedEvent>(&env, "contributed");
+    assert_eq!((event.requested, event.accepted), (250_000, 200_000));
+    assert_eq!(client.excess_demand(), 50_000);
+}
+
+#[test]
+fn test_clipped_batch_entries_sum_in_excess_demand() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &1_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 800_000);
+    client.contribute(&alice, &800_000, &0, &None, &false, &None, &None, &None);
+
+    // Dave is clipped by 50_000 and Erin's 30_000 is skipped entirely.
+    let carol = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &carol, 300_000);
+    let entries = soroban_sdk::vec![
+        &env,
+        (carol.clone(), 150_000),
+        (Address::generate(&env), 100_000),
+        (Address::generate(&env), 30_000),
+    ];
+    let accepted = client.contribute_batch(&carol, &entries);
+    assert_eq!(accepted, soroban_sdk::vec![&env, 150_000, 50_000, 0]);
+    assert_eq!(client.excess_demand(), 80_000);
+}
+
+// ── Auto Refund Tests ───────────────────────────────────────────────────────
+
+#[test]
+fn test_expired_campaign_auto_refund() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let options = crate::CampaignOptions {
+        auto_refund_after_secs: 86_400,
+        allow_dust_goal: true,
+        ..Default::default()
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 400_000);
+    client.contribute(&alice, &300_000, &0, &None, &false, &None, &None, &None);
+
+    env.ledger().set_timestamp(deadline + 100);
+    let result = client.try_trigger_auto_refund();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::CampaignStillActive
+    );
+    let result = client.try_contribute(&alice, &100_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::CampaignEnded
+    );
+
+    env.ledger().set_timestamp(deadline + 86_401);
+    let result = client.try_contribute(&alice, &100_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::CampaignExpired
+    );
+    let result = client.try_withdraw();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::CampaignExpired
+    );
+
+    client.trigger_auto_refund();
+    assert_eq!(last_event::<u64>(&env, "auto_refund_triggered"), deadline);
+    assert!(client.campaign_info().status == crate::Status::Refunded);
+
+    client.claim_refund(&alice);
+    assert_eq!(
+        token::Client::new(&env, &token_address).balance(&alice),
+        400_000
+    );
+}
+
+#[test]
+fn test_auto_refund_rejected_when_goal_met() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let options = crate::CampaignOptions {
+        auto_refund_after_secs: 86_400,
+        allow_dust_goal: true,
+        ..Default::default()
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 1_000_000);
+    client.contribute(&alice, &1_000_000, &0, &None, &false, &None, &None, &None);
+
+    env.ledger().set_timestamp(deadline + 86_401);
+    let result = client.try_trigger_auto_refund();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::GoalReached
+    );
+    client.withdraw();
+}
+
+// ── Withdraw Preview Tests ──────────────────────────────────────────────────
+
+#[test]
+fn test_withdraw_preview_matches_withdrawal() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let platform = Address::generate(&env);
+    let config = crate::PlatformConfig {
+        address: platform.clone(),
+        fee_bps: 250,
+        fee_mode: crate::FeeMode::OnWithdraw,
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &Some(config),
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 1_200_000);
+
+    client.contribute(&alice, &600_000, &0, &None, &false, &None, &None, &None);
+    let result = client.try_withdraw_preview();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::CampaignStillActive
+    );
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+    client.contribute(&alice, &600_000, &0, &None, &false, &None, &None, &None);
+
+    env.ledger().set_timestamp(deadline + 1);
+    let preview = client.withdraw_preview();
+    assert_eq!(
+        preview,
+        crate::WithdrawPreview {
+            gross: 1_200_000,
+            platform_fee: 30_000,
+            referral_rewards: 0,
+            escrow: 0,
+            net_to_creator: 1_170_000,
+        }
+    );
+
+    let token_client = token::Client::new(&env, &token_address);
+    let creator_before = token_client.balance(&creator);
+    client.withdraw();
+
+    assert_eq!(
+        token_client.balance(&creator) - creator_before,
+        preview.net_to_creator
+    );
+    assert_eq!(token_client.balance(&platform), preview.platform_fee);
+}
+
+#[test]
+fn test_withdraw_preview_rejects_unmet_goal() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 300_000);
+    client.contribute(&alice, &300_000, &0, &None, &false, &None, &None, &None);
+
+    env.ledger().set_timestamp(deadline + 1);
+    let result = client.try_withdraw_preview();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::GoalNotReached
+    );
+}
+
+// ── Goal Decrease Tests ─────────────────────────────────────────────────────
+
+/// Initialize a campaign with a 1_000_000 goal ending in 30 days.
+fn setup_goal_campaign() -> (Env, CrowdfundContractClient<'static>, Address, Address, u64) {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 30 * 86_400;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    (env, client, token_address, admin, deadline)
+}
+
+#[test]
+fn test_update_goal_lowers_goal() {
+    let (env, client, token_address, admin, _deadline) = setup_goal_campaign();
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 500_000);
+    client.contribute(&alice, &500_000, &0, &None, &false, &None, &None, &None);
+
+    client.update_goal(&600_000);
+    let event: crate::events::GoalUpdatedEvent = last_event(&env, "goal_updated");
+    assert_eq!(
+        event,
+        crate::events::GoalUpdatedEvent {
+            old_goal: 1_000_000,
+            new_goal: 600_000
+        }
+    );
+    assert_eq!(client.goal(), 600_000);
+}
+
+#[test]
+#[should_panic(expected = "new goal must be below current goal")]
+fn test_update_goal_rejects_increase() {
+    let (_env, client, _token_address, _admin, _deadline) = setup_goal_campaign();
+
+    client.update_goal(&1_500_000);
+}
+
+#[test]
+fn test_update_goal_rejected_inside_buffer() {
+    let (env, client, _token_address, _admin, deadline) = setup_goal_campaign();
+
+    env.ledger().set_timestamp(deadline - 7 * 86_400);
+    let result = client.try_update_goal(&600_000);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::GoalUpdateTooLate
+    );
+    assert_eq!(client.goal(), 1_000_000);
+}
+
+#[test]
+fn test_update_goal_respects_raised_and_tier_floors() {
+    let (env, client, token_address, admin, _deadline) = setup_goal_campaign();
+    let creator = client.campaign_info().creator;
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 500_000);
+    client.contribute(&alice, &500_000, &0, &None, &false, &None, &None, &None);
+
+    // Floor is 120% of the 500_000 raised.
+    let result = client.try_update_goal(&599_999);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::GoalBelowFloor
+    );
+
+    client.add_reward_tier(
+        &creator,
+        &soroban_sdk::String::from_str(&env, "Gold"),
+        &800_000,
+        &0,
+    );
+    let result = client.try_update_goal(&700_000);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::GoalBelowFloor
+    );
+
+    client.update_goal(&800_000);
+    assert_eq!(client.goal(), 800_000);
+}
+
+// ── Pinned Update Tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_pin_second_update_and_unpin() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    for text in ["Kickoff", "Prototype ready", "Shipping soon"] {
+        client.post_update(&soroban_sdk::String::from_str(&env, text));
+    }
+    assert!(client.pinned_update().is_none());
+
+    client.pin_update(&1);
+    let event: u32 = last_event(&env, "update_pinned");
+    assert_eq!(event, 1);
+    let pinned = client.pinned_update().unwrap();
+    assert_eq!(
+        pinned.content,
+        soroban_sdk::String::from_str(&env, "Prototype ready")
+    );
+
+    client.unpin_update();
+    let event: u32 = last_event(&env, "update_unpinned");
+    assert_eq!(event, 1);
+    assert!(client.pinned_update().is_none());
+}
+
+#[test]
+fn test_pin_update_rejects_unknown_index() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+    client.post_update(&soroban_sdk::String::from_str(&env, "Kickoff"));
+
+    let result = client.try_pin_update(&1);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::InvalidInput
+    );
+}
+
+#[test]
+fn test_pinned_update_survives_withdrawal() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 1_000_000);
+    client.contribute(&alice, &1_000_000, &0, &None, &false, &None, &None, &None);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.withdraw();
+
+    let index = client.post_update(&soroban_sdk::String::from_str(&env, "Thank you!"));
+    client.pin_update(&index);
+    assert_eq!(
+        client.pinned_update().unwrap().content,
+        soroban_sdk::String::from_str(&env, "Thank you!")
+    );
+    assert_eq!(client.update_posts(&0, &10).len(), 1);
+}
+
+// ── Referral Reversal Tests ─────────────────────────────────────────────────
+
+#[test]
+fn test_refund_reverses_referral_credit() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let referrer = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 200_000);
+    mint_to(&env, &token_address, &admin, &bob, 200_000);
+    client.contribute(
+        &alice,
+        &200_000,
+        &0,
+        &Some(referrer.clone()),
+        &false,
+        &None,
+        &None,
+        &None,
+    );
+    client.contribute(
+        &bob,
+        &200_000,
+        &0,
+        &Some(referrer.clone()),
+        &false,
+        &None,
+        &None,
+        &None,
+    );
+    assert_eq!(client.referral_tally(&referrer), 400_000);
+
+    // Refunding one of the two referred backers halves the tally.
+    env.ledger().set_timestamp(deadline + 1);
+    let keeper = Address::generate(&env);
+    client.refund_batch(&keeper, &1);
+    assert_eq!(client.referral_tally(&referrer), 200_000);
+
+    client.refund_batch(&keeper, &1);
+    assert_eq!(client.referral_tally(&referrer), 0);
+}
+
+#[test]
+fn test_referral_reversed_event() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let referrer = Address::generate(&env);
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 300_000);
+    client.contribute(
+        &alice,
+        &300_000,
+        &0,
+        &Some(referrer.clone()),
+        &false,
+        &None,
+        &None,
+        &None,
+    );
+
+    client.cancel();
+    let event: crate::events::ReferralReversedEvent = last_event(&env, "referral_reversed");
+    assert_eq!(
+        event,
+        crate::events::ReferralReversedEvent {
+            referrer: referrer.clone(),
+            reversed: 300_000
+        }
+    );
+    assert_eq!(client.referral_tally(&referrer), 0);
+}
+
+// ── Payout Settlement Tests ─────────────────────────────────────────────────
+
+#[test]
+fn test_failed_creator_payout_becomes_claimable() {
+    let (env, client, creator, _token_address, admin) = setup_env();
+
+    // A token whose issuer may deauthorize holders.
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+    sac.issuer()
+        .set_flag(soroban_sdk::testutils::IssuerFlags::RevocableFlag);
+    let token_address = sac.address();
+
+    let platform = Address::generate(&env);
+    let config = crate::PlatformConfig {
+        address: platform.clone(),
+        fee_bps: 500,
+        fee_mode: crate::FeeMode::OnWithdraw,
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &Some(config),
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 1_000_000);
+    client.contribute(&alice, &1_000_000, &0, &None, &false, &None, &None, &None);
+
+    // A deauthorized creator cannot receive the token.
+    let admin_client = token::StellarAssetClient::new(&env, &token_address);
+    admin_client.set_authorized(&creator, &false);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.withdraw();
+    let event: crate::events::PayoutEvent = last_event(&env, "payout_failed");
+    assert_eq!(
+        event,
+        crate::events::PayoutEvent {
+            recipient: creator.clone(),
+            token: token_address.clone(),
+            amount: 950_000,
+        }
+    );
+
+    // The fee settled; the principal is parked and the campaign succeeded.
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&platform), 50_000);
+    assert!(client.campaign_info().status == crate::Status::Successful);
+    assert_eq!(client.pending_payouts().len(), 0);
+    assert_eq!(client.claimable_payout(&creator, &token_address), 950_000);
+
+    admin_client.set_authorized(&creator, &true);
+    let creator_before = token_client.balance(&creator);
+    client.claim_payout(&creator);
+    assert_eq!(token_client.balance(&creator) - creator_before, 950_000);
+    assert_eq!(client.claimable_payout(&creator, &token_address), 0);
+
+    let result = client.try_claim_payout(&creator);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::NothingToClaim
+    );
+}
+
+#[test]
+fn test_settle_rejects_zero_limit() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let result = client.try_settle(&0);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::InvalidLimit
+    );
+    assert_eq!(client.settle(&5), 0);
+}
+
+// ── Per-Address Cap Tests ───────────────────────────────────────────────────
+
+/// Initialize a campaign capping each address at 500_000, with pledges
+/// counted toward the cap when `combined` is set.
+fn setup_capped_campaign(
+    combined: bool,
+) -> (Env, CrowdfundContractClient<'static>, Address, Address) {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let options = crate::CampaignOptions {
+        allow_dust_goal: true,
+        max_contribution_per_address: 500_000,
+        pledges_count_toward_cap: combined,
+        ..Default::default()
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 1_000_000);
+    (env, client, alice, token_address)
+}
+
+#[test]
+fn test_combined_cap_counts_pledges_and_contributions() {
+    let (env, client, alice, _token_address) = setup_capped_campaign(true);
+    assert_eq!(client.contribution_cap(), (500_000, true));
+
+    client.contribute(&alice, &200_000, &0, &None, &false, &None, &None, &None);
+    client.pledge(&alice, &200_000);
+
+    // 200_000 contributed + 200_000 pledged leaves 100_000 of headroom.
+    let result = client.try_pledge(&alice, &100_001);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::MaxContributionExceeded
+    );
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+    let result = client.try_contribute(&alice, &100_001, &0, &None, &false, &None, &None, &None);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::MaxContributionExceeded
+    );
+
+    client.contribute(&alice, &50_000, &0, &None, &false, &None, &None, &None);
+    client.pledge(&alice, &50_000);
+    assert_eq!(client.contribution(&alice), 250_000);
+    assert_eq!(client.pledge_amount(&alice), 250_000);
+}
+
+#[test]
+fn test_independent_cap_limits_each_separately() {
+    let (env, client, alice, _token_address) = setup_capped_campaign(false);
+    assert_eq!(client.contribution_cap(), (500_000, false));
+
+    client.pledge(&alice, &500_000);
+    client.contribute(&alice, &400_000, &0, &None, &false, &None, &None, &None);
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+    let result = client.try_contribute(&alice, &100_001, &0, &None, &false, &None, &None, &None);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::MaxContributionExceeded
+    );
+    let result = client.try_pledge(&alice, &1_000);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::MaxContributionExceeded
+    );
+}
+
+// ── Contribution Dry-Run Tests ──────────────────────────────────────────────
+
+/// Assert that `can_contribute` predicts the outcome of a real
+/// contribution of `amount` by `contributor`.
+fn assert_dry_run_matches(client: &CrowdfundContractClient, contributor: &Address, amount: i128) {
+    let check = client.can_contribute(contributor, &amount);
+    let before = client.contribution(contributor);
+    match client.try_contribute(contributor, &amount, &0, &None, &false, &None, &None, &None) {
+        // Contributions bounced by the cooldown are accepted as 0.
+        Ok(Ok(0)) if !check.ok => {
+            assert_eq!(check.reason, crate::ContractError::RateLimitExceeded as u32);
+            assert_eq!(client.contribution(contributor), before);
+        }
+        Ok(Ok(accepted)) => {
+            assert!(check.ok);
+            assert_eq!(check.accepted_amount, accepted);
+            assert_eq!(client.contribution(contributor) - before, accepted);
+        }
+        Err(Ok(err)) => {
+            assert!(!check.ok);
+            assert_eq!(check.reason, err as u32);
+        }
+        // Amounts below the minimum panic in `contribute`.
+        _ => assert_eq!(check.reason, crate::ContractError::BelowMinimum as u32),
+    }
+}
+
+#[test]
+fn test_can_contribute_agrees_with_contribute() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let options = crate::CampaignOptions {
+        allow_dust_goal: true,
+        max_contribution_per_address: 600_000,
+        start_time: env.ledger().timestamp() + 100,
+        ..Default::default()
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &1_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 2_000_000);
+    mint_to(&env, &token_address, &admin, &bob, 2_000_000);
+
+    // Not started.
+    assert_dry_run_matches(&client, &alice, 10_000);
+    env.ledger().set_timestamp(env.ledger().timestamp() + 100);
+
+    // Below the minimum, then accepted.
+    assert_dry_run_matches(&client, &alice, 999);
+    assert_dry_run_matches(&client, &alice, 500_000);
+    assert!(client.can_contribute(&bob, &500_000).ok);
+
+    // Rate limited, then over the per-address cap.
+    assert_dry_run_matches(&client, &alice, 10_000);
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+    assert_dry_run_matches(&client, &alice, 100_001);
+
+    // Paused.
+    client.set_paused(&true);
+    assert_dry_run_matches(&client, &bob, 10_000);
+    client.set_paused(&false);
+
+    // Truncated to the hard-cap headroom, then the cap is reached.
+    assert_dry_run_matches(&client, &bob, 600_000);
+    assert_eq!(client.contribution(&bob), 500_000);
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+    assert_dry_run_matches(&client, &bob, 10_000);
+
+    // Ended.
+    env.ledger().set_timestamp(deadline + 1);
+    assert_dry_run_matches(&client, &alice, 10_000);
+}
+
+#[test]
+fn test_can_contribute_changes_no_state() {
+    let (env, client, creator, token_address, admin) = setup_env();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+
+    let check = client.can_contribute(&alice, &100_000);
+    assert_eq!(
+        check,
+        crate::ContributeCheck {
+            ok: true,
+            accepted_amount: 100_000,
+            reason: 0
+        }
+    );
+    assert_eq!(client.total_raised(), 0);
+    assert_eq!(client.contribution(&alice), 0);
+    assert_eq!(client.cooldown_remaining(&alice), 0);
+}
+
+// ── Deadline Boundary Tests ─────────────────────────────────────────────────
+
+/// A campaign ending at `deadline` in which alice contributed `raised` and
+/// bob pledged `pledged`, with the ledger then moved to `deadline + offset`.
+fn boundary_campaign(
+    raised: i128,
+    pledged: i128,
+    offset: i64,
+) -> (Env, CrowdfundContractClient<'static>, Address, Address) {
+    let (env, client, creator, token_address, admin) = setup_env();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 1_000_000);
+    mint_to(&env, &token_address, &admin, &bob, 1_000_000);
+    if raised > 0 {
+        client.contribute(&alice, &raised, &0, &None, &false, &None, &None, &None);
+    }
+    if pledged > 0 {
+        client.pledge(&bob, &pledged);
+    }
+
+    env.ledger()
+        .set_timestamp(deadline.checked_add_signed(offset).unwrap());
+    (env, client, alice, bob)
+}
+
+#[test]
+fn test_contribute_and_pledge_accepted_through_deadline_second() {
+    for offset in [-1, 0] {
+        let (_env, client, _alice, bob) = boundary_campaign(0, 0, offset);
+        assert!(client
+            .try_contribute(&bob, &10_000, &0, &None, &false, &None, &None, &None)
+            .is_ok());
+        assert!(client.try_pledge(&bob, &10_000).is_ok());
+    }
+
+    let (_env, client, _alice, bob) = boundary_campaign(0, 0, 1);
+    let result = client.try_contribute(&bob, &10_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::CampaignEnded
+    );
+    let result = client.try_pledge(&bob, &10_000);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::CampaignEnded
+    );
+}
+
+#[test]
+fn test_settlement_opens_after_deadline_second() {
+    let still_active = crate::ContractError::CampaignStillActive;
+    for offset in [-1, 0] {
+        let (_env, client, _alice, _bob) = boundary_campaign(1_000_000, 0, offset);
+        assert_eq!(client.try_withdraw().unwrap_err().unwrap(), still_active);
+        assert_eq!(client.try_finalize().unwrap_err().unwrap(), still_active);
+
+        let (_env, client, _alice, _bob) = boundary_campaign(100_000, 0, offset);
+        assert_eq!(client.try_refund().unwrap_err().unwrap(), still_active);
+
+        let (_env, client, _alice, _bob) = boundary_campaign(0, 1_000_000, offset);
+        assert_eq!(
+            client
+                .try_collect_pledges(&client.campaign_info().creator)
+                .unwrap_err()
+                .unwrap(),
+            still_active
+        );
+    }
+
+    let (_env, client, _alice, _bob) = boundary_campaign(1_000_000, 0, 1);
+    client.withdraw();
+    let (_env, client, _alice, _bob) = boundary_campaign(1_000_000, 0, 1);
+    client.finalize();
+    let (_env, client, _alice, _bob) = boundary_campaign(100_000, 0, 1);
+    client.refund();
+    let (_env, client, _alice, _bob) = boundary_campaign(0, 1_000_000, 1);
+    client.collect_pledges(&client.campaign_info().creator);
+    assert_eq!(client.total_raised(), 1_000_000);
+}
+
+// ── Storage Migration Tests ─────────────────────────────────────────────────
+
+/// Put `key` back in instance storage, as campaigns initialized before the
+/// layout split stored it.
+fn move_to_instance(env: &Env, contract: &Address, key: &crate::DataKey) {
+    env.as_contract(contract, || {
+        let value: soroban_sdk::Val = env.storage().persistent().get(key).unwrap();
+        env.storage().persistent().remove(key);
+        env.storage().instance().set(key, &value);
+    });
+}
+
+#[test]
+fn test_migrate_moves_legacy_instance_entries() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 1_000_000);
+    client.contribute(&alice, &300_000, &0, &None, &false, &None, &None, &None);
+    let tier = soroban_sdk::String::from_str(&env, "Bronze");
+    client.add_reward_tier(&creator, &tier, &10_000, &0);
+    let item = soroban_sdk::String::from_str(&env, "Ship it");
+    client.add_roadmap_item(&(deadline + 100), &item);
+
+    for key in [
+        crate::DataKey::TotalRaised,
+        crate::DataKey::RewardTiers,
+        crate::DataKey::Roadmap,
+    ] {
+        move_to_instance(&env, &client.address, &key);
+    }
+
+    // Views read the legacy layout before migrating.
+    assert_eq!(client.total_raised(), 300_000);
+    assert_eq!(client.campaign_info().total_raised, 300_000);
+    assert_eq!(client.reward_tiers().len(), 1);
+    assert_eq!(client.roadmap().len(), 1);
+
+    assert_eq!(client.migrate(), 3);
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().instance().has(&crate::DataKey::TotalRaised));
+        assert!(env.storage().persistent().has(&crate::DataKey::TotalRaised));
+    });
+    assert_eq!(client.total_raised(), 300_000);
+    assert_eq!(client.reward_tiers().len(), 1);
+    assert_eq!(client.roadmap().len(), 1);
+
+    // Already migrated: nothing left to move.
+    assert_eq!(client.migrate(), 0);
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+    client.contribute(&alice, &100_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(client.total_raised(), 400_000);
+}
+
+#[test]
+fn test_migrate_upgrades_legacy_reward_tiers() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let name = soroban_sdk::String::from_str(&env, "Bronze");
+    env.as_contract(&client.address, || {
+        let legacy = soroban_sdk::vec![
+            &env,
+            crate::LegacyRewardTier {
+                name: name.clone(),
+                min_amount: 10_000,
+            }
+        ];
+        env.storage()
+            .persistent()
+            .set(&crate::DataKey::RewardTiers, &legacy);
+    });
+
+    // Views read the legacy tiers as pending without an estimate.
+    let tier = client.reward_tiers().get(0).unwrap();
+    assert_eq!(tier.name, name);
+    assert_eq!(tier.estimated_delivery, 0);
+    assert_eq!(tier.delivery_status, 0);
+
+    client.migrate();
+    env.as_contract(&client.address, || {
+        let stored: soroban_sdk::Vec<crate::RewardTier> = env
+            .storage()
+            .persistent()
+            .get(&crate::DataKey::RewardTiers)
+            .unwrap();
+        assert_eq!(stored.get(0).unwrap().min_amount, 10_000);
+    });
+    client.add_reward_tier(&creator, &name, &50_000, &0);
+    assert_eq!(client.reward_tiers().len(), 2);
+}
+
+// ── Prorated Refund Tests ───────────────────────────────────────────────────
+
+/// A failed campaign with contributions of 300k, 200k and 100k whose
+/// contract balance was then drained by 100k.
+fn setup_short_campaign() -> (Env, CrowdfundContractClient<'static>, Address, [Address; 3]) {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let contributors = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    for (contributor, amount) in contributors.iter().zip([300_000, 200_000, 100_000]) {
+        mint_to(&env, &token_address, &admin, contributor, amount);
+        client.contribute(contributor, &amount, &0, &None, &false, &None, &None, &None);
+    }
+    token::Client::new(&env, &token_address).burn(&client.address, &100_000);
+
+    env.ledger().set_timestamp(deadline + 1);
+    (env, client, token_address, contributors)
+}
+
+#[test]
+fn test_refund_prorates_when_balance_short() {
+    let (env, client, token_address, contributors) = setup_short_campaign();
+    let token_client = token::Client::new(&env, &token_address);
+
+    client.refund();
+
+    let paid: [i128; 3] = [
+        token_client.balance(&contributors[0]),
+        token_client.balance(&contributors[1]),
+        token_client.balance(&contributors[2]),
+    ];
+    // 5/6 of each contribution, rounded down; the last claimant receives
+    // the rounding remainder.
+    assert_eq!(paid, [250_000, 166_666, 83_334]);
+    assert_eq!(paid.iter().sum::<i128>(), 500_000);
+    assert_eq!(token_client.balance(&client.address), 0);
+    assert_eq!(client.total_raised(), 0);
+}
+
+#[test]
+fn test_claim_refund_uses_ratio_frozen_at_refund_mode() {
+    let (env, client, token_address, contributors) = setup_short_campaign();
+    let token_client = token::Client::new(&env, &token_address);
+
+    client.claim_refund(&contributors[1]);
+    let event: crate::events::ProratedRefundEvent = last_event(&env, "prorated_refund");
+    assert_eq!(
+        event,
+        crate::events::ProratedRefundEvent {
+            contributor: contributors[1].clone(),
+            contribution: 200_000,
+            refunded: 166_666,
+            balance: 500_000,
+            total_raised: 600_000,
+        }
+    );
+
+    // Later claims keep the ratio frozen at the first claim even though
+    // the balance has since dropped.
+    client.claim_refund(&contributors[0]);
+    client.claim_refund(&contributors[2]);
+    assert_eq!(token_client.balance(&contributors[0]), 250_000);
+    assert_eq!(token_client.balance(&contributors[1]), 166_666);
+    assert_eq!(token_client.balance(&contributors[2]), 83_334);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+#[test]
+fn test_refund_not_prorated_when_balance_covers_principal() {
+    use soroban_sdk::testutils::Events;
+
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 300_000);
+    client.contribute(&alice, &300_000, &0, &None, &false, &None, &None, &None);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.claim_refund(&alice);
+    assert_eq!(
+        token::Client::new(&env, &token_address).balance(&alice),
+        300_000
+    );
+    let prorated = campaign_topics(&env, "prorated_refund");
+    assert!(!env
+        .events()
+        .all()
+        .iter()
+        .any(|(_, topics, _)| topics == prorated));
+}
+
+// ── Tier Change Tests ───────────────────────────────────────────────────────
+
+/// `tier_changed` events published by the last invocation.
+fn tier_changed_events(env: &Env) -> soroban_sdk::Vec<crate::events::TierChangedEvent> {
+    use soroban_sdk::{testutils::Events, FromVal};
+
+    let topics = campaign_topics(env, "tier_changed");
+    let mut changes = soroban_sdk::Vec::new(env);
+    for (_, t, data) in env.events().all().iter() {
+        if t == topics {
+            changes.push_back(crate::events::TierChangedEvent::from_val(env, &data));
+        }
+    }
+    changes
+}
+
+#[test]
+fn test_top_up_from_bronze_to_gold_emits_one_tier_change() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+    let bronze = soroban_sdk::String::from_str(&env, "Bronze");
+    let gold = soroban_sdk::String::from_str(&env, "Gold");
+    client.add_reward_tier(&creator, &bronze, &10_000, &0);
+    client.add_reward_tier(&creator, &gold, &500_000, &0);
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 1_000_000);
+    client.contribute(&alice, &20_000, &0, &None, &false, &None, &None, &None);
+    let first = tier_changed_events(&env);
+    assert_eq!(first.len(), 1);
+    assert_eq!(first.get(0).unwrap().old_tier, None);
+    assert_eq!(first.get(0).unwrap().new_tier, Some(bronze.clone()));
+
+    // Staying within Bronze is not a change.
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+    client.contribute(&alice, &30_000, &0, &None, &false, &None, &None, &None);
+    assert!(tier_changed_events(&env).is_empty());
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+    client.contribute(&alice, &500_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(
+        tier_changed_events(&env),
+        soroban_sdk::vec![
+            &env,
+            crate::events::TierChangedEvent {
+                contributor: alice.clone(),
+                old_tier: Some(bronze),
+                new_tier: Some(gold.clone()),
+            }
+        ]
+    );
+    assert_eq!(client.get_user_tier(&alice), Some(gold));
+}
+
+#[test]
+fn test_refund_emits_tier_downgrade() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+    let gold = soroban_sdk::String::from_str(&env, "Gold");
+    client.add_reward_tier(&creator, &gold, &500_000, &0);
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 600_000);
+    client.contribute(&alice, &600_000, &0, &None, &false, &None, &None, &None);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.claim_refund(&alice);
+    assert_eq!(
+        tier_changed_events(&env),
+        soroban_sdk::vec![
+            &env,
+            crate::events::TierChangedEvent {
+                contributor: alice,
+                old_tier: Some(gold),
+                new_tier: None,
+            }
+        ]
+    );
+}
+
+#[test]
+fn test_anonymous_contribution_records_tier_silently() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+    let bronze = soroban_sdk::String::from_str(&env, "Bronze");
+    client.add_reward_tier(&creator, &bronze, &10_000, &0);
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    client.contribute(&alice, &20_000, &0, &None, &true, &None, &None, &None);
+    assert!(tier_changed_events(&env).is_empty());
+    assert_eq!(client.get_user_tier(&alice), Some(bronze));
+}
+
+#[test]
+fn test_anonymous_backer_tier_drops_silently() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let platform = Address::generate(&env);
+    let config = crate::PlatformConfig {
+        address: platform,
+        fee_bps: 500,
+        fee_mode: crate::FeeMode::OnWithdraw,
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &Some(config),
+        &dust_goal_options(),
+        &None,
+    );
+    let bronze = soroban_sdk::String::from_str(&env, "Bronze");
+    let gold = soroban_sdk::String::from_str(&env, "Gold");
+    client.add_reward_tier(&creator, &bronze, &10_000, &0);
+    client.add_reward_tier(&creator, &gold, &500_000, &0);
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 600_000);
+    client.contribute(&alice, &600_000, &0, &None, &true, &None, &None, &None);
+
+    // A reversal from Gold to Bronze does not reveal the backer.
+    client.reverse_contribution(&alice, &200_000, &7);
+    assert!(tier_changed_events(&env).is_empty());
+    assert_eq!(client.get_user_tier(&alice), Some(bronze));
+
+    // Nor does the refund that drops them out of the tiers.
+    env.ledger().set_timestamp(deadline + 1);
+    client.claim_refund(&alice);
+    assert!(tier_changed_events(&env).is_empty());
+    assert_eq!(client.get_user_tier(&alice), None);
+}
+
+// ── Batch Setter Tests ──────────────────────────────────────────────────────
+
+#[test]
+#[should_panic(expected = "too many reward tiers")]
+fn test_set_reward_tiers_enforces_cap() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let mut tiers = soroban_sdk::Vec::new(&env);
+    for i in 1..=21 {
+        tiers.push_back(crate::RewardTier {
+            name: soroban_sdk::String::from_str(&env, "Tier"),
+            min_amount: i * 1_000,
+            estimated_delivery: 0,
+            delivery_status: 0,
+        });
+    }
+    client.set_reward_tiers(&creator, &tiers);
+}
+
+#[test]
+#[should_panic(expected = "min_amount must be greater than 0")]
+fn test_set_reward_tiers_validates_each_tier() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let tiers = soroban_sdk::vec![
+        &env,
+        crate::RewardTier {
+            name: soroban_sdk::String::from_str(&env, "Bronze"),
+            min_amount: 1_000,
+            estimated_delivery: 0,
+            delivery_status: 0,
+        },
+        crate::RewardTier {
+            name: soroban_sdk::String::from_str(&env, "Free"),
+            min_amount: 0,
+            estimated_delivery: 0,
+            delivery_status: 0,
+        },
+    ];
+    client.set_reward_tiers(&creator, &tiers);
+}
+
+#[test]
+#[should_panic(expected = "date must be in the future")]
+fn test_set_roadmap_validates_each_item() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let items = soroban_sdk::vec![
+        &env,
+        crate::RoadmapItem {
+            date: deadline,
+            description: soroban_sdk::String::from_str(&env, "Build"),
+        },
+        crate::RoadmapItem {
+            date: env.ledger().timestamp(),
+            description: soroban_sdk::String::from_str(&env, "Ship"),
+        },
+    ];
+    client.set_roadmap(&items);
+}
+
+#[test]
+fn test_set_roadmap_replaces_timeline() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+    client.add_roadmap_item(&deadline, &soroban_sdk::String::from_str(&env, "Old"));
+
+    let items = soroban_sdk::vec![
+        &env,
+        crate::RoadmapItem {
+            date: deadline + 10,
+            description: soroban_sdk::String::from_str(&env, "Build"),
+        },
+        crate::RoadmapItem {
+            date: deadline + 20,
+            description: soroban_sdk::String::from_str(&env, "Ship"),
+        },
+    ];
+    client.set_roadmap(&items);
+    let roadmap = client.roadmap();
+    assert_eq!(roadmap.len(), 2);
+    assert_eq!(roadmap.get(0).unwrap().date, deadline + 10);
+}
+
+// ── Heartbeat Tests ─────────────────────────────────────────────────────────
+
+/// A funded vesting campaign with a 1,000s heartbeat interval that turned
+/// Successful with a first vested withdrawal at `deadline + 1`.
+fn setup_heartbeat_campaign() -> (Env, CrowdfundContractClient<'static>, Address, u64) {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let options = crate::CampaignOptions {
+        vest_duration_secs: 10_000,
+        heartbeat_interval_secs: 1_000,
+        allow_dust_goal: true,
+        ..Default::default()
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &1_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+
+    let contributor = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &contributor, 1_000_000);
+    client.contribute(
+        &contributor,
+        &1_000_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.withdraw_vested();
+    (env, client, contributor, deadline + 1)
+}
+
+#[test]
+fn test_missed_heartbeat_freezes_vesting_until_next_heartbeat() {
+    let (env, client, contributor, succeeded_at) = setup_heartbeat_campaign();
+    assert_eq!(client.last_heartbeat(), Some(succeeded_at));
+
+    // Within the grace period (1.5x the interval) nobody can flag.
+    env.ledger().set_timestamp(succeeded_at + 1_500);
+    assert!(!client.is_inactive());
+    let result = client.try_flag_inactive(&contributor);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::HeartbeatNotOverdue
+    );
+
+    env.ledger().set_timestamp(succeeded_at + 1_501);
+    assert!(client.is_inactive());
+    client.flag_inactive(&contributor);
+    let event: crate::events::InactiveCreatorEvent = last_event(&env, "inactive_creator");
+    assert_eq!(
+        event,
+        crate::events::InactiveCreatorEvent {
+            contributor: contributor.clone(),
+            last_heartbeat: succeeded_at,
+        }
+    );
+    let result = client.try_withdraw_vested();
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::CreatorInactive
+    );
+
+    client.heartbeat();
+    assert_eq!(client.last_heartbeat(), Some(succeeded_at + 1_501));
+    assert!(!client.is_inactive());
+    client.withdraw_vested();
+    assert_eq!(client.withdrawn_amount(), 150_200);
+}
+
+#[test]
+fn test_regular_heartbeats_keep_creator_active() {
+    let (env, client, contributor, succeeded_at) = setup_heartbeat_campaign();
+
+    for beat in 1..=3 {
+        env.ledger().set_timestamp(succeeded_at + beat * 1_000);
+        client.heartbeat();
+    }
+    env.ledger().set_timestamp(succeeded_at + 4_400);
+    assert!(!client.is_inactive());
+    let result = client.try_flag_inactive(&contributor);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::HeartbeatNotOverdue
+    );
+}
+
+#[test]
+#[should_panic(expected = "campaign is not successful")]
+fn test_heartbeat_before_success_panics() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+    let options = crate::CampaignOptions {
+        heartbeat_interval_secs: 1_000,
+        allow_dust_goal: true,
+        ..Default::default()
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &1_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+    client.heartbeat();
+}
+
+// ── Storage TTL Tests ───────────────────────────────────────────────────────
+
+/// TTL, in ledgers, left on a persistent entry of the campaign.
+fn persistent_ttl<K: soroban_sdk::IntoVal<Env, soroban_sdk::Val>>(
+    env: &Env,
+    client: &CrowdfundContractClient,
+    key: &K,
+) -> u32 {
+    use soroban_sdk::testutils::storage::Persistent;
+
+    env.as_contract(&client.address, || env.storage().persistent().get_ttl(key))
+}
+
+#[test]
+fn test_refund_record_outlives_old_ttl_horizon() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    client.contribute(&alice, &100_000, &0, &None, &false, &None, &None, &None);
+    // Active records live until well past the deadline.
+    let contribution_key = crate::DataKey::Contribution(alice.clone());
+    assert!(persistent_ttl(&env, &client, &contribution_key) > 3600 / 5);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.claim_refund(&alice);
+    let refunded_key = crate::RecordKey::Refunded(alice.clone());
+    assert!(persistent_ttl(&env, &client, &refunded_key) >= 6_000_000);
+
+    // Far past the old 100-ledger bump, the history is still readable.
+    env.as_contract(&client.address, || {
+        env.storage().instance().extend_ttl(200_000, 200_000);
+    });
+    env.ledger()
+        .with_mut(|ledger| ledger.sequence_number += 100_000);
+    assert_eq!(client.refunded_amount(&alice), 100_000);
+    assert!(client.was_refunded(&alice));
+}
+
+#[test]
+fn test_extend_storage_rebumps_audit_records() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    client.contribute(&alice, &100_000, &0, &None, &false, &None, &None, &None);
+    env.ledger().set_timestamp(deadline + 1);
+    client.claim_refund(&alice);
+
+    env.as_contract(&client.address, || {
+        env.storage().instance().extend_ttl(600_000, 600_000);
+    });
+    env.ledger()
+        .with_mut(|ledger| ledger.sequence_number += 500_000);
+    let refunded_key = crate::RecordKey::Refunded(alice.clone());
+    let before = persistent_ttl(&env, &client, &refunded_key);
+    assert_eq!(client.extend_storage(&0, &10), 1);
+    assert_eq!(
+        persistent_ttl(&env, &client, &refunded_key),
+        before + 500_000
+    );
+}
+
+// ── Idempotent Initialize Tests ─────────────────────────────────────────────
+
+#[test]
+fn test_initialize_retry_with_same_nonce_succeeds() {
+    let (env, client, creator, token_address, _admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    let nonce = Some(soroban_sdk::BytesN::from_array(&env, &[7u8; 32]));
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &nonce,
+    );
+
+    // A retry of the same deployment is a no-op.
+    let result = client.try_initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &nonce,
+    );
+    assert_eq!(result, Ok(Ok(())));
+    assert_eq!(client.goal(), 1_000_000);
+
+    // Changed arguments or a different nonce are still rejected.
+    let result = client.try_initialize(
+        &creator,
+        &token_address,
+        &2_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &nonce,
+    );
+    assert_eq!(result, Err(Ok(crate::ContractError::AlreadyInitialized)));
+    let other = Some(soroban_sdk::BytesN::from_array(&env, &[8u8; 32]));
+    let result = client.try_initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &other,
+    );
+    assert_eq!(result, Err(Ok(crate::ContractError::AlreadyInitialized)));
+    assert_eq!(client.goal(), 1_000_000);
+}
+
+// ── Account Age Tests ───────────────────────────────────────────────────────
+
+#[test]
+fn test_new_account_earns_no_referral_credit_until_aged() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    let options = crate::CampaignOptions {
+        allow_dust_goal: true,
+        min_first_seen_ledgers: 100,
+        ..Default::default()
+    };
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+
+    let referrer = Address::generate(&env);
+    let newcomer = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &newcomer, 100_000);
+    assert_eq!(client.first_seen_ledger(&newcomer), None);
+
+    // The contribution itself is accepted; only the referral is skipped.
+    client.contribute(
+        &newcomer,
+        &10_000,
+        &0,
+        &Some(referrer.clone()),
+        &false,
+        &None,
+        &None,
+        &None,
+    );
+    let first_seen = env.ledger().sequence();
+    assert_eq!(
+        last_event::<crate::events::BonusSkippedEvent>(&env, "bonus_skipped"),
+        crate::events::BonusSkippedEvent {
+            contributor: Some(newcomer.clone()),
+            bonus: soroban_sdk::Symbol::new(&env, "referral"),
+            reason: 1,
+            eligible_ledger: first_seen + 100,
+        }
+    );
+    assert_eq!(client.first_seen_ledger(&newcomer), Some(first_seen));
+    assert_eq!(client.contribution(&newcomer), 10_000);
+    assert_eq!(client.referral_tally(&referrer), 0);
+
+    env.ledger().with_mut(|ledger| {
+        ledger.sequence_number += 99;
+        ledger.timestamp += 10;
+    });
+    client.contribute(
+        &newcomer,
+        &10_000,
+        &0,
+        &Some(referrer.clone()),
+        &false,
+        &None,
+        &None,
+        &None,
+    );
+    assert_eq!(client.referral_tally(&referrer), 0);
+
+    env.ledger().with_mut(|ledger| {
+        ledger.sequence_number += 1;
+        ledger.timestamp += 10;
+    });
+    client.contribute(
+        &newcomer,
+        &10_000,
+        &0,
+        &Some(referrer.clone()),
+        &false,
+        &None,
+        &None,
+        &None,
+    );
+    assert_eq!(client.referral_tally(&referrer), 10_000);
+    assert_eq!(client.first_seen_ledger(&newcomer), Some(first_seen));
+}
+
+// ── Refund Top-Up Tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_refund_top_up_makes_short_refunds_whole() {
+    let (env, client, token_address, contributors) = setup_short_campaign();
+    let token_client = token::Client::new(&env, &token_address);
+    let creator = client.campaign_info().creator;
+
+    client.claim_refund(&contributors[1]);
+    assert_eq!(token_client.balance(&contributors[1]), 166_666);
+
+    token::StellarAssetClient::new(&env, &token_address).mint(&creator, &100_000);
+    client.fund_refund_pool(&creator, &100_000);
+    assert_eq!(
+        last_event::<crate::events::RefundPoolFundedEvent>(&env, "refund_pool_funded"),
+        crate::events::RefundPoolFundedEvent {
+            from: creator.clone(),
+            amount: 100_000,
+            funded: 100_000,
+        }
+    );
+    assert_eq!(client.refund_top_up_available(), 100_000);
+
+    // An already refunded backer claims the difference.
+    client.claim_refund(&contributors[1]);
+    assert_eq!(token_client.balance(&contributors[1]), 200_000);
+    assert_eq!(
+        client.try_claim_refund(&contributors[1]),
+        Err(Ok(crate::ContractError::AlreadyRefunded))
+    );
+
+    // Later claims receive the prorated refund plus their top-up.
+    client.claim_refund(&contributors[0]);
+    client.claim_refund(&contributors[2]);
+    assert_eq!(token_client.balance(&contributors[0]), 300_000);
+    assert_eq!(token_client.balance(&contributors[2]), 100_000);
+    assert_eq!(client.refund_top_up_available(), 0);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+#[test]
+fn test_unpaid_refund_top_up_returns_to_funder_at_sweep() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let recipient = Address::generate(&env);
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &refund_window_options(&env, &recipient),
+        &None,
+    );
+    let token_client = token::Client::new(&env, &token_address);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 300_000);
+    mint_to(&env, &token_address, &admin, &bob, 100_000);
+    client.contribute(&alice, &300_000, &0, &None, &false, &None, &None, &None);
+    client.contribute(&bob, &100_000, &0, &None, &false, &None, &None, &None);
+    token_client.burn(&client.address, &100_000);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.claim_refund(&alice);
+    assert_eq!(token_client.balance(&alice), 225_000);
+
+    // Half the 100,000 shortfall is topped up; alice gets half of hers.
+    let creator_balance = token_client.balance(&creator);
+    client.fund_refund_pool(&creator, &50_000);
+    client.claim_refund(&alice);
+    assert_eq!(token_client.balance(&alice), 262_500);
+
+    // Bob never claims; at the sweep his top-up share goes back to the
+    // creator and only the unclaimed refund reaches the recipient.
+    env.ledger().set_timestamp(deadline + 1_002);
+    assert_eq!(
+        client.try_fund_refund_pool(&creator, &10_000),
+        Err(Ok(crate::ContractError::RefundWindowClosed))
+    );
+    assert_eq!(client.sweep_unclaimed(), 75_000);
+    assert_eq!(
+        token_client.balance(&creator),
+        creator_balance - 50_000 + 12_500
+    );
+    assert_eq!(token_client.balance(&recipient), 75_000);
+}
+
+#[test]
+#[should_panic(expected = "campaign is not refunded")]
+fn test_fund_refund_pool_requires_refunded_campaign() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    mint_to(&env, &token_address, &admin, &creator, 10_000);
+    client.fund_refund_pool(&creator, &10_000);
+}
+
+// ── Fulfillment Escrow Tests ────────────────────────────────────────────────
+
+fn escrow_options(env: &Env, approval_required_bps: u32) -> Option<crate::CampaignOptions> {
+    Some(crate::CampaignOptions {
+        admin: Some(Address::generate(env)),
+        fulfillment_escrow_bps: 1_000,
+        escrow_release_delay_secs: 30 * 24 * 60 * 60,
+        approval_required_bps,
+        allow_dust_goal: true,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_withdraw_retains_escrow_until_delay() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let platform = Address::generate(&env);
+    let config = crate::PlatformConfig {
+        address: platform.clone(),
+        fee_bps: 250,
+        fee_mode: crate::FeeMode::OnWithdraw,
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &Some(config),
+        &escrow_options(&env, 0),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 1_000_000);
+    client.contribute(&alice, &1_000_000, &0, &None, &false, &None, &None, &None);
+
+    env.ledger().set_timestamp(deadline + 1);
+    let preview = client.withdraw_preview();
+    assert_eq!(preview.escrow, 100_000);
+    assert_eq!(preview.net_to_creator, 875_000);
+
+    let token_client = token::Client::new(&env, &token_address);
+    let creator_before = token_client.balance(&creator);
+    client.withdraw();
+    assert_eq!(last_event::<i128>(&env, "escrow_retained"), 100_000);
+    assert_eq!(token_client.balance(&creator) - creator_before, 875_000);
+    assert_eq!(token_client.balance(&platform), 25_000);
+    assert_eq!(token_client.balance(&client.address), 100_000);
+    assert_eq!(client.escrow_balance(), 100_000);
+
+    assert_eq!(
+        client.try_release_escrow(),
+        Err(Ok(crate::ContractError::EscrowLocked))
+    );
+    env.ledger().set_timestamp(deadline + 1 + 30 * 24 * 60 * 60);
+    assert_eq!(client.release_escrow(), 100_000);
+    assert_eq!(token_client.balance(&creator) - creator_before, 975_000);
+    assert_eq!(client.escrow_balance(), 0);
+}
+
+#[test]
+fn test_backer_approval_releases_escrow_early() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &escrow_options(&env, 5_000),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 600_000);
+    mint_to(&env, &token_address, &admin, &bob, 400_000);
+    client.contribute(&alice, &600_000, &0, &None, &false, &None, &None, &None);
+    client.contribute(&bob, &400_000, &0, &None, &false, &None, &None, &None);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.approve_withdrawal(&alice);
+    client.withdraw();
+    assert_eq!(client.escrow_balance(), 100_000);
+
+    client.approve_escrow_release(&bob);
+    assert_eq!(
+        client.try_release_escrow(),
+        Err(Ok(crate::ContractError::EscrowLocked))
+    );
+    client.approve_escrow_release(&alice);
+    assert_eq!(client.release_escrow(), 100_000);
+}
+
+#[test]
+fn test_admin_arbitration_refunds_escrow_to_backers() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &escrow_options(&env, 0),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 700_000);
+    mint_to(&env, &token_address, &admin, &bob, 500_000);
+    client.contribute(&alice, &700_000, &0, &None, &false, &None, &None, &None);
+    client.contribute(&bob, &500_000, &0, &None, &false, &None, &None, &None);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.withdraw();
+    assert_eq!(client.escrow_balance(), 120_000);
+
+    assert_eq!(
+        client.try_arbitrate_escrow(&false),
+        Err(Ok(crate::ContractError::EscrowLocked))
+    );
+    env.ledger()
+        .set_timestamp(deadline + 1 + 180 * 24 * 60 * 60);
+    client.arbitrate_escrow(&false);
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&alice), 70_000);
+    assert_eq!(token_client.balance(&bob), 50_000);
+    assert_eq!(client.escrow_balance(), 0);
+}
+
+// ── Cooldown Bypass Tests ───────────────────────────────────────────────────
+
+#[test]
+fn test_small_top_ups_bypass_cooldown() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    let options = crate::CampaignOptions {
+        cooldown_bypass_amount: 5_000,
+        allow_dust_goal: true,
+        ..Default::default()
+    };
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    client.contribute(&alice, &2_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(client.cooldown_bypass_remaining(&alice), 3_000);
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 1);
+    client.contribute(&alice, &2_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(client.contribution(&alice), 4_000);
+    assert_eq!(client.cooldown_bypass_remaining(&alice), 1_000);
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 1);
+    assert_eq!(
+        client.contribute(&alice, &10_000, &0, &None, &false, &None, &None, &None),
+        0
+    );
+    assert_eq!(client.contribution(&alice), 4_000);
+
+    // Once the cooldown has passed, a fresh window opens.
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+    assert_eq!(client.cooldown_bypass_remaining(&alice), 5_000);
+    client.contribute(&alice, &10_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(client.contribution(&alice), 14_000);
+}
+
+// ── Source Tag Tests ────────────────────────────────────────────────────────
+
+#[test]
+fn test_source_tags_aggregate_with_unknown_as_other() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    use crate::events::ContributedEvent;
+    use soroban_sdk::Symbol;
+
+    let twitter = Symbol::new(&env, "twitter");
+    let newsletter = Symbol::new(&env, "newsletter_42");
+    let other = Symbol::new(&env, "other");
+    client.register_source_tag(&twitter);
+    client.register_source_tag(&newsletter);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    mint_to(&env, &token_address, &admin, &bob, 100_000);
+    mint_to(&env, &token_address, &admin, &carol, 100_000);
+
+    client.contribute(
+        &alice,
+        &10_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &Some(twitter.clone()),
+        &None,
+    );
+    client.contribute(
+        &bob,
+        &20_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &Some(twitter.clone()),
+        &None,
+    );
+    client.contribute(
+        &carol,
+        &5_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &Some(newsletter.clone()),
+        &None,
+    );
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+    client.contribute(
+        &carol,
+        &7_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &Some(Symbol::new(&env, "spam")),
+        &None,
+    );
+    assert_eq!(
+        last_event::<ContributedEvent>(&env, "contributed").source_tag,
+        Some(other.clone())
+    );
+
+    assert_eq!(client.source_tally(&twitter), 30_000);
+    assert_eq!(client.source_tally(&newsletter), 5_000);
+    assert_eq!(client.source_tally(&other), 7_000);
+    assert_eq!(client.source_tally(&Symbol::new(&env, "spam")), 0);
+    assert_eq!(
+        client.source_tags(),
+        soroban_sdk::vec![&env, twitter, newsletter, other]
+    );
+}
+
+// ── Health Check Tests ──────────────────────────────────────────────────────
+
+#[test]
+fn test_health_check_reports_corrupted_state() {
+    use soroban_sdk::testutils::Events;
+
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    mint_to(&env, &token_address, &admin, &bob, 100_000);
+    client.contribute(&alice, &50_000, &0, &None, &false, &None, &None, &None);
+    client.pledge(&bob, &20_000);
+    assert_eq!(client.health_check(&0), soroban_sdk::vec![&env]);
+
+    // Raised beyond the hard cap and the tokens held, with a contributor
+    // count and pledge total that disagree with their records.
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&crate::DataKey::TotalRaised, &3_000_000i128);
+        env.storage()
+            .instance()
+            .set(&crate::DataKey::ActiveContributorCount, &2u32);
+        env.storage()
+            .persistent()
+            .set(&crate::DataKey::TotalPledged, &5_000i128);
+    });
+    assert_eq!(
+        client.health_check(&0),
+        soroban_sdk::vec![
+            &env,
+            crate::HEALTH_BALANCE_SHORT,
+            crate::HEALTH_OVER_HARD_CAP,
+            crate::HEALTH_CONTRIBUTOR_COUNT,
+            crate::HEALTH_PLEDGE_TOTAL,
+        ]
+    );
+    assert_eq!(
+        env.events()
+            .all()
+            .iter()
+            .filter(|(_, topics, _)| *topics == campaign_topics(&env, "health_violation"))
+            .count(),
+        4
+    );
+
+    // A Successful campaign still before its deadline.
+    env.as_contract(&client.address, || {
+        env.storage()
+            .instance()
+            .set(&crate::DataKey::Status, &crate::Status::Successful);
+    });
+    assert_eq!(
+        client.health_check(&0),
+        soroban_sdk::vec![
+            &env,
+            crate::HEALTH_OVER_HARD_CAP,
+            crate::HEALTH_STATUS_DEADLINE
+        ]
+    );
+}
+
+// ── Circuit Breaker Tests ───────────────────────────────────────────────────
+
+#[test]
+fn test_circuit_breaker_trips_on_third_contribution() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+    client.set_circuit_breaker(&Some(crate::CircuitBreaker {
+        max_inflow: 250_000,
+        window_secs: 600,
+    }));
+
+    let backers = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    for backer in backers.iter() {
+        mint_to(&env, &token_address, &admin, backer, 100_000);
+    }
+    let start = env.ledger().timestamp();
+    assert_eq!(
+        client.contribute(
+            &backers[0],
+            &100_000,
+            &0,
+            &None,
+            &false,
+            &None,
+            &None,
+            &None
+        ),
+        100_000
+    );
+    assert_eq!(
+        client.contribute(
+            &backers[1],
+            &100_000,
+            &0,
+            &None,
+            &false,
+            &None,
+            &None,
+            &None
+        ),
+        100_000
+    );
+    assert_eq!(client.inflow_window(), (200_000, start + 600));
+
+    // The third pushes the window past the limit: refused, and paused.
+    assert_eq!(
+        client.contribute(
+            &backers[2],
+            &100_000,
+            &0,
+            &None,
+            &false,
+            &None,
+            &None,
+            &None
+        ),
+        0
+    );
+    assert_eq!(last_event::<i128>(&env, "circuit_breaker_tripped"), 300_000);
+    assert_eq!(
+        token::Client::new(&env, &token_address).balance(&backers[2]),
+        100_000
+    );
+    assert_eq!(client.total_raised(), 200_000);
+    assert_eq!(
+        client
+            .try_contribute(
+                &backers[2],
+                &100_000,
+                &0,
+                &None,
+                &false,
+                &None,
+                &None,
+                &None
+            )
+            .unwrap_err()
+            .unwrap(),
+        crate::ContractError::ContractPaused
+    );
+
+    // Unpausing resets the window.
+    client.reset_circuit_breaker(&creator);
+    assert_eq!(client.inflow_window().0, 0);
+    client.contribute(
+        &backers[2],
+        &100_000,
+        &0,
+        &None,
+        &false,
+        &None,
+        &None,
+        &None,
+    );
+    assert_eq!(client.total_raised(), 300_000);
+}
+
+#[test]
+fn test_circuit_breaker_trips_on_batch_total() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+    client.set_circuit_breaker(&Some(crate::CircuitBreaker {
+        max_inflow: 250_000,
+        window_secs: 600,
+    }));
+
+    let exchange = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &exchange, 500_000);
+    let entries = soroban_sdk::vec![
+        &env,
+        (Address::generate(&env), 100_000i128),
+        (Address::generate(&env), 100_000i128)
+    ];
+    client.contribute_batch(&exchange, &entries);
+    assert_eq!(client.inflow_window().0, 200_000);
+
+    // The next batch would take the window to 400_000: refused, and paused.
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+    let accepted = client.contribute_batch(&exchange, &entries);
+    assert_eq!(accepted, soroban_sdk::vec![&env, 0i128, 0i128]);
+    assert_eq!(last_event::<i128>(&env, "circuit_breaker_tripped"), 400_000);
+    assert_eq!(client.total_raised(), 200_000);
+    assert_eq!(
+        token::Client::new(&env, &token_address).balance(&exchange),
+        300_000
+    );
+    assert_eq!(
+        client
+            .try_contribute_batch(&exchange, &entries)
+            .unwrap_err()
+            .unwrap(),
+        crate::ContractError::ContractPaused
+    );
+}
+
+// ── Contribution Reversal Tests ─────────────────────────────────────────────
+
+/// A campaign with a 5% platform fee and one 600_000 contribution from the
+/// returned backer.
+fn setup_reversible_campaign() -> (
+    Env,
+    CrowdfundContractClient<'static>,
+    Address,
+    Address,
+    Address,
+) {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let platform = Address::generate(&env);
+    let config = crate::PlatformConfig {
+        address: platform.clone(),
+        fee_bps: 500,
+        fee_mode: crate::FeeMode::OnWithdraw,
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &Some(config),
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 1_000_000);
+    client.contribute(&alice, &600_000, &0, &None, &false, &None, &None, &None);
+    (env, client, platform, token_address, alice)
+}
+
+#[test]
+fn test_platform_reverses_contribution_mid_campaign() {
+    let (env, client, platform, token_address, alice) = setup_reversible_campaign();
+
+    // The reversal is capped at the recorded contribution.
+    assert_eq!(client.reverse_contribution(&alice, &200_000, &7), 200_000);
+    assert_eq!(
+        last_event::<crate::events::ContributionReversedEvent>(&env, "contribution_reversed"),
+        crate::events::ContributionReversedEvent {
+            contributor: alice.clone(),
+            amount: 200_000,
+            reason: 7,
+        }
+    );
+    assert_eq!(client.reverse_contribution(&alice, &500_000, &7), 400_000);
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&platform), 600_000);
+    assert_eq!(client.contribution(&alice), 0);
+    assert_eq!(client.total_raised(), 0);
+    assert_eq!(client.contributor_count(), 0);
+    assert_eq!(
+        client.try_reverse_contribution(&alice, &1_000, &7),
+        Err(Ok(crate::ContractError::NothingToClaim))
+    );
+}
+
+#[test]
+fn test_reversed_contributor_recontributes_with_skip_cooldown_for_new() {
+    let (_env, client, _platform, _token_address, alice) = setup_reversible_campaign();
+    client.reverse_contribution(&alice, &600_000, &7);
+    assert_eq!(
+        client.contribute(&alice, &100_000, &0, &None, &false, &None, &None, &None),
+        0
+    );
+
+    client.set_skip_cooldown_for_new(&true);
+    assert_eq!(client.cooldown_remaining(&alice), 0);
+    client.contribute(&alice, &100_000, &0, &None, &false, &None, &None, &None);
+
+    // Holding a contribution again subjects them to the cooldown.
+    assert!(client.cooldown_remaining(&alice) > 0);
+}
+
+#[test]
+fn test_refund_clears_contribution_cooldown() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    env.ledger().set_timestamp(deadline);
+    client.contribute(&alice, &100_000, &0, &None, &false, &None, &None, &None);
+    assert!(client.cooldown_remaining(&alice) > 0);
+
+    env.ledger().set_timestamp(deadline + 1);
+    client.claim_refund(&alice);
+    assert_eq!(client.cooldown_remaining(&alice), 0);
+}
+
+#[test]
+fn test_waived_platform_fee_is_not_charged_at_withdrawal() {
+    let (env, client, platform, token_address, alice) = setup_reversible_campaign();
+
+    client.waive_platform_fee();
+    assert_eq!(
+        last_event::<crate::events::FeeWaivedEvent>(&env, "fee_waived"),
+        crate::events::FeeWaivedEvent {
+            platform: platform.clone(),
+            original_fee_bps: 500,
+        }
+    );
+    assert_eq!(client.waived_fee_bps(), Some(500));
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+    client.contribute(&alice, &400_000, &0, &None, &false, &None, &None, &None);
+    env.ledger().set_timestamp(client.deadline() + 1);
+    assert_eq!(client.withdraw_preview().platform_fee, 0);
+
+    let token_client = token::Client::new(&env, &token_address);
+    client.withdraw();
+    assert_eq!(token_client.balance(&platform), 0);
+    assert_eq!(client.paid_to_creator(), 1_000_000);
+}
+
+#[test]
+fn test_platform_fee_waiver_is_irreversible() {
+    use soroban_sdk::testutils::Events;
+
+    let (env, client, _platform, _token_address, _alice) = setup_reversible_campaign();
+
+    client.waive_platform_fee();
+    // A second waiver changes nothing and publishes nothing.
+    client.waive_platform_fee();
+    assert!(env.events().all().is_empty());
+    assert_eq!(client.waived_fee_bps(), Some(500));
+
+    // Nothing restores the fee: the platform config can only be set by
+    // `initialize`, which rejects a second call.
+    let config = crate::PlatformConfig {
+        address: Address::generate(&env),
+        fee_bps: 500,
+        fee_mode: crate::FeeMode::OnWithdraw,
+    };
+    let info = client.campaign_info();
+    let deadline = client.deadline();
+    assert_eq!(
+        client.try_initialize(
+            &info.creator,
+            &info.token,
+            &1_000_000,
+            &2_000_000,
+            &deadline,
+            &1_000,
+            &Some(config),
+            &dust_goal_options(),
+            &None,
+        ),
+        Err(Ok(crate::ContractError::AlreadyInitialized))
+    );
+    assert_eq!(client.waived_fee_bps(), Some(500));
+}
+
+#[test]
+fn test_contributor_cap_frees_slot_on_reversal() {
+    let (env, client, _platform, token_address, alice) = setup_reversible_campaign();
+    assert_eq!(client.slots_remaining(), None);
+    client.set_max_contributors(&2);
+    assert_eq!(client.slots_remaining(), Some(1));
+
+    let admin = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &bob, 100_000);
+    mint_to(&env, &token_address, &admin, &carol, 100_000);
+    client.contribute(&bob, &50_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(last_event::<u32>(&env, "contributor_slots_exhausted"), 2);
+    assert_eq!(client.slots_remaining(), Some(0));
+
+    // Newcomers are turned away; existing contributors may top up.
+    assert_eq!(
+        client
+            .try_contribute(&carol, &50_000, &0, &None, &false, &None, &None, &None)
+            .unwrap_err()
+            .unwrap(),
+        crate::ContractError::ContributorLimitReached
+    );
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+    client.contribute(&bob, &10_000, &0, &None, &false, &None, &None, &None);
+
+    // Reversing Alice's whole contribution frees her slot.
+    client.reverse_contribution(&alice, &600_000, &7);
+    assert_eq!(client.slots_remaining(), Some(1));
+    client.contribute(&carol, &50_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(client.slots_remaining(), Some(0));
+}
+
+#[test]
+fn test_reversal_rejected_after_withdrawal() {
+    let (env, client, _platform, token_address, alice) = setup_reversible_campaign();
+    let bob = Address::generate(&env);
+    let admin_client = token::StellarAssetClient::new(&env, &token_address);
+    admin_client.mint(&bob, &400_000);
+    client.contribute(&bob, &400_000, &0, &None, &false, &None, &None, &None);
+
+    env.ledger().set_timestamp(client.deadline() + 1);
+    client.withdraw();
+    assert_eq!(
+        client.try_reverse_contribution(&alice, &100_000, &7),
+        Err(Ok(crate::ContractError::CampaignNotActive))
+    );
+}
+
+#[test]
+fn test_reversal_rejected_for_non_platform_caller() {
+    use soroban_sdk::testutils::{MockAuth, MockAuthInvoke};
+    use soroban_sdk::IntoVal;
+
+    let (env, client, _platform, _token_address, alice) = setup_reversible_campaign();
+    let stranger = Address::generate(&env);
+
+    env.set_auths(&[]);
+    let result = client
+        .mock_auths(&[MockAuth {
+            address: &stranger,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "reverse_contribution",
+                args: (alice.clone(), 100_000i128, 7u32).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_reverse_contribution(&alice, &100_000, &7);
+    assert!(result.is_err());
+    assert_eq!(client.contribution(&alice), 600_000);
+}
+
+#[test]
+fn test_reversal_without_platform_config_rejected() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    client.contribute(&alice, &100_000, &0, &None, &false, &None, &None, &None);
+
+    assert_eq!(
+        client.try_reverse_contribution(&alice, &100_000, &7),
+        Err(Ok(crate::ContractError::NotAuthorized))
+    );
+}
+
+// ── Creator Alert Tests ─────────────────────────────────────────────────────
+
+#[test]
+fn test_deadline_alert_fires_once_within_window() {
+    use soroban_sdk::{testutils::Events, Symbol, TryFromVal};
+
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 10 * 3600;
+    let options = crate::CampaignOptions {
+        alert_window_secs: 3600,
+        allow_dust_goal: true,
+        ..Default::default()
+    };
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &Some(options),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    let deadline_alert = |env: &Env| {
+        env.events().all().iter().any(|(_, topics, data)| {
+            topics == campaign_topics(env, "alert")
+                && Symbol::try_from_val(env, &data) == Ok(Symbol::new(env, "deadline_near"))
+        })
+    };
+
+    client.contribute(&alice, &10_000, &0, &None, &false, &None, &None, &None);
+    assert!(!deadline_alert(&env));
+    assert_eq!(client.creator_alerts(), soroban_sdk::vec![&env]);
+
+    env.ledger().set_timestamp(deadline - 1800);
+    client.contribute(&alice, &10_000, &0, &None, &false, &None, &None, &None);
+    assert!(deadline_alert(&env));
+    assert_eq!(
+        client.creator_alerts(),
+        soroban_sdk::vec![&env, Symbol::new(&env, "deadline_near")]
+    );
+
+    env.ledger().set_timestamp(deadline - 900);
+    client.contribute(&alice, &10_000, &0, &None, &false, &None, &None, &None);
+    assert!(!deadline_alert(&env));
+    assert_eq!(
+        client.creator_alerts(),
+        soroban_sdk::vec![&env, Symbol::new(&env, "deadline_near")]
+    );
+}
+
+#[test]
+fn test_batch_reaching_goal_raises_alert() {
+    use soroban_sdk::{testutils::Events, Symbol, TryFromVal};
+
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 10 * 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let exchange = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &exchange, 1_000_000);
+    let entries = soroban_sdk::vec![
+        &env,
+        (Address::generate(&env), 500_000i128),
+        (Address::generate(&env), 500_000i128)
+    ];
+    client.contribute_batch(&exchange, &entries);
+
+    assert!(env.events().all().iter().any(|(_, topics, data)| {
+        topics == campaign_topics(&env, "alert")
+            && Symbol::try_from_val(&env, &data) == Ok(Symbol::new(&env, "goal_reached"))
+    }));
+    assert_eq!(
+        client.creator_alerts(),
+        soroban_sdk::vec![&env, Symbol::new(&env, "goal_reached")]
+    );
+}
+
+// ── Backer Profile Tests ────────────────────────────────────────────────────
+
+/// Assert that `addr`'s profile agrees with the individual getters.
+fn assert_profile_matches_getters(client: &CrowdfundContractClient, addr: &Address) {
+    let profile = client.backer_profile(addr);
+    assert_eq!(profile.contributed, client.contribution(addr));
+    assert_eq!(profile.pledged_outstanding, client.pledge_amount(addr));
+    assert_eq!(profile.refunds_received, client.refunded_amount(addr));
+    assert_eq!(profile.tier, client.get_user_tier(addr));
+    assert_eq!(profile.referral_tally, client.referral_tally(addr));
+}
+
+#[test]
+fn test_backer_profile_tracks_contribution_pledge_and_refund() {
+    let (env, client, _platform, _token_address, alice) = setup_reversible_campaign();
+    let start = env.ledger().timestamp();
+
+    let stranger = Address::generate(&env);
+    let empty = client.backer_profile(&stranger);
+    assert_eq!(
+        empty,
+        crate::BackerProfile {
+            contributed: 0,
+            pledged_outstanding: 0,
+            refunds_received: 0,
+            first_contribution_ts: 0,
+            tier: None,
+            referral_tally: 0,
+        }
+    );
+
+    client.add_reward_tier(
+        &client.campaign_info().creator,
+        &soroban_sdk::String::from_str(&env, "Gold"),
+        &500_000,
+        &0,
+    );
+    assert_profile_matches_getters(&client, &alice);
+    assert_eq!(client.backer_profile(&alice).first_contribution_ts, start);
+
+    env.ledger().set_timestamp(start + 60);
+    client.pledge(&alice, &20_000);
+    assert_profile_matches_getters(&client, &alice);
+    assert_eq!(client.backer_profile(&alice).pledged_outstanding, 20_000);
+
+    // A partial clawback drops the backer below the Gold tier.
+    client.reverse_contribution(&alice, &200_000, &1);
+    let profile = client.backer_profile(&alice);
+    assert_profile_matches_getters(&client, &alice);
+    assert_eq!(profile.contributed, 400_000);
+    assert_eq!(profile.tier, None);
+    assert_eq!(profile.first_contribution_ts, start);
+
+    env.ledger().set_timestamp(client.deadline() + 1);
+    client.claim_refund(&alice);
+    let profile = client.backer_profile(&alice);
+    assert_profile_matches_getters(&client, &alice);
+    assert_eq!(profile.contributed, 0);
+    assert_eq!(profile.pledged_outstanding, 0);
+    assert_eq!(profile.refunds_received, 400_000);
+    assert_eq!(profile.first_contribution_ts, start);
+}
+
+// ── Fee-On-Entry Tests ──────────────────────────────────────────────────────
+
+/// A campaign taking a 5% platform fee on entry, with a goal of 900_000.
+fn setup_fee_on_entry() -> (
+    Env,
+    CrowdfundContractClient<'static>,
+    Address,
+    Address,
+    Address,
+    Address,
+) {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let platform = Address::generate(&env);
+    let config = crate::PlatformConfig {
+        address: platform.clone(),
+        fee_bps: 500,
+        fee_mode: crate::FeeMode::OnContribute,
+    };
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &900_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &Some(config),
+        &dust_goal_options(),
+        &None,
+    );
+    (env, client, creator, token_address, admin, platform)
+}
+
+#[test]
+fn test_fee_on_entry_withdraw_pays_recorded_total() {
+    let (env, client, creator, token_address, admin, platform) = setup_fee_on_entry();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 600_000);
+    mint_to(&env, &token_address, &admin, &bob, 400_000);
+
+    assert_eq!(
+        client.contribute(&alice, &600_000, &0, &None, &false, &None, &None, &None),
+        570_000
+    );
+    assert_eq!(
+        last_event::<crate::events::EntryFeeEvent>(&env, "entry_fee_charged"),
+        crate::events::EntryFeeEvent {
+            contributor: alice.clone(),
+            gross: 600_000,
+            fee: 30_000,
+            net: 570_000,
+        }
+    );
+    client.contribute(&bob, &400_000, &0, &None, &false, &None, &None, &None);
+    assert_eq!(client.contribution(&alice), 570_000);
+    assert_eq!(client.total_raised(), 950_000);
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&platform), 50_000);
+
+    env.ledger().set_timestamp(client.deadline() + 1);
+    client.withdraw();
+    assert_eq!(token_client.balance(&creator), 10_000_000 + 950_000);
+    assert_eq!(token_client.balance(&platform), 50_000);
+}
+
+#[test]
+fn test_fee_on_entry_refund_returns_net() {
+    let (env, client, _creator, token_address, admin, platform) = setup_fee_on_entry();
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 200_000);
+    client.contribute(&alice, &200_000, &0, &None, &false, &None, &None, &None);
+
+    env.ledger().set_timestamp(client.deadline() + 1);
+    client.claim_refund(&alice);
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&alice), 190_000);
+    assert_eq!(token_client.balance(&platform), 10_000);
+    assert_eq!(client.refunded_amount(&alice), 190_000);
+}
+
+// ── Installment Plans ───────────────────────────────────────────────────────
+
+#[test]
+fn test_installments_collected_each_period() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 30 * 24 * 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    let week = 7 * 24 * 3600;
+    client.create_installment_plan(&alice, &10_000, &week);
+    let expiration_ledger = env.ledger().sequence() + 1_000_000;
+    token::Client::new(&env, &token_address).approve(
+        &alice,
+        &client.address,
+        &100_000,
+        &expiration_ledger,
+    );
+
+    let start = env.ledger().timestamp();
+    assert_eq!(client.execute_installments(&0, &10), 1);
+    assert_eq!(client.contribution(&alice), 10_000);
+    // Not due again until a full period has passed.
+    assert_eq!(client.execute_installments(&0, &10), 0);
+
+    env.ledger().set_timestamp(start + week);
+    assert_eq!(client.execute_installments(&0, &10), 1);
+    assert_eq!(client.contribution(&alice), 20_000);
+    assert_eq!(client.total_raised(), 20_000);
+    assert_eq!(
+        token::Client::new(&env, &token_address).balance(&alice),
+        80_000
+    );
+    let plan = client.installment_plan(&alice).unwrap();
+    assert_eq!(plan.next_due, start + 2 * week);
+    assert_eq!(plan.failures, 0);
+
+    client.cancel_installment_plan(&alice);
+    assert_eq!(client.installment_plan(&alice), None);
+    env.ledger().set_timestamp(start + 2 * week);
+    assert_eq!(client.execute_installments(&0, &10), 0);
+}
+
+#[test]
+fn test_installment_plan_dropped_after_two_failures() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 30 * 24 * 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    // Funded, but no allowance for the contract to pull from.
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 100_000);
+    client.create_installment_plan(&alice, &10_000, &3600);
+
+    assert_eq!(client.execute_installments(&0, &10), 0);
+    assert_eq!(
+        last_event::<crate::events::InstallmentFailedEvent>(&env, "installment_failed")
+            .consecutive_failures,
+        1
+    );
+    assert_eq!(client.installment_plan(&alice).unwrap().failures, 1);
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 1);
+    assert_eq!(client.execute_installments(&0, &10), 0);
+    assert_eq!(client.installment_plan(&alice), None);
+    assert_eq!(client.contribution(&alice), 0);
+}
+
+// ── State Digest ────────────────────────────────────────────────────────────
+
+#[test]
+fn test_state_digest_tracks_polled_fields() {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let initial = client.state_digest();
+    // Views leave it alone.
+    client.total_raised();
+    client.contributor_count();
+    let (digest, total_raised, status) = client.summary();
+    assert_eq!(digest, initial);
+    assert_eq!(total_raised, 0);
+    assert!(status == crate::Status::Active);
+    assert_eq!(client.state_digest(), initial);
+
+    let alice = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &alice, 10_000);
+    client.contribute(&alice, &10_000, &0, &None, &false, &None, &None, &None);
+    let contributed = client.state_digest();
+    assert_ne!(contributed, initial);
+    assert_eq!(client.summary().1, 10_000);
+
+    client.set_paused(&true);
+    let paused = client.state_digest();
+    assert_ne!(paused, contributed);
+
+    // Unpausing restores the earlier state, and with it the digest.
+    client.set_paused(&false);
+    assert_eq!(client.state_digest(), contributed);
+}
+
+// ── Voucher Tests ───────────────────────────────────────────────────────────
+
+fn setup_voucher_campaign() -> (
+    Env,
+    CrowdfundContractClient<'static>,
+    Address,
+    soroban_sdk::Bytes,
+    soroban_sdk::BytesN<32>,
+) {
+    let (env, client, creator, token_address, admin) = setup_env();
+    let deadline = env.ledger().timestamp() + 3600;
+    client.initialize(
+        &creator,
+        &token_address,
+        &1_000_000,
+        &2_000_000,
+        &deadline,
+        &1_000,
+        &None,
+        &dust_goal_options(),
+        &None,
+    );
+
+    let funder = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &funder, 50_000);
+    let code = soroban_sdk::Bytes::from_slice(&env, b"WELCOME-2026");
+    let code_hash: soroban_sdk::BytesN<32> = env.crypto().sha256(&code).into();
+    client.create_voucher(&funder, &50_000, &code_hash);
+
+    (env, client, funder, code, code_hash)
+}
+
+#[test]
+fn test_redeem_voucher_credits_redeemer() {
+    let (env, client, funder, code, code_hash) = setup_voucher_campaign();
+    let token_client = token::Client::new(&env, &client.token());
+    assert_eq!(token_client.balance(&client.address), 50_000);
+    assert_eq!(client.total_raised(), 0);
+
+    let newcomer = Address::generate(&env);
+    assert_eq!(client.redeem_voucher(&newcomer, &code), 50_000);
+    assert_eq!(
+        last_event::<crate::events::VoucherEvent>(&env, "voucher_redeemed"),
+        crate::events::VoucherEvent {
+            code_hash: code_hash.clone(),
+            account: newcomer.clone(),
+            amount: 50_000,
+        }
+    );
+    assert_eq!(client.contribution(&newcomer), 50_000);
+    assert_eq!(client.total_raised(), 50_000);
+    assert!(client.voucher(&code_hash).unwrap().spent);
+
+    // A spent voucher cannot be redeemed or reclaimed again.
+    let result = client.try_redeem_voucher(&Address::generate(&env), &code);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::InvalidVoucher
+    );
+    env.ledger().set_timestamp(client.deadline() + 1);
+    let result = client.try_reclaim_voucher(&code_hash);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::InvalidVoucher
+    );
+    assert_eq!(token_client.balance(&funder), 0);
+}
+
+#[test]
+fn test_redeem_voucher_pays_entry_fee_once() {
+    let (env, client, _creator, token_address, admin, platform) = setup_fee_on_entry();
+    let token_client = token::Client::new(&env, &token_address);
+
+    let backer = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &backer, 100_000);
+    client.contribute(&backer, &100_000, &0, &None, &false, &None, &None, &None);
+
+    let funder = Address::generate(&env);
+    mint_to(&env, &token_address, &admin, &funder, 50_000);
+    let code = soroban_sdk::Bytes::from_slice(&env, b"CHARITY-2026");
+    let code_hash: soroban_sdk::BytesN<32> = env.crypto().sha256(&code).into();
+    client.create_voucher(&funder, &50_000, &code_hash);
+
+    let newcomer = Address::generate(&env);
+    assert_eq!(client.redeem_voucher(&newcomer, &code), 47_500);
+
+    assert_eq!(token_client.balance(&funder), 0);
+    assert_eq!(token_client.balance(&newcomer), 0);
+    assert_eq!(token_client.balance(&platform), 5_000 + 2_500);
+    assert_eq!(token_client.balance(&client.address), 95_000 + 47_500);
+    assert_eq!(client.contribution(&newcomer), 47_500);
+    assert_eq!(client.total_raised(), 95_000 + 47_500);
+}
+
+#[test]
+fn test_set_token_rejected_while_vouchers_outstanding() {
+    let (env, client, _funder, _code, code_hash) = setup_voucher_campaign();
+    let old_token = client.token();
+
+    let new_token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    assert_eq!(
+        client.try_set_token(&new_token).unwrap_err().unwrap(),
+        crate::ContractError::CampaignHasFunds
+    );
+    assert_eq!(client.token(), old_token);
+    assert!(!client.voucher(&code_hash).unwrap().spent);
+}
+
+#[test]
+fn test_redeem_voucher_rejects_wrong_code() {
+    let (env, client, _funder, _code, code_hash) = setup_voucher_campaign();
+
+    let wrong = soroban_sdk::Bytes::from_slice(&env, b"welcome-2026");
+    let result = client.try_redeem_voucher(&Address::generate(&env), &wrong);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::InvalidVoucher
+    );
+    assert!(!client.voucher(&code_hash).unwrap().spent);
+}
+
+#[test]
+fn test_reclaim_voucher_after_deadline() {
+    let (env, client, funder, _code, code_hash) = setup_voucher_campaign();
+
+    let result = client.try_reclaim_voucher(&code_hash);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        crate::ContractError::CampaignStillActive
+    );
+
+    env.ledger().set_timestamp(client.deadline() + 1);
+    client.reclaim_voucher(&code_hash);
+    let token_client = token::Client::new(&env, &client.token());
+    assert_eq!(token_client.balance(&funder), 50_000);
+    assert_eq!(token_client.balance(&client.address), 0);
+    assert!(client.voucher(&code_hash).unwrap().spent);
+}